@@ -0,0 +1,274 @@
+use crate::application::structs::{
+    include_endpoint, CreateLocationRequest, LocationInclude, LocationQuery, LocationStruct,
+    UpdateLocationRequest,
+};
+use crate::application::Client;
+#[cfg(feature = "stream")]
+use crate::http::stream_pages;
+use crate::http::{collect_pages, paged_endpoint, EmptyBody};
+use crate::structs::{Page, PteroList, PteroObject};
+use reqwest::Method;
+
+impl Client {
+    /// Lists the locations on the first page of locations in the application. Use
+    /// [`Client::list_locations_all`] to list every location
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use pterodactyl_api::application::ClientBuilder;
+    ///
+    /// #[tokio::main(flavor = "current_thread")]
+    /// async fn main() {
+    ///     let client = ClientBuilder::new("https://pterodactyl.example.com", "your-api-key")
+    ///         .build();
+    ///
+    ///     match client.list_locations().await {
+    ///         Ok(locations) => {
+    ///             println!("Found {} locations:", locations.len());
+    ///             for location in locations {
+    ///                 println!("- {} ({})", location.short, location.id);
+    ///             }
+    ///         },
+    ///         Err(e) => eprintln!("Error listing locations: {}", e),
+    ///     }
+    /// }
+    /// ```
+    pub async fn list_locations(&self) -> crate::Result<Vec<LocationStruct>> {
+        self.http
+            .request::<PteroList<LocationStruct>>(Method::GET, "locations")
+            .await
+            .map(|locations| locations.data)
+    }
+
+    /// Gets a single page of the locations in the application, along with the pagination
+    /// metadata
+    pub async fn list_locations_page(&self, page: u32) -> crate::Result<Page<LocationStruct>> {
+        self.http
+            .request::<Page<LocationStruct>>(Method::GET, &paged_endpoint("locations", page))
+            .await
+    }
+
+    /// Lists every location in the application, fetching every page
+    pub async fn list_locations_all(&self) -> crate::Result<Vec<LocationStruct>> {
+        collect_pages(|page| self.list_locations_page(page)).await
+    }
+
+    /// Lazily streams every location in the application, fetching each page as it is needed
+    #[cfg(feature = "stream")]
+    pub fn list_locations_stream(
+        &self,
+    ) -> impl futures_core::Stream<Item = crate::Result<LocationStruct>> + '_ {
+        stream_pages(move |page| self.list_locations_page(page))
+    }
+
+    /// Gets a single page of the locations matching the given query, along with the pagination
+    /// metadata
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use pterodactyl_api::application::ClientBuilder;
+    /// use pterodactyl_api::application::structs::{LocationInclude, LocationQuery};
+    ///
+    /// #[tokio::main(flavor = "current_thread")]
+    /// async fn main() {
+    ///     let client = ClientBuilder::new("https://pterodactyl.example.com", "your-api-key")
+    ///         .build();
+    ///
+    ///     let query = LocationQuery::new().with_include(LocationInclude::Nodes);
+    ///     match client.list_locations_filtered(&query).await {
+    ///         Ok(page) => {
+    ///             for location in page.data {
+    ///                 let nodes = location.relationships.map(|r| r.nodes).unwrap_or_default();
+    ///                 println!("- {}: {} nodes", location.short, nodes.len());
+    ///             }
+    ///         },
+    ///         Err(e) => eprintln!("Error listing locations: {}", e),
+    ///     }
+    /// }
+    /// ```
+    pub async fn list_locations_filtered(
+        &self,
+        query: &LocationQuery,
+    ) -> crate::Result<Page<LocationStruct>> {
+        self.http
+            .request::<Page<LocationStruct>>(Method::GET, &query.endpoint("locations"))
+            .await
+    }
+
+    /// Lists every location matching the given query, fetching every page
+    pub async fn list_locations_filtered_all(
+        &self,
+        query: &LocationQuery,
+    ) -> crate::Result<Vec<LocationStruct>> {
+        self.http.collect_query("locations", query).await
+    }
+
+    /// Lazily streams the locations matching the given query, fetching each page when the stream
+    /// reaches it
+    #[cfg(feature = "stream")]
+    pub fn list_locations_filtered_stream(
+        &self,
+        query: LocationQuery,
+    ) -> impl futures_core::Stream<Item = crate::Result<LocationStruct>> + '_ {
+        self.http.stream_query("locations".to_owned(), query)
+    }
+
+    /// Gets a specific location by ID, including the given relationships
+    pub async fn get_location(
+        &self,
+        id: u32,
+        include: &[LocationInclude],
+    ) -> crate::Result<LocationStruct> {
+        self.http
+            .request::<PteroObject<LocationStruct>>(
+                Method::GET,
+                &include_endpoint(&format!("locations/{}", id), include),
+            )
+            .await
+            .map(|location| location.attributes)
+    }
+
+    /// Creates a new location
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use pterodactyl_api::application::{ClientBuilder, structs::CreateLocationRequest};
+    ///
+    /// #[tokio::main(flavor = "current_thread")]
+    /// async fn main() {
+    ///     let client = ClientBuilder::new("https://pterodactyl.example.com", "your-api-key")
+    ///         .build();
+    ///
+    ///     let location_request = CreateLocationRequest {
+    ///         short: "us.nyc".to_string(),
+    ///         long: Some("New York City".to_string()),
+    ///     };
+    ///
+    ///     match client.create_location(location_request).await {
+    ///         Ok(location) => println!("Location created: {} ({})", location.short, location.id),
+    ///         Err(e) => eprintln!("Error creating location: {}", e),
+    ///     }
+    /// }
+    /// ```
+    pub async fn create_location(
+        &self,
+        request: CreateLocationRequest,
+    ) -> crate::Result<LocationStruct> {
+        self.http
+            .request_with_body::<PteroObject<LocationStruct>, _>(
+                Method::POST,
+                "locations",
+                &request,
+            )
+            .await
+            .map(|response| response.attributes)
+    }
+
+    /// Updates the location with the specified ID
+    pub async fn update_location(
+        &self,
+        id: u32,
+        request: UpdateLocationRequest,
+    ) -> crate::Result<LocationStruct> {
+        self.http
+            .request_with_body::<PteroObject<LocationStruct>, _>(
+                Method::PATCH,
+                &format!("locations/{}", id),
+                &request,
+            )
+            .await
+            .map(|response| response.attributes)
+    }
+
+    /// Deletes the location with the specified ID. The panel refuses to delete locations which
+    /// still have nodes
+    pub async fn delete_location(&self, id: u32) -> crate::Result<()> {
+        self.http
+            .request::<EmptyBody>(Method::DELETE, &format!("locations/{}", id))
+            .await?;
+        Ok(())
+    }
+}
+
+#[cfg(all(test, feature = "mock-server"))]
+mod test {
+    use crate::application::structs::{
+        CreateLocationRequest, LocationFilter, LocationInclude, LocationQuery,
+        UpdateLocationRequest,
+    };
+    use crate::application::ClientBuilder;
+    use crate::mock::MockPanel;
+    use reqwest::StatusCode;
+
+    #[tokio::test]
+    async fn test_locations() {
+        let panel = MockPanel::start().await.unwrap();
+        let client = ClientBuilder::new(panel.url(), "key").build();
+        let node = client.list_nodes().await.unwrap().remove(0);
+
+        let query = LocationQuery::new()
+            .with_include(LocationInclude::Nodes)
+            .with_include(LocationInclude::Servers);
+        let location = client
+            .list_locations_filtered(&query)
+            .await
+            .unwrap()
+            .data
+            .remove(0);
+        assert_eq!(location.id, node.location_id);
+        let relationships = location.relationships.unwrap();
+        assert_eq!(relationships.nodes[0].id, node.id);
+        assert_eq!(relationships.servers[0].name, "Test Server");
+
+        let created = client
+            .create_location(CreateLocationRequest {
+                short: "eu.ams".to_owned(),
+                long: None,
+            })
+            .await
+            .unwrap();
+        let Err(crate::Error::Validation(errors)) = client
+            .create_location(CreateLocationRequest {
+                short: "eu.ams".to_owned(),
+                long: None,
+            })
+            .await
+        else {
+            panic!("creating a location with a taken short code should fail validation");
+        };
+        assert_eq!(errors.fields().collect::<Vec<_>>(), ["short"]);
+
+        let updated = client
+            .update_location(
+                created.id,
+                UpdateLocationRequest {
+                    short: None,
+                    long: Some("Amsterdam".to_owned()),
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(updated.short, "eu.ams");
+        assert_eq!(updated.long.as_deref(), Some("Amsterdam"));
+        let query = LocationQuery::new().with_filter(LocationFilter::Long, "amsterdam");
+        let page = client.list_locations_filtered(&query).await.unwrap();
+        assert_eq!(page.data.len(), 1);
+        let locations = client.list_locations_filtered_all(&query).await.unwrap();
+        assert_eq!(locations[0].id, created.id);
+        let location = client.get_location(created.id, &[]).await.unwrap();
+        assert!(location.relationships.is_none());
+
+        assert!(matches!(
+            client.delete_location(node.location_id).await,
+            Err(crate::Error::Api {
+                status: StatusCode::BAD_REQUEST,
+                ..
+            })
+        ));
+        client.delete_location(created.id).await.unwrap();
+        assert_eq!(client.list_locations().await.unwrap().len(), 1);
+    }
+}
@@ -1,9 +1,11 @@
 //! Pterodactyl Application API implementation, for all endpoints under `api/application`
 
-use crate::http::EmptyBody;
-use reqwest::Method;
-use std::sync::RwLock;
+use crate::http::{Api, HttpCore};
+use crate::{Diagnosis, KeyType};
+use std::sync::Arc;
 
+/// Location-related endpoints for the application API
+pub mod locations;
 /// Nest and egg related endpoints for the application API
 pub mod nests;
 /// Node-related endpoints for the application API
@@ -12,176 +14,41 @@ pub mod nodes;
 pub mod servers;
 /// Data structures for the application API
 pub mod structs;
+/// User-related endpoints for the application API
+pub mod users;
 
-/// The rate limits of the API key
-#[derive(Debug, Copy, Clone)]
-pub struct RateLimits {
-    /// The request limit per minute
-    pub limit: u32,
-    /// The number of requests remaining in this minute
-    pub limit_remaining: u32,
-}
+pub use crate::RateLimits;
 
-/// A Pterodactyl application client, to make requests to the Pterodactyl application API
-#[derive(Debug)]
+/// A Pterodactyl application client, to make requests to the Pterodactyl application API.
+/// Cloning the client is cheap, and the clones share their connection and rate limit state
+#[derive(Debug, Clone)]
 pub struct Client {
-    pub(crate) url: String,
-    pub(crate) client: reqwest::Client,
-    pub(crate) api_key: String,
-    pub(crate) rate_limits: RwLock<Option<RateLimits>>,
+    pub(crate) http: Arc<HttpCore>,
 }
 
 impl Client {
     /// Gets the rate limit information after the previous request
     pub fn get_rate_limits(&self) -> Option<RateLimits> {
-        *self.rate_limits.read().unwrap()
-    }
-
-    /// Makes a request to the Pterodactyl application API
-    pub(crate) async fn request<Response: crate::http::ResponseBody>(
-        &self,
-        method: Method,
-        endpoint: &str,
-    ) -> crate::Result<Response> {
-        self.request_with_body::<Response, _>(method, endpoint, EmptyBody)
-            .await
-    }
-
-    /// Makes a request with a body to the Pterodactyl application API
-    pub(crate) async fn request_with_body<
-        Response: crate::http::ResponseBody,
-        Body: crate::http::RequestBody,
-    >(
-        &self,
-        method: Method,
-        endpoint: &str,
-        body: Body,
-    ) -> crate::Result<Response> {
-        Response::decode(
-            self.get_response::<_, crate::http::NullErrorHandler>(method, endpoint, body)
-                .await?,
-        )
-        .await
-    }
-
-    #[allow(dead_code)]
-    pub(crate) async fn request_with_body_return_string<
-        Response: crate::http::ResponseBody,
-        Body: crate::http::RequestBody,
-    >(
-        &self,
-        method: Method,
-        endpoint: &str,
-        body: Body,
-    ) -> crate::Result<String> {
-        let re = self
-            .get_response::<_, crate::http::NullErrorHandler>(method, endpoint, body)
-            .await;
-        println!("re: {:?}", re);
-        Ok(re.unwrap().text().await.unwrap())
-    }
-
-    /// Gets a response from the Pterodactyl application API
-    pub(crate) async fn get_response<
-        Body: crate::http::RequestBody,
-        EHandler: crate::http::ErrorHandler,
-    >(
-        &self,
-        method: Method,
-        endpoint: &str,
-        body: Body,
-    ) -> crate::Result<reqwest::Response> {
-        let request = self
-            .client
-            .request(method, format!("{}{}", self.url, endpoint))
-            .header("Accept", "application/json")
-            .header("Content-Type", "application/json")
-            .header("Authorization", format!("Bearer {}", self.api_key));
-        let request = body.encode(request)?;
-
-        let response = request.send().await?;
-
-        if !response.status().is_success() {
-            let status = response.status();
-            println!("status: {:?}", status);
-            if let Some(err) = EHandler::get_error(response).await {
-                return Err(err);
-            }
-            return Err(Self::translate_error(status));
-        }
-
-        if let Some(limit) = response
-            .headers()
-            .get("x-ratelimit-limit")
-            .and_then(|header| header.to_str().ok())
-            .and_then(|header| header.parse().ok())
-        {
-            if let Some(limit_remaining) = response
-                .headers()
-                .get("x-ratelimit-remaining")
-                .and_then(|header| header.to_str().ok())
-                .and_then(|header| header.parse().ok())
-            {
-                *self.rate_limits.write().unwrap() = Some(RateLimits {
-                    limit,
-                    limit_remaining,
-                });
-            }
-        }
-
-        Ok(response)
+        self.http.rate_limits()
     }
 
-    /// Translates an HTTP status code to an error
-    fn translate_error(status: reqwest::StatusCode) -> crate::Error {
-        match status {
-            reqwest::StatusCode::FORBIDDEN => crate::Error::PermissionError,
-            reqwest::StatusCode::NOT_FOUND => crate::Error::ResourceNotFound,
-            reqwest::StatusCode::TOO_MANY_REQUESTS => crate::Error::RateLimit,
-            status => crate::Error::Http(status),
-        }
+    /// Checks that the panel is reachable and accepts the API key, and detects whether the key is
+    /// a client or application key, reporting the problems it found. Unlike other requests,
+    /// problems are reported in the diagnosis rather than as errors
+    pub async fn diagnose(&self) -> Diagnosis {
+        crate::diagnostics::diagnose(&self.http, KeyType::Application, "servers?per_page=1").await
     }
 }
 
-/// A builder for an application client
-#[derive(Debug)]
-pub struct ClientBuilder {
-    url: String,
-    client: Option<reqwest::Client>,
-    api_key: String,
-}
-
-impl ClientBuilder {
-    /// Creates a new application client builder, connecting to the given URL where a Pterodactyl server is
-    /// hosted, using the given API key for authentication
-    pub fn new(url: impl Into<String>, api_key: impl Into<String>) -> Self {
-        let mut url = url.into();
-        if !url.ends_with('/') {
-            url.push('/');
-        }
-        url.push_str("api/application/");
-        Self {
-            url,
-            client: None,
-            api_key: api_key.into(),
-        }
-    }
+impl Api for Client {
+    const PATH: &'static str = "api/application/";
 
-    /// Uses the specified [`reqwest::Client`] for requests instead of making a default one
-    pub fn with_client(self, client: reqwest::Client) -> Self {
+    fn from_http(http: HttpCore) -> Self {
         Self {
-            client: Some(client),
-            ..self
-        }
-    }
-
-    /// Builds a client
-    pub fn build(self) -> Client {
-        Client {
-            url: self.url,
-            client: self.client.unwrap_or_default(),
-            api_key: self.api_key,
-            rate_limits: RwLock::new(None),
+            http: Arc::new(http),
         }
     }
 }
+
+/// A builder for an application client
+pub type ClientBuilder = crate::ClientBuilder<Client>;
@@ -1,10 +1,14 @@
-use crate::application::structs::{EggStruct, NestStruct};
+use crate::application::structs::{EggQuery, EggStruct, NestStruct};
 use crate::application::Client;
-use crate::structs::{PteroList, PteroObject};
+#[cfg(feature = "stream")]
+use crate::http::stream_pages;
+use crate::http::{collect_pages, paged_endpoint};
+use crate::structs::{Page, PteroList, PteroObject};
 use reqwest::Method;
 
 impl Client {
-    /// Lists all nests in the application
+    /// Lists the nests on the first page of nests in the application. Use
+    /// [`Client::list_nests_all`] to list every nest
     ///
     /// # Example
     ///
@@ -28,11 +32,32 @@ impl Client {
     /// }
     /// ```
     pub async fn list_nests(&self) -> crate::Result<Vec<NestStruct>> {
-        self.request::<PteroList<NestStruct>>(Method::GET, "nests")
+        self.http
+            .request::<PteroList<NestStruct>>(Method::GET, "nests")
             .await
             .map(|nests| nests.data)
     }
 
+    /// Gets a single page of the nests in the application, along with the pagination metadata
+    pub async fn list_nests_page(&self, page: u32) -> crate::Result<Page<NestStruct>> {
+        self.http
+            .request::<Page<NestStruct>>(Method::GET, &paged_endpoint("nests", page))
+            .await
+    }
+
+    /// Lists every nest in the application, fetching every page
+    pub async fn list_nests_all(&self) -> crate::Result<Vec<NestStruct>> {
+        collect_pages(|page| self.list_nests_page(page)).await
+    }
+
+    /// Lazily streams every nest in the application, fetching each page as it is needed
+    #[cfg(feature = "stream")]
+    pub fn list_nests_stream(
+        &self,
+    ) -> impl futures_core::Stream<Item = crate::Result<NestStruct>> + '_ {
+        stream_pages(move |page| self.list_nests_page(page))
+    }
+
     /// Gets a specific nest by ID
     ///
     /// # Example
@@ -52,12 +77,14 @@ impl Client {
     /// }
     /// ```
     pub async fn get_nest(&self, id: u32) -> crate::Result<NestStruct> {
-        self.request::<PteroObject<NestStruct>>(Method::GET, &format!("nests/{}", id))
+        self.http
+            .request::<PteroObject<NestStruct>>(Method::GET, &format!("nests/{}", id))
             .await
             .map(|nest| nest.attributes)
     }
 
-    /// Lists all eggs in a specific nest
+    /// Lists the eggs on the first page of eggs in a specific nest. Use [`Client::list_eggs_all`]
+    /// to list every egg
     ///
     /// # Example
     ///
@@ -92,11 +119,87 @@ impl Client {
             }
             _ => format!("nests/{}/eggs", nest_id),
         };
-        self.request::<PteroList<EggStruct>>(Method::GET, &endpoint)
+        self.http
+            .request::<PteroList<EggStruct>>(Method::GET, &endpoint)
             .await
             .map(|eggs| eggs.data)
     }
 
+    /// Gets a single page of the eggs in a specific nest, along with the pagination metadata
+    pub async fn list_eggs_page(
+        &self,
+        nest_id: u32,
+        include: Option<Vec<&str>>,
+        page: u32,
+    ) -> crate::Result<Page<EggStruct>> {
+        let endpoint = match include {
+            Some(includes) if !includes.is_empty() => {
+                format!("nests/{}/eggs?include={}", nest_id, includes.join(","))
+            }
+            _ => format!("nests/{}/eggs", nest_id),
+        };
+        self.http
+            .request::<Page<EggStruct>>(Method::GET, &paged_endpoint(&endpoint, page))
+            .await
+    }
+
+    /// Lists every egg in a specific nest, fetching every page
+    pub async fn list_eggs_all(
+        &self,
+        nest_id: u32,
+        include: Option<Vec<&str>>,
+    ) -> crate::Result<Vec<EggStruct>> {
+        collect_pages(|page| self.list_eggs_page(nest_id, include.clone(), page)).await
+    }
+
+    /// Lazily streams every egg in a specific nest, fetching each page as it is needed
+    #[cfg(feature = "stream")]
+    pub fn list_eggs_stream<'a>(
+        &'a self,
+        nest_id: u32,
+        include: Option<Vec<&'a str>>,
+    ) -> impl futures_core::Stream<Item = crate::Result<EggStruct>> + 'a {
+        stream_pages(move |page| self.list_eggs_page(nest_id, include.clone(), page))
+    }
+
+    /// Gets a single page of the eggs in a specific nest matching the given query, along with the
+    /// pagination metadata
+    pub async fn list_eggs_filtered(
+        &self,
+        nest_id: u32,
+        query: &EggQuery,
+    ) -> crate::Result<Page<EggStruct>> {
+        self.http
+            .request::<Page<EggStruct>>(
+                Method::GET,
+                &query.endpoint(&format!("nests/{}/eggs", nest_id)),
+            )
+            .await
+    }
+
+    /// Lists every egg in a specific nest matching the given query, fetching every page
+    pub async fn list_eggs_filtered_all(
+        &self,
+        nest_id: u32,
+        query: &EggQuery,
+    ) -> crate::Result<Vec<EggStruct>> {
+        self.http
+            .collect_query(&format!("nests/{}/eggs", nest_id), query)
+            .await
+    }
+
+    /// Lazily streams the eggs in a specific nest matching the given query. Like
+    /// [`Client::list_eggs_filtered_all`], but only fetches a page once the stream reaches it
+    #[cfg(feature = "stream")]
+    pub fn list_eggs_filtered_stream(
+        &self,
+        nest_id: u32,
+        query: EggQuery,
+    ) -> impl futures_core::Stream<Item = crate::Result<EggStruct>> + '_ {
+        self.http
+            .stream_query(format!("nests/{}/eggs", nest_id), query)
+    }
+
     /// Gets a specific egg by nest ID and egg ID
     ///
     /// The `include` parameter allows you to include additional related resources in the response.
@@ -139,7 +242,8 @@ impl Client {
             _ => format!("nests/{}/eggs/{}", nest_id, egg_id),
         };
 
-        self.request::<PteroObject<EggStruct>>(Method::GET, &endpoint)
+        self.http
+            .request::<PteroObject<EggStruct>>(Method::GET, &endpoint)
             .await
             .map(|egg| egg.attributes)
     }
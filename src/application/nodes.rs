@@ -1,13 +1,17 @@
 use crate::application::structs::{
-    AllocationStruct, CreateAllocationRequest, CreateNodeRequest, NodeStruct, UpdateNodeRequest,
+    AllocationQuery, AllocationStruct, CreateAllocationRequest, CreateNodeRequest, NodeQuery,
+    NodeStruct, UpdateNodeRequest,
 };
 use crate::application::Client;
-use crate::http::EmptyBody;
-use crate::structs::{PteroList, PteroObject};
+#[cfg(feature = "stream")]
+use crate::http::stream_pages;
+use crate::http::{collect_pages, paged_endpoint, EmptyBody};
+use crate::structs::{Page, PteroList, PteroObject};
 use reqwest::Method;
 
 impl Client {
-    /// Lists all nodes in the application
+    /// Lists the nodes on the first page of nodes in the application. Use
+    /// [`Client::list_nodes_all`] to list every node
     ///
     /// # Example
     ///
@@ -31,11 +35,58 @@ impl Client {
     /// }
     /// ```
     pub async fn list_nodes(&self) -> crate::Result<Vec<NodeStruct>> {
-        self.request::<PteroList<NodeStruct>>(Method::GET, "nodes")
+        self.http
+            .request::<PteroList<NodeStruct>>(Method::GET, "nodes")
             .await
             .map(|nodes| nodes.data)
     }
 
+    /// Gets a single page of the nodes in the application, along with the pagination metadata
+    pub async fn list_nodes_page(&self, page: u32) -> crate::Result<Page<NodeStruct>> {
+        self.http
+            .request::<Page<NodeStruct>>(Method::GET, &paged_endpoint("nodes", page))
+            .await
+    }
+
+    /// Lists every node in the application, fetching every page
+    pub async fn list_nodes_all(&self) -> crate::Result<Vec<NodeStruct>> {
+        collect_pages(|page| self.list_nodes_page(page)).await
+    }
+
+    /// Lazily streams every node in the application, fetching each page as it is needed
+    #[cfg(feature = "stream")]
+    pub fn list_nodes_stream(
+        &self,
+    ) -> impl futures_core::Stream<Item = crate::Result<NodeStruct>> + '_ {
+        stream_pages(move |page| self.list_nodes_page(page))
+    }
+
+    /// Gets a single page of the nodes matching the given query, along with the pagination
+    /// metadata
+    pub async fn list_nodes_filtered(&self, query: &NodeQuery) -> crate::Result<Page<NodeStruct>> {
+        self.http
+            .request::<Page<NodeStruct>>(Method::GET, &query.endpoint("nodes"))
+            .await
+    }
+
+    /// Lists every node matching the given query, fetching every page
+    pub async fn list_nodes_filtered_all(
+        &self,
+        query: &NodeQuery,
+    ) -> crate::Result<Vec<NodeStruct>> {
+        self.http.collect_query("nodes", query).await
+    }
+
+    /// Lazily streams the nodes matching the given query, fetching each page when the stream
+    /// reaches it
+    #[cfg(feature = "stream")]
+    pub fn list_nodes_filtered_stream(
+        &self,
+        query: NodeQuery,
+    ) -> impl futures_core::Stream<Item = crate::Result<NodeStruct>> + '_ {
+        self.http.stream_query("nodes".to_owned(), query)
+    }
+
     /// Gets a specific node by ID
     ///
     /// # Example
@@ -55,7 +106,8 @@ impl Client {
     /// }
     /// ```
     pub async fn get_node(&self, id: u32) -> crate::Result<NodeStruct> {
-        self.request::<PteroObject<NodeStruct>>(Method::GET, &format!("nodes/{}", id))
+        self.http
+            .request::<PteroObject<NodeStruct>>(Method::GET, &format!("nodes/{}", id))
             .await
             .map(|node| node.attributes)
     }
@@ -98,7 +150,8 @@ impl Client {
     /// }
     /// ```
     pub async fn create_node(&self, request: CreateNodeRequest) -> crate::Result<NodeStruct> {
-        self.request_with_body::<PteroObject<NodeStruct>, _>(Method::POST, "nodes", &request)
+        self.http
+            .request_with_body::<PteroObject<NodeStruct>, _>(Method::POST, "nodes", &request)
             .await
             .map(|response| response.attributes)
     }
@@ -145,13 +198,14 @@ impl Client {
         id: u32,
         request: UpdateNodeRequest,
     ) -> crate::Result<NodeStruct> {
-        self.request_with_body::<PteroObject<NodeStruct>, _>(
-            Method::PATCH,
-            &format!("nodes/{}", id),
-            &request,
-        )
-        .await
-        .map(|response| response.attributes)
+        self.http
+            .request_with_body::<PteroObject<NodeStruct>, _>(
+                Method::PATCH,
+                &format!("nodes/{}", id),
+                &request,
+            )
+            .await
+            .map(|response| response.attributes)
     }
 
     /// Deletes a node with the specified ID
@@ -173,12 +227,14 @@ impl Client {
     /// }
     /// ```
     pub async fn delete_node(&self, id: u32) -> crate::Result<()> {
-        self.request::<EmptyBody>(Method::DELETE, &format!("nodes/{}", id))
+        self.http
+            .request::<EmptyBody>(Method::DELETE, &format!("nodes/{}", id))
             .await?;
         Ok(())
     }
 
-    /// Lists all allocations for a specific node
+    /// Lists the allocations on the first page of allocations for a specific node. Use
+    /// [`Client::list_node_allocations_all`] to list every allocation
     ///
     /// # Example
     ///
@@ -205,12 +261,83 @@ impl Client {
         &self,
         node_id: u32,
     ) -> crate::Result<Vec<AllocationStruct>> {
-        self.request::<PteroList<AllocationStruct>>(
-            Method::GET,
-            &format!("nodes/{}/allocations", node_id),
-        )
-        .await
-        .map(|allocations| allocations.data)
+        self.http
+            .request::<PteroList<AllocationStruct>>(
+                Method::GET,
+                &format!("nodes/{}/allocations", node_id),
+            )
+            .await
+            .map(|allocations| allocations.data)
+    }
+
+    /// Gets a single page of the allocations for a specific node, along with the pagination
+    /// metadata
+    pub async fn list_node_allocations_page(
+        &self,
+        node_id: u32,
+        page: u32,
+    ) -> crate::Result<Page<AllocationStruct>> {
+        self.http
+            .request::<Page<AllocationStruct>>(
+                Method::GET,
+                &paged_endpoint(&format!("nodes/{}/allocations", node_id), page),
+            )
+            .await
+    }
+
+    /// Lists every allocation for a specific node, fetching every page
+    pub async fn list_node_allocations_all(
+        &self,
+        node_id: u32,
+    ) -> crate::Result<Vec<AllocationStruct>> {
+        collect_pages(|page| self.list_node_allocations_page(node_id, page)).await
+    }
+
+    /// Lazily streams every allocation for a specific node, fetching each page as it is needed
+    #[cfg(feature = "stream")]
+    pub fn list_node_allocations_stream(
+        &self,
+        node_id: u32,
+    ) -> impl futures_core::Stream<Item = crate::Result<AllocationStruct>> + '_ {
+        stream_pages(move |page| self.list_node_allocations_page(node_id, page))
+    }
+
+    /// Gets a single page of the allocations for a specific node matching the given query, along
+    /// with the pagination metadata
+    pub async fn list_node_allocations_filtered(
+        &self,
+        node_id: u32,
+        query: &AllocationQuery,
+    ) -> crate::Result<Page<AllocationStruct>> {
+        self.http
+            .request::<Page<AllocationStruct>>(
+                Method::GET,
+                &query.endpoint(&format!("nodes/{}/allocations", node_id)),
+            )
+            .await
+    }
+
+    /// Lists every allocation for a specific node matching the given query, fetching every page
+    pub async fn list_node_allocations_filtered_all(
+        &self,
+        node_id: u32,
+        query: &AllocationQuery,
+    ) -> crate::Result<Vec<AllocationStruct>> {
+        self.http
+            .collect_query(&format!("nodes/{}/allocations", node_id), query)
+            .await
+    }
+
+    /// Lazily streams the allocations for a specific node matching the given query, fetching
+    /// each page when the stream reaches it
+    #[cfg(feature = "stream")]
+    pub fn list_node_allocations_filtered_stream(
+        &self,
+        node_id: u32,
+        query: AllocationQuery,
+    ) -> impl futures_core::Stream<Item = crate::Result<AllocationStruct>> + '_ {
+        self.http
+            .stream_query(format!("nodes/{}/allocations", node_id), query)
     }
 
     /// Creates a new allocation for a specific node
@@ -242,12 +369,13 @@ impl Client {
         node_id: u32,
         request: CreateAllocationRequest,
     ) -> crate::Result<()> {
-        self.request_with_body::<EmptyBody, _>(
-            Method::POST,
-            &format!("nodes/{}/allocations", node_id),
-            &request,
-        )
-        .await?;
+        self.http
+            .request_with_body::<EmptyBody, _>(
+                Method::POST,
+                &format!("nodes/{}/allocations", node_id),
+                &request,
+            )
+            .await?;
         Ok(())
     }
 
@@ -270,7 +398,8 @@ impl Client {
     /// }
     /// ```
     pub async fn delete_allocation(&self, allocation_id: u32) -> crate::Result<()> {
-        self.request::<EmptyBody>(Method::DELETE, &format!("allocations/{}", allocation_id))
+        self.http
+            .request::<EmptyBody>(Method::DELETE, &format!("allocations/{}", allocation_id))
             .await?;
         Ok(())
     }
@@ -1,15 +1,20 @@
 use std::collections::HashMap;
-use urlencoding::encode;
 
-use crate::application::structs::ServerStruct;
+use crate::application::structs::{
+    include_endpoint, BuildUpdate, CreateServerDatabaseRequest, DatabaseInclude,
+    ServerDatabaseStruct, ServerQuery, ServerStruct, UpdateServerDetailsRequest,
+};
 use crate::application::{structs::CreateServerRequest, structs::CreateServerResponse, Client};
-use crate::http::EmptyBody;
-use crate::structs::{PteroList, PteroObject};
+#[cfg(feature = "stream")]
+use crate::http::stream_pages;
+use crate::http::{collect_pages, paged_endpoint, EmptyBody};
+use crate::structs::{Page, PteroList, PteroObject};
 use reqwest::Method;
 use serde::{Deserialize, Serialize};
 
 impl Client {
-    /// Lists all servers in the application
+    /// Lists the servers on the first page of servers in the application. Use
+    /// [`Client::list_servers_all`] to list every server
     ///
     /// # Example
     ///
@@ -33,66 +38,81 @@ impl Client {
     /// }
     /// ```
     pub async fn list_servers(&self) -> crate::Result<Vec<ServerStruct>> {
-        self.request::<PteroList<ServerStruct>>(Method::GET, "servers")
+        self.http
+            .request::<PteroList<ServerStruct>>(Method::GET, "servers")
             .await
             .map(|servers| servers.data)
     }
 
-    /// Lists servers with query parameters (filters/pagination/include/sort)
+    /// Gets a single page of the servers in the application, along with the pagination metadata
+    pub async fn list_servers_page(&self, page: u32) -> crate::Result<Page<ServerStruct>> {
+        self.http
+            .request::<Page<ServerStruct>>(Method::GET, &paged_endpoint("servers", page))
+            .await
+    }
+
+    /// Lists every server in the application, fetching every page
+    pub async fn list_servers_all(&self) -> crate::Result<Vec<ServerStruct>> {
+        collect_pages(|page| self.list_servers_page(page)).await
+    }
+
+    /// Lazily streams every server in the application, fetching each page as it is needed
+    #[cfg(feature = "stream")]
+    pub fn list_servers_stream(
+        &self,
+    ) -> impl futures_core::Stream<Item = crate::Result<ServerStruct>> + '_ {
+        stream_pages(move |page| self.list_servers_page(page))
+    }
+
+    /// Gets a single page of the servers matching the given query, along with the pagination
+    /// metadata
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use pterodactyl_api::application::ClientBuilder;
+    /// use pterodactyl_api::application::structs::{ServerFilter, ServerQuery, ServerSort, SortDirection};
     ///
-    /// This wraps GET /api/application/servers with optional query parameters supported by
-    /// the latest Pterodactyl API, such as `filter[uuid]`, `filter[name]`, `filter[external_id]`,
-    /// `per_page`, `page`, `include`, and `sort`.
+    /// #[tokio::main(flavor = "current_thread")]
+    /// async fn main() {
+    ///     let client = ClientBuilder::new("https://pterodactyl.example.com", "your-api-key")
+    ///         .build();
+    ///
+    ///     let query = ServerQuery::new()
+    ///         .with_filter(ServerFilter::ExternalId, "customer-42")
+    ///         .with_sort(ServerSort::Id, SortDirection::Descending);
+    ///
+    ///     match client.list_servers_filtered(&query).await {
+    ///         Ok(page) => println!("Found {} servers", page.data.len()),
+    ///         Err(e) => eprintln!("Error listing servers: {}", e),
+    ///     }
+    /// }
+    /// ```
     pub async fn list_servers_filtered(
         &self,
-        filter_name: Option<&str>,
-        filter_uuid: Option<&str>,
-        filter_external_id: Option<&str>,
-        filter_image: Option<&str>,
-        include: Option<&[&str]>,
-        sort: Option<&str>,
-        per_page: Option<u32>,
-        page: Option<u32>,
-    ) -> crate::Result<Vec<ServerStruct>> {
-        let mut pairs: Vec<String> = Vec::new();
-
-        if let Some(v) = filter_name {
-            pairs.push(format!("{}={}", encode("filter[name]"), encode(v)));
-        }
-        if let Some(v) = filter_uuid {
-            pairs.push(format!("{}={}", encode("filter[uuid]"), encode(v)));
-        }
-        if let Some(v) = filter_external_id {
-            pairs.push(format!("{}={}", encode("filter[external_id]"), encode(v)));
-        }
-        if let Some(v) = filter_image {
-            pairs.push(format!("{}={}", encode("filter[image]"), encode(v)));
-        }
-        if let Some(v) = include {
-            if !v.is_empty() {
-                pairs.push(format!("{}={}", encode("include"), encode(&v.join(","))));
-            }
-        }
-        if let Some(v) = sort {
-            pairs.push(format!("{}={}", encode("sort"), encode(v)));
-        }
-        if let Some(v) = per_page {
-            pairs.push(format!("{}={}", encode("per_page"), encode(&v.to_string())));
-        }
-        if let Some(v) = page {
-            pairs.push(format!("{}={}", encode("page"), encode(&v.to_string())));
-        }
+        query: &ServerQuery,
+    ) -> crate::Result<Page<ServerStruct>> {
+        self.http
+            .request::<Page<ServerStruct>>(Method::GET, &query.endpoint("servers"))
+            .await
+    }
 
-        let qs = pairs.join("&");
-        let endpoint = if qs.is_empty() {
-            "servers".to_string()
-        } else {
-            format!("servers?{}", qs)
-        };
+    /// Lists every server matching the given query, fetching every page
+    pub async fn list_servers_filtered_all(
+        &self,
+        query: &ServerQuery,
+    ) -> crate::Result<Vec<ServerStruct>> {
+        self.http.collect_query("servers", query).await
+    }
 
-        self.request::<PteroList<ServerStruct>>(Method::GET, &endpoint)
-            .await
-            .map(|servers| servers.data)
+    /// Lazily streams the servers matching the given query, fetching each page when the stream
+    /// reaches it
+    #[cfg(feature = "stream")]
+    pub fn list_servers_filtered_stream(
+        &self,
+        query: ServerQuery,
+    ) -> impl futures_core::Stream<Item = crate::Result<ServerStruct>> + '_ {
+        self.http.stream_query("servers".to_owned(), query)
     }
 
     /// Gets a specific server by ID
@@ -114,7 +134,39 @@ impl Client {
     /// }
     /// ```
     pub async fn get_server(&self, id: u32) -> crate::Result<ServerStruct> {
-        self.request::<PteroObject<ServerStruct>>(Method::GET, &format!("servers/{}", id))
+        self.http
+            .request::<PteroObject<ServerStruct>>(Method::GET, &format!("servers/{}", id))
+            .await
+            .map(|server| server.attributes)
+    }
+
+    /// Gets a specific server by the external ID it was given when created or updated
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use pterodactyl_api::application::ClientBuilder;
+    ///
+    /// #[tokio::main(flavor = "current_thread")]
+    /// async fn main() {
+    ///     let client = ClientBuilder::new("https://pterodactyl.example.com", "your-api-key")
+    ///         .build();
+    ///
+    ///     match client.get_server_by_external_id("order-1234").await {
+    ///         Ok(server) => println!("Server: {} ({})", server.name, server.identifier),
+    ///         Err(e) => eprintln!("Error getting server: {}", e),
+    ///     }
+    /// }
+    /// ```
+    pub async fn get_server_by_external_id(
+        &self,
+        external_id: &str,
+    ) -> crate::Result<ServerStruct> {
+        self.http
+            .request::<PteroObject<ServerStruct>>(
+                Method::GET,
+                &format!("servers/external/{}", urlencoding::encode(external_id)),
+            )
             .await
             .map(|server| server.attributes)
     }
@@ -171,7 +223,8 @@ impl Client {
         &self,
         request: CreateServerRequest,
     ) -> crate::Result<CreateServerResponse> {
-        self.request_with_body::<CreateServerResponse, _>(Method::POST, "servers", &request)
+        self.http
+            .request_with_body::<CreateServerResponse, _>(Method::POST, "servers", &request)
             .await
             .map(|response| response)
     }
@@ -195,7 +248,8 @@ impl Client {
     /// }
     /// ```
     pub async fn delete_server(&self, id: u32) -> crate::Result<()> {
-        self.request::<EmptyBody>(Method::DELETE, &format!("servers/{}", id))
+        self.http
+            .request::<EmptyBody>(Method::DELETE, &format!("servers/{}", id))
             .await?;
         Ok(())
     }
@@ -221,56 +275,71 @@ impl Client {
     /// }
     /// ```
     pub async fn force_delete_server(&self, id: u32) -> crate::Result<()> {
-        self.request::<EmptyBody>(Method::DELETE, &format!("servers/{}/force", id))
+        self.http
+            .request::<EmptyBody>(Method::DELETE, &format!("servers/{}/force", id))
             .await?;
         Ok(())
     }
 
-    /// Updates server build limits (CPU/Memory/Disk/IO/Swap/Threads)
+    /// Updates server build limits (CPU/Memory/Disk/IO/Swap/Threads), keeping the feature limits
+    /// and allocations. Use [`Client::apply_build_update`] to change those too
     pub async fn update_server_build(
         &self,
         id: u32,
         limits: crate::application::structs::ServerLimits,
     ) -> crate::Result<()> {
-        #[derive(Serialize)]
-        struct UpdateBuildBody {
-            memory: u32,
-            swap: u32,
-            disk: u32,
-            io: u32,
-            cpu: u32,
-            allocation: u32,
-            feature_limits: crate::application::structs::ServerFeatureLimits,
-            #[serde(skip_serializing_if = "Option::is_none")]
-            threads: Option<u32>,
-            #[serde(skip_serializing_if = "Option::is_none")]
-            oom_disabled: Option<bool>,
-        }
-
-        // 获取当前服务器以填充必填但未变更的字段
         let current = self.get_server(id).await?;
-
-        let body = UpdateBuildBody {
-            memory: limits.memory,
-            swap: limits.swap,
-            disk: limits.disk,
-            io: limits.io,
-            cpu: limits.cpu,
-            allocation: current.allocation,
-            feature_limits: current.feature_limits,
-            threads: limits.threads,
-            oom_disabled: limits.oom_disabled,
-        };
-
-        self.request_with_body::<EmptyBody, _>(
-            Method::PATCH,
-            &format!("servers/{}/build", id),
-            &body,
-        )
-        .await?;
+        let body = BuildUpdate::new().with_limits(limits).fill(&current);
+        self.http
+            .request_with_body::<EmptyBody, _>(
+                Method::PATCH,
+                &format!("servers/{}/build", id),
+                &body,
+            )
+            .await?;
         Ok(())
     }
 
+    /// Applies a partial update of the build configuration of a server, filling the fields which
+    /// aren't set from the current server
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use pterodactyl_api::application::{ClientBuilder, structs::BuildUpdate};
+    ///
+    /// #[tokio::main(flavor = "current_thread")]
+    /// async fn main() {
+    ///     let client = ClientBuilder::new("https://pterodactyl.example.com", "your-api-key")
+    ///         .build();
+    ///
+    ///     let upgrade = BuildUpdate::new()
+    ///         .with_memory(8192)
+    ///         .with_allocation_limit(2)
+    ///         .with_added_allocation(42);
+    ///
+    ///     match client.apply_build_update(1, upgrade).await {
+    ///         Ok(server) => println!("Server upgraded to {} MB", server.limits.memory),
+    ///         Err(e) => eprintln!("Error updating server build: {}", e),
+    ///     }
+    /// }
+    /// ```
+    pub async fn apply_build_update(
+        &self,
+        id: u32,
+        update: BuildUpdate,
+    ) -> crate::Result<ServerStruct> {
+        let current = self.get_server(id).await?;
+        self.http
+            .request_with_body::<PteroObject<ServerStruct>, _>(
+                Method::PATCH,
+                &format!("servers/{}/build", id),
+                &update.fill(&current),
+            )
+            .await
+            .map(|response| response.attributes)
+    }
+
     /// Updates the startup variables for a server
     ///
     /// # Example
@@ -306,15 +375,73 @@ impl Client {
             image,
             skip_scripts,
         };
-        self.request_with_body::<EmptyBody, _>(
-            Method::PATCH,
-            &format!("servers/{}/startup", id),
-            &body,
-        )
-        .await?;
+        self.http
+            .request_with_body::<EmptyBody, _>(
+                Method::PATCH,
+                &format!("servers/{}/startup", id),
+                &body,
+            )
+            .await?;
         Ok(())
     }
 
+    /// Updates the name, owner, external ID and description of a server
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use pterodactyl_api::application::{ClientBuilder, structs::UpdateServerDetailsRequest};
+    ///
+    /// #[tokio::main(flavor = "current_thread")]
+    /// async fn main() {
+    ///     let client = ClientBuilder::new("https://pterodactyl.example.com", "your-api-key")
+    ///         .build();
+    ///
+    ///     let details = UpdateServerDetailsRequest {
+    ///         name: "Survival".to_string(),
+    ///         user: 1,
+    ///         external_id: Some("order-1234".to_string()),
+    ///         description: None,
+    ///     };
+    ///
+    ///     match client.update_server_details(1, details).await {
+    ///         Ok(server) => println!("Server updated: {} ({})", server.name, server.identifier),
+    ///         Err(e) => eprintln!("Error updating server: {}", e),
+    ///     }
+    /// }
+    /// ```
+    pub async fn update_server_details(
+        &self,
+        id: u32,
+        request: UpdateServerDetailsRequest,
+    ) -> crate::Result<ServerStruct> {
+        self.http
+            .request_with_body::<PteroObject<ServerStruct>, _>(
+                Method::PATCH,
+                &format!("servers/{}/details", id),
+                &request,
+            )
+            .await
+            .map(|response| response.attributes)
+    }
+
+    /// Transfers the ownership of a server to the user with the given ID, keeping the other
+    /// details of the server
+    pub async fn transfer_server_ownership(
+        &self,
+        id: u32,
+        user: u32,
+    ) -> crate::Result<ServerStruct> {
+        let current = self.get_server(id).await?;
+        let request = UpdateServerDetailsRequest {
+            name: current.name,
+            user,
+            external_id: current.external_id,
+            description: Some(current.description).filter(|description| !description.is_empty()),
+        };
+        self.update_server_details(id, request).await
+    }
+
     /// Suspends a server with the specified ID
     ///
     /// # Example
@@ -334,7 +461,8 @@ impl Client {
     /// }
     /// ```
     pub async fn suspend_server(&self, id: u32) -> crate::Result<()> {
-        self.request::<EmptyBody>(Method::POST, &format!("servers/{}/suspend", id))
+        self.http
+            .request::<EmptyBody>(Method::POST, &format!("servers/{}/suspend", id))
             .await?;
         Ok(())
     }
@@ -358,7 +486,155 @@ impl Client {
     /// }
     /// ```
     pub async fn resume_server(&self, id: u32) -> crate::Result<()> {
-        self.request::<EmptyBody>(Method::POST, &format!("servers/{}/resume", id))
+        self.http
+            .request::<EmptyBody>(Method::POST, &format!("servers/{}/resume", id))
+            .await?;
+        Ok(())
+    }
+
+    /// Reinstalls a server with the specified ID, running the install script of its egg again
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use pterodactyl_api::application::ClientBuilder;
+    ///
+    /// #[tokio::main(flavor = "current_thread")]
+    /// async fn main() {
+    ///     let client = ClientBuilder::new("https://pterodactyl.example.com", "your-api-key")
+    ///         .build();
+    ///
+    ///     match client.reinstall_server(1).await {
+    ///         Ok(_) => println!("Server reinstall started"),
+    ///         Err(e) => eprintln!("Error reinstalling server: {}", e),
+    ///     }
+    /// }
+    /// ```
+    pub async fn reinstall_server(&self, id: u32) -> crate::Result<()> {
+        self.http
+            .request::<EmptyBody>(Method::POST, &format!("servers/{}/reinstall", id))
+            .await?;
+        Ok(())
+    }
+
+    /// Lists the databases of a server, including the given relationships
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use pterodactyl_api::application::{ClientBuilder, structs::DatabaseInclude};
+    ///
+    /// #[tokio::main(flavor = "current_thread")]
+    /// async fn main() {
+    ///     let client = ClientBuilder::new("https://pterodactyl.example.com", "your-api-key")
+    ///         .build();
+    ///
+    ///     match client.list_server_databases(1, &[DatabaseInclude::Host]).await {
+    ///         Ok(databases) => {
+    ///             println!("Found {} databases:", databases.len());
+    ///             for database in databases {
+    ///                 println!("- {} ({})", database.database, database.id);
+    ///             }
+    ///         },
+    ///         Err(e) => eprintln!("Error listing databases: {}", e),
+    ///     }
+    /// }
+    /// ```
+    pub async fn list_server_databases(
+        &self,
+        id: u32,
+        include: &[DatabaseInclude],
+    ) -> crate::Result<Vec<ServerDatabaseStruct>> {
+        self.http
+            .request::<PteroList<ServerDatabaseStruct>>(
+                Method::GET,
+                &include_endpoint(&format!("servers/{}/databases", id), include),
+            )
+            .await
+            .map(|databases| databases.data)
+    }
+
+    /// Gets a specific database of a server, including the given relationships
+    pub async fn get_server_database(
+        &self,
+        id: u32,
+        database_id: u32,
+        include: &[DatabaseInclude],
+    ) -> crate::Result<ServerDatabaseStruct> {
+        self.http
+            .request::<PteroObject<ServerDatabaseStruct>>(
+                Method::GET,
+                &include_endpoint(
+                    &format!("servers/{}/databases/{}", id, database_id),
+                    include,
+                ),
+            )
+            .await
+            .map(|database| database.attributes)
+    }
+
+    /// Creates a database on a server
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use pterodactyl_api::application::{ClientBuilder, structs::CreateServerDatabaseRequest};
+    ///
+    /// #[tokio::main(flavor = "current_thread")]
+    /// async fn main() {
+    ///     let client = ClientBuilder::new("https://pterodactyl.example.com", "your-api-key")
+    ///         .build();
+    ///
+    ///     let database_request = CreateServerDatabaseRequest {
+    ///         database: "players".to_string(),
+    ///         remote: "%".to_string(),
+    ///         host: 1,
+    ///     };
+    ///
+    ///     match client.create_server_database(1, database_request).await {
+    ///         Ok(database) => println!("Database created: {}", database.database),
+    ///         Err(e) => eprintln!("Error creating database: {}", e),
+    ///     }
+    /// }
+    /// ```
+    pub async fn create_server_database(
+        &self,
+        id: u32,
+        request: CreateServerDatabaseRequest,
+    ) -> crate::Result<ServerDatabaseStruct> {
+        self.http
+            .request_with_body::<PteroObject<ServerDatabaseStruct>, _>(
+                Method::POST,
+                &format!("servers/{}/databases", id),
+                &request,
+            )
+            .await
+            .map(|response| response.attributes)
+    }
+
+    /// Resets the password of a database of a server. Use [`Client::get_server_database`] with
+    /// [`DatabaseInclude::Password`] to read the new password
+    pub async fn reset_server_database_password(
+        &self,
+        id: u32,
+        database_id: u32,
+    ) -> crate::Result<()> {
+        self.http
+            .request::<EmptyBody>(
+                Method::POST,
+                &format!("servers/{}/databases/{}/reset-password", id, database_id),
+            )
+            .await?;
+        Ok(())
+    }
+
+    /// Deletes a database of a server
+    pub async fn delete_server_database(&self, id: u32, database_id: u32) -> crate::Result<()> {
+        self.http
+            .request::<EmptyBody>(
+                Method::DELETE,
+                &format!("servers/{}/databases/{}", id, database_id),
+            )
             .await?;
         Ok(())
     }
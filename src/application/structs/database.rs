@@ -0,0 +1,76 @@
+use crate::Secret;
+use serde::{Deserialize, Serialize};
+
+/// Represents a database of a server in the application API
+#[derive(Debug, Deserialize, Clone, Serialize)]
+pub struct ServerDatabaseStruct {
+    /// The database's ID
+    pub id: u32,
+    /// The ID of the server the database belongs to
+    pub server: u32,
+    /// The ID of the database host the database is on
+    pub host: u32,
+    /// The database's name
+    pub database: String,
+    /// The username required to login to the database
+    pub username: String,
+    /// Where the database accepts connections from
+    pub remote: String,
+    /// The maximum number of connections to the database at a time
+    #[serde(default)]
+    pub max_connections: Option<u32>,
+    /// The database's created at timestamp
+    pub created_at: String,
+    /// The database's updated at timestamp
+    pub updated_at: String,
+    /// The database's relationships (only included when requested)
+    #[serde(default)]
+    pub relationships: Option<ServerDatabaseRelationships>,
+}
+
+/// Represents a server database's relationships
+#[derive(Debug, Deserialize, Clone, Serialize, Default)]
+pub struct ServerDatabaseRelationships {
+    /// The password required to login to the database
+    #[serde(
+        default,
+        deserialize_with = "crate::client::databases::deserialize_password"
+    )]
+    pub password: Option<Secret>,
+    /// The database host the database is on
+    #[serde(default, deserialize_with = "crate::structs::ptero_object")]
+    pub host: Option<DatabaseHostStruct>,
+}
+
+/// Represents a database host in the application API
+#[derive(Debug, Deserialize, Clone, Serialize)]
+pub struct DatabaseHostStruct {
+    /// The database host's ID
+    pub id: u32,
+    /// The database host's name
+    pub name: String,
+    /// The database host's address
+    pub host: String,
+    /// The database host's port
+    pub port: u16,
+    /// The username the panel uses to manage databases on the host
+    pub username: String,
+    /// The ID of the node the database host is linked to (if any)
+    #[serde(default)]
+    pub node: Option<u32>,
+    /// The database host's created at timestamp
+    pub created_at: String,
+    /// The database host's updated at timestamp
+    pub updated_at: String,
+}
+
+/// Request body for creating a database on a server
+#[derive(Debug, Serialize)]
+pub struct CreateServerDatabaseRequest {
+    /// The database's name, which the panel prefixes with the server's ID
+    pub database: String,
+    /// Where the database accepts connections from, such as `%` for anywhere
+    pub remote: String,
+    /// The ID of the database host to create the database on
+    pub host: u32,
+}
@@ -0,0 +1,53 @@
+use crate::application::structs::{NodeStruct, ServerStruct};
+use serde::{Deserialize, Serialize};
+
+/// Represents a location in the application API, which groups nodes
+#[derive(Debug, Deserialize, Clone, Serialize)]
+pub struct LocationStruct {
+    /// The location's ID
+    pub id: u32,
+    /// The location's short code, such as `us.nyc`
+    pub short: String,
+    /// The location's long description
+    #[serde(default)]
+    pub long: Option<String>,
+    /// The location's created at timestamp
+    pub created_at: String,
+    /// The location's updated at timestamp
+    pub updated_at: String,
+    /// The location's relationships (only included when requested)
+    #[serde(default)]
+    pub relationships: Option<LocationRelationships>,
+}
+
+/// Represents a location's relationships
+#[derive(Debug, Deserialize, Clone, Serialize, Default)]
+pub struct LocationRelationships {
+    /// The nodes in the location
+    #[serde(default, deserialize_with = "crate::structs::ptero_list")]
+    pub nodes: Vec<NodeStruct>,
+    /// The servers on the nodes in the location
+    #[serde(default, deserialize_with = "crate::structs::ptero_list")]
+    pub servers: Vec<ServerStruct>,
+}
+
+/// Request body for creating a new location
+#[derive(Debug, Serialize)]
+pub struct CreateLocationRequest {
+    /// The location's short code
+    pub short: String,
+    /// The location's long description (optional)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub long: Option<String>,
+}
+
+/// Request body for updating a location
+#[derive(Debug, Serialize)]
+pub struct UpdateLocationRequest {
+    /// The location's short code (optional)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub short: Option<String>,
+    /// The location's long description (optional)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub long: Option<String>,
+}
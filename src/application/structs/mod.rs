@@ -1,14 +1,18 @@
 // Re-export all structs from submodules
 mod allocation;
+mod database;
+mod location;
 mod nest;
 mod node;
+mod query;
 mod server;
+mod user;
 mod utils;
 
 // Server-related structs
 pub use server::{
-    CreateServerRequest, CreateServerResponse, ServerContainer, ServerFeatureLimits, ServerLimits,
-    ServerStruct,
+    BuildUpdate, CreateServerRequest, CreateServerResponse, ServerContainer, ServerFeatureLimits,
+    ServerLimits, ServerStruct, UpdateServerDetailsRequest,
 };
 
 // Node-related structs
@@ -20,8 +24,31 @@ pub use nest::{
     EggVariablesList, NestStruct, NullResource,
 };
 
+// User-related structs
+pub use user::{CreateUserRequest, UpdateUserRequest, UserRelationships, UserStruct};
+
+// Location-related structs
+pub use location::{
+    CreateLocationRequest, LocationRelationships, LocationStruct, UpdateLocationRequest,
+};
+
+// Server database-related structs
+pub use database::{
+    CreateServerDatabaseRequest, DatabaseHostStruct, ServerDatabaseRelationships,
+    ServerDatabaseStruct,
+};
+
 // Allocation-related structs
 pub use allocation::{AllocationSettings, AllocationStruct, CreateAllocationRequest};
 
+// Query builders for listing resources
+pub(crate) use query::include_endpoint;
+pub use query::{
+    AllocationFilter, AllocationInclude, AllocationQuery, DatabaseInclude, EggInclude, EggQuery,
+    ListQuery, LocationFilter, LocationInclude, LocationQuery, LocationSort, NodeFilter,
+    NodeInclude, NodeQuery, NodeSort, QueryParam, ServerFilter, ServerInclude, ServerQuery,
+    ServerSort, SortDirection, UserFilter, UserInclude, UserQuery, UserSort,
+};
+
 // Utility functions
 pub use utils::deserialize_installed;
@@ -0,0 +1,421 @@
+use crate::http::QueryString;
+use std::convert::Infallible;
+
+/// A parameter of a [`ListQuery`], such as a filter, sort key or relationship to include
+pub trait QueryParam: Copy {
+    /// The name of the parameter in the API
+    fn name(self) -> &'static str;
+}
+
+impl QueryParam for Infallible {
+    fn name(self) -> &'static str {
+        match self {}
+    }
+}
+
+/// The direction to sort a list in
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone, Default)]
+pub enum SortDirection {
+    /// Sort from lowest to highest
+    #[default]
+    Ascending,
+    /// Sort from highest to lowest
+    Descending,
+}
+
+/// The parameters to list resources through the application API, with typed filters `F`, sort
+/// keys `S` and relationships to include `I`
+///
+/// ```
+/// # use pterodactyl_api::application::structs::{
+/// #     ServerFilter, ServerInclude, ServerQuery, ServerSort, SortDirection,
+/// # };
+/// let query = ServerQuery::new()
+///     .with_filter(ServerFilter::Name, "lobby")
+///     .with_sort(ServerSort::Id, SortDirection::Descending)
+///     .with_include(ServerInclude::Allocations)
+///     .with_per_page(100);
+/// ```
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub struct ListQuery<F, S, I> {
+    filters: Vec<(F, String)>,
+    sort: Option<(S, SortDirection)>,
+    include: Vec<I>,
+    per_page: Option<u32>,
+    page: Option<u32>,
+}
+
+impl<F, S, I> Default for ListQuery<F, S, I> {
+    fn default() -> Self {
+        ListQuery {
+            filters: Vec::new(),
+            sort: None,
+            include: Vec::new(),
+            per_page: None,
+            page: None,
+        }
+    }
+}
+
+impl<F, S, I> ListQuery<F, S, I>
+where
+    F: QueryParam + PartialEq,
+    S: QueryParam,
+    I: QueryParam + PartialEq,
+{
+    /// Creates the default parameters, which list the first page without filters
+    pub fn new() -> Self {
+        ListQuery::default()
+    }
+
+    /// Only lists resources matching the given filter. Setting the same filter again replaces
+    /// the previous value
+    pub fn with_filter(mut self, filter: F, value: impl Into<String>) -> Self {
+        self.filters.retain(|(key, _)| *key != filter);
+        self.filters.push((filter, value.into()));
+        self
+    }
+
+    /// Sorts the list by the given key in the given direction
+    pub fn with_sort(self, sort: S, direction: SortDirection) -> Self {
+        ListQuery {
+            sort: Some((sort, direction)),
+            ..self
+        }
+    }
+
+    /// Includes the given relationship in each resource
+    pub fn with_include(mut self, include: I) -> Self {
+        if !self.include.contains(&include) {
+            self.include.push(include);
+        }
+        self
+    }
+
+    /// Sets the maximum number of resources on each page
+    pub fn with_per_page(self, per_page: u32) -> Self {
+        ListQuery {
+            per_page: Some(per_page),
+            ..self
+        }
+    }
+
+    /// Sets which page to get, starting from 1
+    pub fn with_page(self, page: u32) -> Self {
+        ListQuery {
+            page: Some(page),
+            ..self
+        }
+    }
+
+    pub(crate) fn endpoint(&self, path: &str) -> String {
+        let mut query = QueryString::default();
+        for (filter, value) in &self.filters {
+            query.push(format!("filter[{}]", filter.name()), value);
+        }
+        if let Some((sort, direction)) = self.sort {
+            match direction {
+                SortDirection::Ascending => query.push("sort", sort.name()),
+                SortDirection::Descending => query.push("sort", format!("-{}", sort.name())),
+            }
+        }
+        if !self.include.is_empty() {
+            let include = self
+                .include
+                .iter()
+                .map(|include| include.name())
+                .collect::<Vec<_>>();
+            query.push("include", include.join(","));
+        }
+        if let Some(per_page) = self.per_page {
+            query.push("per_page", per_page);
+        }
+        if let Some(page) = self.page {
+            query.push("page", page);
+        }
+        query.endpoint(path)
+    }
+}
+
+/// Appends the relationships to include to the endpoint of a single resource
+pub(crate) fn include_endpoint<I: QueryParam>(path: &str, include: &[I]) -> String {
+    let mut query = QueryString::default();
+    if !include.is_empty() {
+        let include = include
+            .iter()
+            .map(|include| include.name())
+            .collect::<Vec<_>>();
+        query.push("include", include.join(","));
+    }
+    query.endpoint(path)
+}
+
+macro_rules! query_params {
+    ($(#[$meta:meta])* $name:ident { $($(#[$variant_meta:meta])* $variant:ident => $param:literal),* $(,)? }) => {
+        $(#[$meta])*
+        #[derive(Debug, PartialEq, Eq, Hash, Copy, Clone)]
+        pub enum $name {
+            $(
+            $(#[$variant_meta])*
+            $variant,
+            )*
+        }
+
+        impl QueryParam for $name {
+            fn name(self) -> &'static str {
+                match self {
+                    $($name::$variant => $param,)*
+                }
+            }
+        }
+    };
+}
+
+query_params!(
+    /// The filters for listing servers
+    ServerFilter {
+        /// Filter by server name
+        Name => "name",
+        /// Filter by server UUID
+        Uuid => "uuid",
+        /// Filter by the short server identifier
+        UuidShort => "uuidShort",
+        /// Filter by external ID
+        ExternalId => "external_id",
+        /// Filter by docker image
+        Image => "image",
+        /// Filter by server description
+        Description => "description",
+    }
+);
+
+query_params!(
+    /// The keys to sort servers by
+    ServerSort {
+        /// Sort by server ID
+        Id => "id",
+        /// Sort by server UUID
+        Uuid => "uuid",
+    }
+);
+
+query_params!(
+    /// The relationships which can be included when listing servers
+    ServerInclude {
+        /// The network allocations of the server
+        Allocations => "allocations",
+        /// The owner of the server
+        User => "user",
+        /// The subusers of the server
+        Subusers => "subusers",
+        /// The nest of the server
+        Nest => "nest",
+        /// The egg of the server
+        Egg => "egg",
+        /// The startup variables of the server
+        Variables => "variables",
+        /// The location of the server
+        Location => "location",
+        /// The node of the server
+        Node => "node",
+        /// The databases of the server
+        Databases => "databases",
+    }
+);
+
+query_params!(
+    /// The filters for listing nodes
+    NodeFilter {
+        /// Filter by node UUID
+        Uuid => "uuid",
+        /// Filter by node name
+        Name => "name",
+        /// Filter by fully qualified domain name
+        Fqdn => "fqdn",
+        /// Filter by daemon token ID
+        DaemonTokenId => "daemon_token_id",
+    }
+);
+
+query_params!(
+    /// The keys to sort nodes by
+    NodeSort {
+        /// Sort by node ID
+        Id => "id",
+        /// Sort by node UUID
+        Uuid => "uuid",
+        /// Sort by memory limit
+        Memory => "memory",
+        /// Sort by disk space
+        Disk => "disk",
+    }
+);
+
+query_params!(
+    /// The relationships which can be included when listing nodes
+    NodeInclude {
+        /// The network allocations of the node
+        Allocations => "allocations",
+        /// The location of the node
+        Location => "location",
+        /// The servers on the node
+        Servers => "servers",
+    }
+);
+
+query_params!(
+    /// The filters for listing allocations
+    AllocationFilter {
+        /// Filter by IP address
+        Ip => "ip",
+        /// Filter by port
+        Port => "port",
+        /// Filter by IP alias
+        IpAlias => "ip_alias",
+        /// Filter by the ID of the server the allocation is assigned to
+        ServerId => "server_id",
+    }
+);
+
+query_params!(
+    /// The relationships which can be included when listing allocations
+    AllocationInclude {
+        /// The node of the allocation
+        Node => "node",
+        /// The server the allocation is assigned to
+        Server => "server",
+    }
+);
+
+query_params!(
+    /// The relationships which can be included when listing eggs
+    EggInclude {
+        /// The nest of the egg
+        Nest => "nest",
+        /// The servers using the egg
+        Servers => "servers",
+        /// The configuration of the egg
+        Config => "config",
+        /// The install script of the egg
+        Script => "script",
+        /// The variables of the egg
+        Variables => "variables",
+    }
+);
+
+query_params!(
+    /// The filters for listing users
+    UserFilter {
+        /// Filter by email address
+        Email => "email",
+        /// Filter by username
+        Username => "username",
+        /// Filter by user UUID
+        Uuid => "uuid",
+        /// Filter by external ID
+        ExternalId => "external_id",
+    }
+);
+
+query_params!(
+    /// The keys to sort users by
+    UserSort {
+        /// Sort by user ID
+        Id => "id",
+        /// Sort by user UUID
+        Uuid => "uuid",
+    }
+);
+
+query_params!(
+    /// The relationships which can be included when listing users
+    UserInclude {
+        /// The servers owned by the user
+        Servers => "servers",
+    }
+);
+
+query_params!(
+    /// The filters for listing locations
+    LocationFilter {
+        /// Filter by the short code of the location
+        Short => "short",
+        /// Filter by the long description of the location
+        Long => "long",
+    }
+);
+
+query_params!(
+    /// The keys to sort locations by
+    LocationSort {
+        /// Sort by location ID
+        Id => "id",
+    }
+);
+
+query_params!(
+    /// The relationships which can be included when listing locations
+    LocationInclude {
+        /// The nodes in the location
+        Nodes => "nodes",
+        /// The servers on the nodes in the location
+        Servers => "servers",
+    }
+);
+
+query_params!(
+    /// The relationships which can be included when getting server databases
+    DatabaseInclude {
+        /// The password of the database
+        Password => "password",
+        /// The database host the database is on
+        Host => "host",
+    }
+);
+
+/// The parameters to list servers
+pub type ServerQuery = ListQuery<ServerFilter, ServerSort, ServerInclude>;
+
+/// The parameters to list nodes
+pub type NodeQuery = ListQuery<NodeFilter, NodeSort, NodeInclude>;
+
+/// The parameters to list users
+pub type UserQuery = ListQuery<UserFilter, UserSort, UserInclude>;
+
+/// The parameters to list locations
+pub type LocationQuery = ListQuery<LocationFilter, LocationSort, LocationInclude>;
+
+/// The parameters to list the allocations of a node, which cannot be sorted
+pub type AllocationQuery = ListQuery<AllocationFilter, Infallible, AllocationInclude>;
+
+/// The parameters to list the eggs of a nest, which cannot be filtered or sorted
+pub type EggQuery = ListQuery<Infallible, Infallible, EggInclude>;
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_query_endpoint() {
+        assert_eq!(ServerQuery::new().endpoint("servers"), "servers");
+        assert_eq!(
+            ServerQuery::new()
+                .with_filter(ServerFilter::Name, "lobby")
+                .with_filter(ServerFilter::ExternalId, "a&b")
+                .with_sort(ServerSort::Id, SortDirection::Descending)
+                .with_include(ServerInclude::Allocations)
+                .with_include(ServerInclude::User)
+                .with_include(ServerInclude::Allocations)
+                .with_per_page(10)
+                .with_page(2)
+                .endpoint("servers"),
+            "servers?filter%5Bname%5D=lobby&filter%5Bexternal_id%5D=a%26b&sort=-id&include=allocations%2Cuser&per_page=10&page=2"
+        );
+        assert_eq!(
+            EggQuery::new()
+                .with_include(EggInclude::Variables)
+                .endpoint("nests/1/eggs"),
+            "nests/1/eggs?include=variables"
+        );
+    }
+}
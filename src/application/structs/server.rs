@@ -56,9 +56,9 @@ pub struct ServerLimits {
     pub io: u32,
     /// The server's CPU limit
     pub cpu: u32,
-    /// The server's threads limit (if any)
+    /// The CPU threads the server is pinned to, such as `0-3,5` (if any)
     #[serde(default)]
-    pub threads: Option<u32>,
+    pub threads: Option<String>,
     /// Whether OOM killer is disabled for the server
     #[serde(default)]
     pub oom_disabled: Option<bool>,
@@ -76,6 +76,209 @@ pub struct ServerFeatureLimits {
     pub backups: u32,
 }
 
+/// A partial update of the build configuration of a server, for
+/// [`crate::application::Client::apply_build_update`]. Fields which aren't set keep their current
+/// value, so only the changed fields need to be given:
+///
+/// ```
+/// # use pterodactyl_api::application::structs::BuildUpdate;
+/// let update = BuildUpdate::new()
+///     .with_memory(4096)
+///     .with_backups(5)
+///     .with_added_allocation(42);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BuildUpdate {
+    memory: Option<u32>,
+    swap: Option<u32>,
+    disk: Option<u32>,
+    io: Option<u32>,
+    cpu: Option<u32>,
+    threads: Option<String>,
+    oom_disabled: Option<bool>,
+    databases: Option<u32>,
+    allocations: Option<u32>,
+    backups: Option<u32>,
+    allocation: Option<u32>,
+    add_allocations: Vec<u32>,
+    remove_allocations: Vec<u32>,
+}
+
+impl BuildUpdate {
+    /// Creates an update which changes nothing
+    pub fn new() -> Self {
+        BuildUpdate::default()
+    }
+
+    /// Sets every resource limit. The threads and OOM killer are only changed if they are given
+    pub fn with_limits(self, limits: ServerLimits) -> Self {
+        BuildUpdate {
+            memory: Some(limits.memory),
+            swap: Some(limits.swap),
+            disk: Some(limits.disk),
+            io: Some(limits.io),
+            cpu: Some(limits.cpu),
+            threads: limits.threads.or(self.threads),
+            oom_disabled: limits.oom_disabled.or(self.oom_disabled),
+            ..self
+        }
+    }
+
+    /// Sets the memory limit in MB
+    pub fn with_memory(self, memory: u32) -> Self {
+        BuildUpdate {
+            memory: Some(memory),
+            ..self
+        }
+    }
+
+    /// Sets the swap limit in MB
+    pub fn with_swap(self, swap: u32) -> Self {
+        BuildUpdate {
+            swap: Some(swap),
+            ..self
+        }
+    }
+
+    /// Sets the disk limit in MB
+    pub fn with_disk(self, disk: u32) -> Self {
+        BuildUpdate {
+            disk: Some(disk),
+            ..self
+        }
+    }
+
+    /// Sets the IO weight
+    pub fn with_io(self, io: u32) -> Self {
+        BuildUpdate {
+            io: Some(io),
+            ..self
+        }
+    }
+
+    /// Sets the CPU limit, in percent of a single thread
+    pub fn with_cpu(self, cpu: u32) -> Self {
+        BuildUpdate {
+            cpu: Some(cpu),
+            ..self
+        }
+    }
+
+    /// Pins the server to the given CPU threads, such as `0-3,5`
+    pub fn with_threads(self, threads: impl Into<String>) -> Self {
+        BuildUpdate {
+            threads: Some(threads.into()),
+            ..self
+        }
+    }
+
+    /// Sets whether the OOM killer is disabled
+    pub fn with_oom_disabled(self, oom_disabled: bool) -> Self {
+        BuildUpdate {
+            oom_disabled: Some(oom_disabled),
+            ..self
+        }
+    }
+
+    /// Sets every feature limit
+    pub fn with_feature_limits(self, feature_limits: ServerFeatureLimits) -> Self {
+        BuildUpdate {
+            databases: Some(feature_limits.databases),
+            allocations: Some(feature_limits.allocations),
+            backups: Some(feature_limits.backups),
+            ..self
+        }
+    }
+
+    /// Sets the maximum number of databases
+    pub fn with_databases(self, databases: u32) -> Self {
+        BuildUpdate {
+            databases: Some(databases),
+            ..self
+        }
+    }
+
+    /// Sets the maximum number of allocations
+    pub fn with_allocation_limit(self, allocations: u32) -> Self {
+        BuildUpdate {
+            allocations: Some(allocations),
+            ..self
+        }
+    }
+
+    /// Sets the maximum number of backups
+    pub fn with_backups(self, backups: u32) -> Self {
+        BuildUpdate {
+            backups: Some(backups),
+            ..self
+        }
+    }
+
+    /// Sets the primary allocation, which must be assigned to the server or added in this update
+    pub fn with_allocation(self, allocation: u32) -> Self {
+        BuildUpdate {
+            allocation: Some(allocation),
+            ..self
+        }
+    }
+
+    /// Assigns the allocation with the given ID to the server. The allocation must be free, and on
+    /// the node of the server
+    pub fn with_added_allocation(mut self, allocation: u32) -> Self {
+        self.add_allocations.push(allocation);
+        self
+    }
+
+    /// Unassigns the allocation with the given ID from the server. The primary allocation can't
+    /// be removed
+    pub fn with_removed_allocation(mut self, allocation: u32) -> Self {
+        self.remove_allocations.push(allocation);
+        self
+    }
+
+    /// Fills the fields which aren't set from the current server
+    pub(crate) fn fill(self, current: &ServerStruct) -> BuildBody {
+        let limits = &current.limits;
+        let feature_limits = &current.feature_limits;
+        BuildBody {
+            memory: self.memory.unwrap_or(limits.memory),
+            swap: self.swap.unwrap_or(limits.swap),
+            disk: self.disk.unwrap_or(limits.disk),
+            io: self.io.unwrap_or(limits.io),
+            cpu: self.cpu.unwrap_or(limits.cpu),
+            threads: self.threads.or_else(|| limits.threads.clone()),
+            oom_disabled: self.oom_disabled.or(limits.oom_disabled),
+            feature_limits: ServerFeatureLimits {
+                databases: self.databases.unwrap_or(feature_limits.databases),
+                allocations: self.allocations.unwrap_or(feature_limits.allocations),
+                backups: self.backups.unwrap_or(feature_limits.backups),
+            },
+            allocation: self.allocation.unwrap_or(current.allocation),
+            add_allocations: self.add_allocations,
+            remove_allocations: self.remove_allocations,
+        }
+    }
+}
+
+/// The body of `PATCH servers/{id}/build`, which requires every limit
+#[derive(Debug, Serialize)]
+pub(crate) struct BuildBody {
+    memory: u32,
+    swap: u32,
+    disk: u32,
+    io: u32,
+    cpu: u32,
+    threads: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    oom_disabled: Option<bool>,
+    feature_limits: ServerFeatureLimits,
+    allocation: u32,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    add_allocations: Vec<u32>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    remove_allocations: Vec<u32>,
+}
+
 /// Represents a server's container settings
 #[derive(Debug, Deserialize, Clone, Serialize)]
 pub struct ServerContainer {
@@ -113,6 +316,20 @@ pub struct CreateServerRequest {
     pub allocation: AllocationSettings,
 }
 
+/// Request body for updating the details of a server. The panel replaces every detail, so
+/// [`None`] clears the external ID and description
+#[derive(Debug, Serialize)]
+pub struct UpdateServerDetailsRequest {
+    /// The server's name
+    pub name: String,
+    /// The ID of the user owning the server
+    pub user: u32,
+    /// The server's external ID
+    pub external_id: Option<String>,
+    /// The server's description
+    pub description: Option<String>,
+}
+
 /// Response for server creation
 #[derive(Debug, Deserialize)]
 pub struct CreateServerResponse {
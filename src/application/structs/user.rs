@@ -0,0 +1,96 @@
+use crate::application::structs::ServerStruct;
+use crate::Secret;
+use serde::{Deserialize, Serialize};
+
+/// Represents a user in the application API
+#[derive(Debug, Deserialize, Clone, Serialize)]
+pub struct UserStruct {
+    /// The user's ID
+    pub id: u32,
+    /// The user's external ID, used to link the user to an external system
+    #[serde(default)]
+    pub external_id: Option<String>,
+    /// The user's UUID
+    pub uuid: String,
+    /// The user's username
+    pub username: String,
+    /// The user's email address
+    pub email: String,
+    /// The user's first name
+    pub first_name: String,
+    /// The user's last name
+    pub last_name: String,
+    /// The user's language code
+    pub language: String,
+    /// Whether the user is an administrator
+    pub root_admin: bool,
+    /// Whether the user has two-factor authentication enabled
+    #[serde(rename = "2fa")]
+    pub two_factor: bool,
+    /// The user's created at timestamp
+    pub created_at: String,
+    /// The user's updated at timestamp
+    pub updated_at: String,
+    /// The user's relationships (only included when requested)
+    #[serde(default)]
+    pub relationships: Option<UserRelationships>,
+}
+
+/// Represents a user's relationships
+#[derive(Debug, Deserialize, Clone, Serialize, Default)]
+pub struct UserRelationships {
+    /// The servers owned by the user
+    #[serde(default, deserialize_with = "crate::structs::ptero_list")]
+    pub servers: Vec<ServerStruct>,
+}
+
+/// Request body for creating a new user
+#[derive(Debug, Serialize)]
+pub struct CreateUserRequest {
+    /// The user's email address
+    pub email: String,
+    /// The user's username
+    pub username: String,
+    /// The user's first name
+    pub first_name: String,
+    /// The user's last name
+    pub last_name: String,
+    /// The user's external ID (optional)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub external_id: Option<String>,
+    /// The user's password (optional, the panel emails the user a link to set one otherwise)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub password: Option<Secret>,
+    /// Whether the user is an administrator (optional)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub root_admin: Option<bool>,
+    /// The user's language code (optional)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+}
+
+/// Request body for updating a user. The panel requires the email, username and names to be
+/// given again, even when they don't change
+#[derive(Debug, Serialize)]
+pub struct UpdateUserRequest {
+    /// The user's email address
+    pub email: String,
+    /// The user's username
+    pub username: String,
+    /// The user's first name
+    pub first_name: String,
+    /// The user's last name
+    pub last_name: String,
+    /// The user's external ID (optional)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub external_id: Option<String>,
+    /// The user's new password (optional)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub password: Option<Secret>,
+    /// Whether the user is an administrator (optional)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub root_admin: Option<bool>,
+    /// The user's language code (optional)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+}
@@ -0,0 +1,299 @@
+use crate::application::structs::{
+    include_endpoint, CreateUserRequest, UpdateUserRequest, UserInclude, UserQuery, UserStruct,
+};
+use crate::application::Client;
+#[cfg(feature = "stream")]
+use crate::http::stream_pages;
+use crate::http::{collect_pages, paged_endpoint, EmptyBody};
+use crate::structs::{Page, PteroList, PteroObject};
+use reqwest::Method;
+
+impl Client {
+    /// Lists the users on the first page of users in the application. Use
+    /// [`Client::list_users_all`] to list every user
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use pterodactyl_api::application::ClientBuilder;
+    ///
+    /// #[tokio::main(flavor = "current_thread")]
+    /// async fn main() {
+    ///     let client = ClientBuilder::new("https://pterodactyl.example.com", "your-api-key")
+    ///         .build();
+    ///
+    ///     match client.list_users().await {
+    ///         Ok(users) => {
+    ///             println!("Found {} users:", users.len());
+    ///             for user in users {
+    ///                 println!("- {} ({})", user.username, user.id);
+    ///             }
+    ///         },
+    ///         Err(e) => eprintln!("Error listing users: {}", e),
+    ///     }
+    /// }
+    /// ```
+    pub async fn list_users(&self) -> crate::Result<Vec<UserStruct>> {
+        self.http
+            .request::<PteroList<UserStruct>>(Method::GET, "users")
+            .await
+            .map(|users| users.data)
+    }
+
+    /// Gets a single page of the users in the application, along with the pagination metadata
+    pub async fn list_users_page(&self, page: u32) -> crate::Result<Page<UserStruct>> {
+        self.http
+            .request::<Page<UserStruct>>(Method::GET, &paged_endpoint("users", page))
+            .await
+    }
+
+    /// Lists every user in the application, fetching every page
+    pub async fn list_users_all(&self) -> crate::Result<Vec<UserStruct>> {
+        collect_pages(|page| self.list_users_page(page)).await
+    }
+
+    /// Lazily streams every user in the application, fetching each page as it is needed
+    #[cfg(feature = "stream")]
+    pub fn list_users_stream(
+        &self,
+    ) -> impl futures_core::Stream<Item = crate::Result<UserStruct>> + '_ {
+        stream_pages(move |page| self.list_users_page(page))
+    }
+
+    /// Gets a single page of the users matching the given query, along with the pagination
+    /// metadata
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use pterodactyl_api::application::ClientBuilder;
+    /// use pterodactyl_api::application::structs::{UserFilter, UserInclude, UserQuery};
+    ///
+    /// #[tokio::main(flavor = "current_thread")]
+    /// async fn main() {
+    ///     let client = ClientBuilder::new("https://pterodactyl.example.com", "your-api-key")
+    ///         .build();
+    ///
+    ///     let query = UserQuery::new()
+    ///         .with_filter(UserFilter::Email, "user@example.com")
+    ///         .with_include(UserInclude::Servers);
+    ///     match client.list_users_filtered(&query).await {
+    ///         Ok(page) => println!("Found {} matching users", page.data.len()),
+    ///         Err(e) => eprintln!("Error listing users: {}", e),
+    ///     }
+    /// }
+    /// ```
+    pub async fn list_users_filtered(&self, query: &UserQuery) -> crate::Result<Page<UserStruct>> {
+        self.http
+            .request::<Page<UserStruct>>(Method::GET, &query.endpoint("users"))
+            .await
+    }
+
+    /// Lists every user matching the given query, fetching every page
+    pub async fn list_users_filtered_all(
+        &self,
+        query: &UserQuery,
+    ) -> crate::Result<Vec<UserStruct>> {
+        self.http.collect_query("users", query).await
+    }
+
+    /// Lazily streams the users matching the given query, fetching each page when the stream
+    /// reaches it
+    #[cfg(feature = "stream")]
+    pub fn list_users_filtered_stream(
+        &self,
+        query: UserQuery,
+    ) -> impl futures_core::Stream<Item = crate::Result<UserStruct>> + '_ {
+        self.http.stream_query("users".to_owned(), query)
+    }
+
+    /// Gets a specific user by ID, including the given relationships
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use pterodactyl_api::application::ClientBuilder;
+    /// use pterodactyl_api::application::structs::UserInclude;
+    ///
+    /// #[tokio::main(flavor = "current_thread")]
+    /// async fn main() {
+    ///     let client = ClientBuilder::new("https://pterodactyl.example.com", "your-api-key")
+    ///         .build();
+    ///
+    ///     match client.get_user(1, &[UserInclude::Servers]).await {
+    ///         Ok(user) => println!("User: {} ({})", user.username, user.email),
+    ///         Err(e) => eprintln!("Error getting user: {}", e),
+    ///     }
+    /// }
+    /// ```
+    pub async fn get_user(&self, id: u32, include: &[UserInclude]) -> crate::Result<UserStruct> {
+        self.http
+            .request::<PteroObject<UserStruct>>(
+                Method::GET,
+                &include_endpoint(&format!("users/{}", id), include),
+            )
+            .await
+            .map(|user| user.attributes)
+    }
+
+    /// Gets a specific user by the external ID it was given when created or updated, including
+    /// the given relationships
+    pub async fn get_user_by_external_id(
+        &self,
+        external_id: &str,
+        include: &[UserInclude],
+    ) -> crate::Result<UserStruct> {
+        let path = format!("users/external/{}", urlencoding::encode(external_id));
+        self.http
+            .request::<PteroObject<UserStruct>>(Method::GET, &include_endpoint(&path, include))
+            .await
+            .map(|user| user.attributes)
+    }
+
+    /// Creates a new user
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use pterodactyl_api::application::{ClientBuilder, structs::CreateUserRequest};
+    ///
+    /// #[tokio::main(flavor = "current_thread")]
+    /// async fn main() {
+    ///     let client = ClientBuilder::new("https://pterodactyl.example.com", "your-api-key")
+    ///         .build();
+    ///
+    ///     let user_request = CreateUserRequest {
+    ///         email: "user@example.com".to_string(),
+    ///         username: "user".to_string(),
+    ///         first_name: "Example".to_string(),
+    ///         last_name: "User".to_string(),
+    ///         external_id: Some("customer-42".to_string()),
+    ///         password: None,
+    ///         root_admin: None,
+    ///         language: None,
+    ///     };
+    ///
+    ///     match client.create_user(user_request).await {
+    ///         Ok(user) => println!("User created: {} ({})", user.username, user.id),
+    ///         Err(e) => eprintln!("Error creating user: {}", e),
+    ///     }
+    /// }
+    /// ```
+    pub async fn create_user(&self, request: CreateUserRequest) -> crate::Result<UserStruct> {
+        self.http
+            .request_with_body::<PteroObject<UserStruct>, _>(Method::POST, "users", &request)
+            .await
+            .map(|response| response.attributes)
+    }
+
+    /// Updates the user with the specified ID
+    pub async fn update_user(
+        &self,
+        id: u32,
+        request: UpdateUserRequest,
+    ) -> crate::Result<UserStruct> {
+        self.http
+            .request_with_body::<PteroObject<UserStruct>, _>(
+                Method::PATCH,
+                &format!("users/{}", id),
+                &request,
+            )
+            .await
+            .map(|response| response.attributes)
+    }
+
+    /// Deletes the user with the specified ID. The panel refuses to delete users who still own
+    /// servers
+    pub async fn delete_user(&self, id: u32) -> crate::Result<()> {
+        self.http
+            .request::<EmptyBody>(Method::DELETE, &format!("users/{}", id))
+            .await?;
+        Ok(())
+    }
+}
+
+#[cfg(all(test, feature = "mock-server"))]
+mod test {
+    use crate::application::structs::{
+        CreateUserRequest, UpdateUserRequest, UserFilter, UserInclude, UserQuery,
+    };
+    use crate::application::ClientBuilder;
+    use crate::mock::MockPanel;
+    use reqwest::StatusCode;
+
+    #[tokio::test]
+    async fn test_users() {
+        let panel = MockPanel::start().await.unwrap();
+        let client = ClientBuilder::new(panel.url(), "key").build();
+        let request = |email: &str| CreateUserRequest {
+            email: email.to_owned(),
+            username: "player".to_owned(),
+            first_name: "Example".to_owned(),
+            last_name: "Player".to_owned(),
+            external_id: Some("customer/42".to_owned()),
+            password: Some("hunter22".into()),
+            root_admin: None,
+            language: None,
+        };
+
+        let Err(crate::Error::Validation(errors)) = client.create_user(request("player")).await
+        else {
+            panic!("creating a user with an invalid email should fail validation");
+        };
+        assert_eq!(errors.fields().collect::<Vec<_>>(), ["email"]);
+        let created = client
+            .create_user(request("player@example.com"))
+            .await
+            .unwrap();
+        assert!(!created.root_admin);
+
+        let query = UserQuery::new().with_filter(UserFilter::Email, "player@example.com");
+        let page = client.list_users_filtered(&query).await.unwrap();
+        assert_eq!(page.data.len(), 1);
+        assert_eq!(page.data[0].id, created.id);
+        let users = client.list_users_filtered_all(&query).await.unwrap();
+        assert_eq!(users.len(), 1);
+        assert_eq!(client.list_users().await.unwrap().len(), 2);
+
+        let user = client
+            .get_user_by_external_id("customer/42", &[])
+            .await
+            .unwrap();
+        assert_eq!(user.id, created.id);
+        assert!(user.relationships.is_none());
+        let admin = client.get_user(1, &[UserInclude::Servers]).await.unwrap();
+        assert_eq!(admin.relationships.unwrap().servers[0].name, "Test Server");
+
+        let updated = client
+            .update_user(
+                created.id,
+                UpdateUserRequest {
+                    email: created.email.clone(),
+                    username: "renamed".to_owned(),
+                    first_name: created.first_name.clone(),
+                    last_name: created.last_name.clone(),
+                    external_id: None,
+                    password: None,
+                    root_admin: Some(true),
+                    language: None,
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(updated.username, "renamed");
+        assert!(updated.root_admin);
+
+        assert!(matches!(
+            client.delete_user(1).await,
+            Err(crate::Error::Api {
+                status: StatusCode::BAD_REQUEST,
+                ..
+            })
+        ));
+        client.delete_user(created.id).await.unwrap();
+        assert!(matches!(
+            client.get_user(created.id, &[]).await,
+            Err(crate::Error::ResourceNotFound { .. })
+        ));
+    }
+}
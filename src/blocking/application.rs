@@ -0,0 +1,165 @@
+//! A blocking version of the application API
+
+use crate::application::structs::{
+    AllocationQuery, AllocationStruct, BuildUpdate, CreateAllocationRequest, CreateLocationRequest,
+    CreateNodeRequest, CreateServerDatabaseRequest, CreateServerRequest, CreateServerResponse,
+    CreateUserRequest, DatabaseInclude, EggQuery, EggStruct, LocationInclude, LocationQuery,
+    LocationStruct, NestStruct, NodeQuery, NodeStruct, ServerDatabaseStruct, ServerLimits,
+    ServerQuery, ServerStruct, UpdateLocationRequest, UpdateNodeRequest,
+    UpdateServerDetailsRequest, UpdateUserRequest, UserInclude, UserQuery, UserStruct,
+};
+use crate::{Diagnosis, Page, RateLimits};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::runtime::Runtime;
+
+/// A blocking Pterodactyl client, to make requests to the Pterodactyl application API. Cloning
+/// the client is cheap, and the clones share their runtime
+#[derive(Debug, Clone)]
+pub struct Client {
+    inner: crate::application::Client,
+    runtime: Arc<Runtime>,
+}
+
+impl Client {
+    /// Wraps an async client, creating the runtime to run its requests on
+    pub fn new(client: crate::application::Client) -> crate::Result<Self> {
+        Ok(Client {
+            inner: client,
+            runtime: super::runtime()?,
+        })
+    }
+
+    /// Gets the async client which this client wraps
+    pub fn as_async(&self) -> &crate::application::Client {
+        &self.inner
+    }
+
+    /// Gets the rate limit information after the previous request
+    pub fn get_rate_limits(&self) -> Option<RateLimits> {
+        self.inner.get_rate_limits()
+    }
+
+    /// Blocking version of [`crate::application::Client::diagnose`]
+    pub fn diagnose(&self) -> Diagnosis {
+        self.runtime.block_on(self.inner.diagnose())
+    }
+
+    blocking_methods! {
+        "crate::application::Client", |this| this.inner;
+        fn list_nests(&self) -> Vec<NestStruct>;
+        fn list_nests_page(&self, page: u32) -> Page<NestStruct>;
+        fn list_nests_all(&self) -> Vec<NestStruct>;
+        fn get_nest(&self, id: u32) -> NestStruct;
+        fn list_eggs(&self, nest_id: u32, include: Option<Vec<&str>>) -> Vec<EggStruct>;
+        fn list_eggs_page(
+            &self,
+            nest_id: u32,
+            include: Option<Vec<&str>>,
+            page: u32,
+        ) -> Page<EggStruct>;
+        fn list_eggs_all(&self, nest_id: u32, include: Option<Vec<&str>>) -> Vec<EggStruct>;
+        fn list_eggs_filtered(&self, nest_id: u32, query: &EggQuery) -> Page<EggStruct>;
+        fn list_eggs_filtered_all(&self, nest_id: u32, query: &EggQuery) -> Vec<EggStruct>;
+        fn get_egg(&self, nest_id: u32, egg_id: u32, include: Option<Vec<&str>>) -> EggStruct;
+
+        fn list_locations(&self) -> Vec<LocationStruct>;
+        fn list_locations_page(&self, page: u32) -> Page<LocationStruct>;
+        fn list_locations_all(&self) -> Vec<LocationStruct>;
+        fn list_locations_filtered(&self, query: &LocationQuery) -> Page<LocationStruct>;
+        fn list_locations_filtered_all(&self, query: &LocationQuery) -> Vec<LocationStruct>;
+        fn get_location(&self, id: u32, include: &[LocationInclude]) -> LocationStruct;
+        fn create_location(&self, request: CreateLocationRequest) -> LocationStruct;
+        fn update_location(&self, id: u32, request: UpdateLocationRequest) -> LocationStruct;
+        fn delete_location(&self, id: u32) -> ();
+
+        fn list_nodes(&self) -> Vec<NodeStruct>;
+        fn list_nodes_page(&self, page: u32) -> Page<NodeStruct>;
+        fn list_nodes_all(&self) -> Vec<NodeStruct>;
+        fn list_nodes_filtered(&self, query: &NodeQuery) -> Page<NodeStruct>;
+        fn list_nodes_filtered_all(&self, query: &NodeQuery) -> Vec<NodeStruct>;
+        fn get_node(&self, id: u32) -> NodeStruct;
+        fn create_node(&self, request: CreateNodeRequest) -> NodeStruct;
+        fn update_node(&self, id: u32, request: UpdateNodeRequest) -> NodeStruct;
+        fn delete_node(&self, id: u32) -> ();
+        fn list_node_allocations(&self, node_id: u32) -> Vec<AllocationStruct>;
+        fn list_node_allocations_page(&self, node_id: u32, page: u32) -> Page<AllocationStruct>;
+        fn list_node_allocations_all(&self, node_id: u32) -> Vec<AllocationStruct>;
+        fn list_node_allocations_filtered(
+            &self,
+            node_id: u32,
+            query: &AllocationQuery,
+        ) -> Page<AllocationStruct>;
+        fn list_node_allocations_filtered_all(
+            &self,
+            node_id: u32,
+            query: &AllocationQuery,
+        ) -> Vec<AllocationStruct>;
+        fn create_node_allocation(&self, node_id: u32, request: CreateAllocationRequest) -> ();
+        fn delete_allocation(&self, allocation_id: u32) -> ();
+
+        fn list_servers(&self) -> Vec<ServerStruct>;
+        fn list_servers_page(&self, page: u32) -> Page<ServerStruct>;
+        fn list_servers_all(&self) -> Vec<ServerStruct>;
+        fn list_servers_filtered(&self, query: &ServerQuery) -> Page<ServerStruct>;
+        fn list_servers_filtered_all(&self, query: &ServerQuery) -> Vec<ServerStruct>;
+        fn get_server(&self, id: u32) -> ServerStruct;
+        fn get_server_by_external_id(&self, external_id: &str) -> ServerStruct;
+        fn create_server(&self, request: CreateServerRequest) -> CreateServerResponse;
+        fn delete_server(&self, id: u32) -> ();
+        fn force_delete_server(&self, id: u32) -> ();
+        fn update_server_build(&self, id: u32, limits: ServerLimits) -> ();
+        fn apply_build_update(&self, id: u32, update: BuildUpdate) -> ServerStruct;
+        fn update_startup_variables(
+            &self,
+            id: u32,
+            startup: String,
+            environment: HashMap<String, String>,
+            egg: String,
+            image: String,
+            skip_scripts: bool,
+        ) -> ();
+        fn update_server_details(
+            &self,
+            id: u32,
+            request: UpdateServerDetailsRequest,
+        ) -> ServerStruct;
+        fn transfer_server_ownership(&self, id: u32, user: u32) -> ServerStruct;
+        fn suspend_server(&self, id: u32) -> ();
+        fn resume_server(&self, id: u32) -> ();
+        fn reinstall_server(&self, id: u32) -> ();
+        fn list_server_databases(
+            &self,
+            id: u32,
+            include: &[DatabaseInclude],
+        ) -> Vec<ServerDatabaseStruct>;
+        fn get_server_database(
+            &self,
+            id: u32,
+            database_id: u32,
+            include: &[DatabaseInclude],
+        ) -> ServerDatabaseStruct;
+        fn create_server_database(
+            &self,
+            id: u32,
+            request: CreateServerDatabaseRequest,
+        ) -> ServerDatabaseStruct;
+        fn reset_server_database_password(&self, id: u32, database_id: u32) -> ();
+        fn delete_server_database(&self, id: u32, database_id: u32) -> ();
+
+        fn list_users(&self) -> Vec<UserStruct>;
+        fn list_users_page(&self, page: u32) -> Page<UserStruct>;
+        fn list_users_all(&self) -> Vec<UserStruct>;
+        fn list_users_filtered(&self, query: &UserQuery) -> Page<UserStruct>;
+        fn list_users_filtered_all(&self, query: &UserQuery) -> Vec<UserStruct>;
+        fn get_user(&self, id: u32, include: &[UserInclude]) -> UserStruct;
+        fn get_user_by_external_id(
+            &self,
+            external_id: &str,
+            include: &[UserInclude],
+        ) -> UserStruct;
+        fn create_user(&self, request: CreateUserRequest) -> UserStruct;
+        fn update_user(&self, id: u32, request: UpdateUserRequest) -> UserStruct;
+        fn delete_user(&self, id: u32) -> ();
+    }
+}
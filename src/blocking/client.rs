@@ -0,0 +1,212 @@
+//! A blocking version of the client API
+
+use crate::client::account::{Account, Account2fa, ApiKey, CreatedApiKey, RecoveryTokens};
+use crate::client::backups::{Backup, BackupParams};
+use crate::client::databases::ServerDatabase;
+use crate::client::files::PteroFile;
+use crate::client::network::Allocation;
+use crate::client::schedules::{Schedule, ScheduleParams, ScheduleTask, TaskParams};
+use crate::client::startup::{StartupData, Variable};
+use crate::client::users::User;
+use crate::client::{
+    ClientServerQuery, OwnedServer, PermissionGroup, PowerSignal, RateLimits, ServerResources,
+    ServerStruct,
+};
+use crate::{Diagnosis, Page, Secret};
+use bytes::Bytes;
+use reqwest::Body;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::runtime::Runtime;
+use uuid::Uuid;
+
+/// A blocking Pterodactyl client, to make requests to the Pterodactyl client API. Cloning the
+/// client is cheap, and the clones share their runtime
+#[derive(Debug, Clone)]
+pub struct Client {
+    inner: crate::client::Client,
+    runtime: Arc<Runtime>,
+}
+
+impl Client {
+    /// Wraps an async client, creating the runtime to run its requests on
+    pub fn new(client: crate::client::Client) -> crate::Result<Self> {
+        Ok(Client {
+            inner: client,
+            runtime: super::runtime()?,
+        })
+    }
+
+    /// Gets the async client which this client wraps
+    pub fn as_async(&self) -> &crate::client::Client {
+        &self.inner
+    }
+
+    /// Gets the rate limit information after the previous request
+    pub fn get_rate_limits(&self) -> Option<RateLimits> {
+        self.inner.get_rate_limits()
+    }
+
+    /// Blocking version of [`crate::client::Client::diagnose`]
+    pub fn diagnose(&self) -> Diagnosis {
+        self.runtime.block_on(self.inner.diagnose())
+    }
+
+    /// Gets a server with a specific ID, which can be used to make requests specific to that server
+    pub fn get_server(&self, id: impl Into<String>) -> Server {
+        Server {
+            inner: self.inner.get_owned_server(id),
+            runtime: Arc::clone(&self.runtime),
+        }
+    }
+
+    blocking_methods! {
+        "crate::client::Client", |this| this.inner;
+        fn list_servers(&self) -> Vec<ServerStruct>;
+        fn list_servers_filtered(&self, query: &ClientServerQuery) -> Page<ServerStruct>;
+        fn list_servers_all(&self, query: &ClientServerQuery) -> Vec<ServerStruct>;
+        fn get_permissions(&self) -> HashMap<String, PermissionGroup>;
+        fn get_account_details(&self) -> Account;
+        fn get_account_2fa_details(&self) -> Account2fa;
+        fn enable_2fa(&self, token: impl Into<String>) -> RecoveryTokens;
+        fn disable_2fa(&self, password: impl Into<Secret>) -> ();
+        fn update_email(&self, email: impl Into<String>, password: impl Into<Secret>) -> ();
+        fn update_password(
+            &self,
+            current_password: impl Into<Secret>,
+            new_password: impl Into<Secret>,
+        ) -> ();
+        fn get_api_keys(&self) -> Vec<ApiKey>;
+        fn create_api_key(&self, description: impl Into<String>) -> CreatedApiKey;
+        fn create_api_key_with_allowed_ips(
+            &self,
+            description: impl Into<String>,
+            allowed_ips: Vec<String>,
+        ) -> CreatedApiKey;
+        fn delete_api_key(&self, id: impl Into<String>) -> ();
+    }
+}
+
+/// A blocking handle to a Pterodactyl server, with which requests specific to a server can be
+/// made
+#[derive(Debug, Clone)]
+pub struct Server {
+    inner: OwnedServer,
+    runtime: Arc<Runtime>,
+}
+
+impl Server {
+    /// Gets the async server which this server wraps
+    pub fn as_async(&self) -> &OwnedServer {
+        &self.inner
+    }
+
+    blocking_methods! {
+        "crate::client::Server", |this| this.inner;
+        fn get_details(&self) -> ServerStruct;
+        fn get_resources(&self) -> ServerResources;
+        fn send_command(&self, command: impl Into<String>) -> ();
+        fn send_power_signal(&self, signal: PowerSignal) -> ();
+
+        fn list_backups(&self) -> Vec<Backup>;
+        fn create_backup(&self) -> Backup;
+        fn create_backup_with_params(&self, options: impl Into<BackupParams>) -> Backup;
+        fn get_backup(&self, id: Uuid) -> Backup;
+        fn get_backup_download_link(&self, id: Uuid) -> String;
+        fn delete_backup(&self, id: Uuid) -> ();
+
+        fn list_databases(&self) -> Vec<ServerDatabase>;
+        fn create_database(
+            &self,
+            name: impl Into<String>,
+            remote: impl Into<String>,
+        ) -> ServerDatabase;
+        fn rotate_database_password(&self, id: impl Into<String>) -> ServerDatabase;
+        fn delete_database(&self, id: impl Into<String>) -> ();
+
+        fn list_files(&self, directory: impl AsRef<str>) -> Vec<PteroFile>;
+        fn file_contents(&self, file: impl AsRef<str>) -> Bytes;
+        fn file_contents_text(&self, file: impl AsRef<str>) -> String;
+        fn get_file_download_url(&self, file: impl AsRef<str>) -> String;
+        fn rename_file(&self, from: impl Into<String>, to: impl Into<String>) -> ();
+        fn rename_files(&self, files: Vec<(String, String)>) -> ();
+        fn create_file_copy(&self, file: impl Into<String>) -> ();
+        fn copy_file(&self, from: impl Into<String>, to: impl Into<String>) -> ();
+        fn write_file(&self, file: impl AsRef<str>, data: impl Into<Body>) -> ();
+        fn compress_file(&self, file: impl Into<String>) -> PteroFile;
+        fn compress_files(&self, root: impl Into<String>, files: Vec<String>) -> PteroFile;
+        fn decompress_file(&self, file: impl Into<String>, dest: impl Into<String>) -> ();
+        fn delete_file(&self, file: impl Into<String>) -> ();
+        fn delete_files(&self, files: Vec<String>) -> ();
+        fn create_folder(&self, folder: impl Into<String>) -> ();
+        fn get_files_upload_url(&self) -> String;
+
+        fn list_network_allocations(&self) -> Vec<Allocation>;
+        fn create_network_allocation(&self) -> Allocation;
+        fn set_network_allocation_notes(
+            &self,
+            allocation_id: u64,
+            notes: impl Into<String>,
+        ) -> Allocation;
+        fn set_network_allocation_primary(&self, allocation_id: u64) -> Allocation;
+        fn delete_network_allocation(&self, allocation_id: u64) -> ();
+
+        fn list_schedules(&self) -> Vec<Schedule>;
+        fn create_schedule(&self, schedule: impl Into<ScheduleParams>) -> Schedule;
+        fn get_schedule(&self, id: u64) -> Schedule;
+        fn update_schedule(&self, id: u64, schedule: impl Into<ScheduleParams>) -> Schedule;
+        fn delete_schedule(&self, id: u64) -> ();
+        fn create_task(&self, schedule_id: u64, task: impl Into<TaskParams>) -> ScheduleTask;
+        fn update_task(
+            &self,
+            schedule_id: u64,
+            task_id: u64,
+            task: impl Into<TaskParams>,
+        ) -> ScheduleTask;
+        fn delete_task(&self, schedule_id: u64, task_id: u64) -> ();
+
+        fn rename(&self, name: impl Into<String>) -> ();
+        fn reinstall(&self) -> ();
+
+        fn get_startup_data(&self) -> StartupData;
+        fn set_startup_variable(&self, name: impl Into<String>, value: impl Into<String>)
+            -> Variable;
+        fn set_docker_image(&self, docker_image: impl Into<String>) -> ();
+
+        fn list_users(&self) -> Vec<User>;
+        fn add_user(&self, email: impl Into<String>, permissions: Vec<String>) -> User;
+        fn get_user(&self, id: Uuid) -> User;
+        fn set_user_permissions(&self, id: Uuid, permissions: Vec<String>) -> User;
+        fn delete_user(&self, id: Uuid) -> ();
+    }
+}
+
+#[cfg(all(test, feature = "mock-server"))]
+mod test {
+    use super::Client;
+    use crate::client::{ClientBuilder, PowerSignal};
+    use crate::mock::MockPanel;
+
+    #[test]
+    fn test_blocking_client() {
+        // the mock panel is served by a runtime on another thread, as blocking on a request
+        // within a runtime panics
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .unwrap();
+        let panel = runtime.block_on(MockPanel::start()).unwrap();
+        std::thread::spawn(move || runtime.block_on(std::future::pending::<()>()));
+
+        let client = Client::new(ClientBuilder::new(panel.url(), "key").build()).unwrap();
+        let servers = client.list_servers().unwrap();
+        let server = client.get_server(&servers[0].identifier);
+        server.send_power_signal(PowerSignal::Start).unwrap();
+        server.write_file("eula.txt", "eula=true").unwrap();
+        assert_eq!(server.file_contents_text("eula.txt").unwrap(), "eula=true");
+        assert_eq!(
+            panel.power_signals(&servers[0].identifier),
+            [PowerSignal::Start]
+        );
+    }
+}
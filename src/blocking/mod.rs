@@ -0,0 +1,52 @@
+//! Blocking versions of the clients, for synchronous code which doesn't run an async runtime.
+//! Every client runs its requests on an internal runtime, which its clones share:
+//!
+//! ```no_run
+//! use pterodactyl_api::blocking::client::Client;
+//! use pterodactyl_api::client::{ClientBuilder, PowerSignal};
+//!
+//! # fn main() -> pterodactyl_api::Result<()> {
+//! let client = Client::new(ClientBuilder::new("https://pterodactyl.example.com", "key").build())?;
+//! for server in client.list_servers()? {
+//!     client
+//!         .get_server(server.identifier)
+//!         .send_power_signal(PowerSignal::Restart)?;
+//! }
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! The blocking clients must not be used within an async runtime, where blocking on a request
+//! panics. Streams and websockets are only available on the async clients.
+
+use std::sync::Arc;
+use tokio::runtime::Runtime;
+
+/// Defines blocking methods which run the async method of the same name on the runtime, with the
+/// async receiver given by the expression before the methods
+macro_rules! blocking_methods {
+    ($async:literal, |$this:ident| $receiver:expr; $(
+        $(#[$meta:meta])*
+        fn $name:ident(&self $(, $arg:ident: $ty:ty)* $(,)?) -> $ret:ty;
+    )*) => {
+        $(
+            $(#[$meta])*
+            #[doc = concat!("Blocking version of [`", $async, "::", stringify!($name), "`]")]
+            pub fn $name(&self $(, $arg: $ty)*) -> crate::Result<$ret> {
+                let $this = self;
+                self.runtime.block_on($receiver.$name($($arg),*))
+            }
+        )*
+    };
+}
+
+pub mod application;
+pub mod client;
+
+/// Creates the runtime which blocking clients run their requests on
+fn runtime() -> crate::Result<Arc<Runtime>> {
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()?;
+    Ok(Arc::new(runtime))
+}
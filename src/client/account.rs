@@ -1,9 +1,10 @@
 //! API for endpoints under `api/client/account`
 
-use crate::client::{Client, ErrorResponse};
+use crate::client::Client;
 use crate::http::{EmptyBody, ErrorHandler};
-use crate::structs::{PteroData, PteroList, PteroObject};
-use reqwest::{Method, Response, StatusCode};
+use crate::structs::{ErrorResponse, PteroData, PteroList, PteroObject};
+use crate::Secret;
+use reqwest::{Method, StatusCode};
 use serde::{Deserialize, Serialize};
 use time::OffsetDateTime;
 
@@ -67,20 +68,22 @@ pub struct CreatedApiKey {
     /// Metadata about the API key
     pub key: ApiKey,
     /// The token used to login using the key
-    pub secret_token: String,
+    pub secret_token: Secret,
 }
 
 impl Client {
     /// Gets the account details of the connected account
     pub async fn get_account_details(&self) -> crate::Result<Account> {
-        self.request::<PteroObject<Account>>(Method::GET, "account")
+        self.http
+            .request::<PteroObject<Account>>(Method::GET, "account")
             .await
             .map(|account| account.attributes)
     }
 
     /// Gets the 2fa details of the connected account
     pub async fn get_account_2fa_details(&self) -> crate::Result<Account2fa> {
-        self.request::<PteroData<Account2fa>>(Method::GET, "account/two-factor")
+        self.http
+            .request::<PteroData<Account2fa>>(Method::GET, "account/two-factor")
             .await
             .map(|account_2fa| account_2fa.data)
     }
@@ -94,50 +97,51 @@ impl Client {
         }
         struct Enable2faErrorHandler;
         impl ErrorHandler for Enable2faErrorHandler {
-            async fn get_error(response: Response) -> Option<crate::Error> {
-                if response.status() != StatusCode::BAD_REQUEST {
+            fn get_error(status: StatusCode, error: &ErrorResponse) -> Option<crate::Error> {
+                if status != StatusCode::BAD_REQUEST {
                     return None;
                 }
-                let error: ErrorResponse = response.json().await.ok()?;
                 if !error.is_error("TwoFactorAuthenticationTokenInvalid") {
                     return None;
                 }
                 Some(crate::Error::Invalid2faToken)
             }
         }
-        self.request_with_error_handler::<PteroObject<RecoveryTokens>, _, Enable2faErrorHandler>(
-            Method::POST,
-            "account/two-factor",
-            &Enable2faBody { code: token.into() },
-        )
-        .await
-        .map(|tokens| tokens.attributes)
+        self.http
+            .request_with_error_handler::<PteroObject<RecoveryTokens>, _, Enable2faErrorHandler>(
+                Method::POST,
+                "account/two-factor",
+                &Enable2faBody { code: token.into() },
+            )
+            .await
+            .map(|tokens| tokens.attributes)
     }
 
     /// Disables 2fa on the connected account. Returns [`crate::Error::IncorrectPassword`] if the
     /// password is incorrect
-    pub async fn disable_2fa(&self, password: impl Into<String>) -> crate::Result<()> {
+    pub async fn disable_2fa(&self, password: impl Into<Secret>) -> crate::Result<()> {
         #[derive(Serialize)]
         struct Disable2faBody {
-            password: String,
+            password: Secret,
         }
         struct Disable2faErrorHandler;
         impl ErrorHandler for Disable2faErrorHandler {
-            async fn get_error(response: Response) -> Option<crate::Error> {
-                if response.status() != StatusCode::BAD_REQUEST {
+            fn get_error(status: StatusCode, _error: &ErrorResponse) -> Option<crate::Error> {
+                if status != StatusCode::BAD_REQUEST {
                     return None;
                 }
                 Some(crate::Error::IncorrectPassword)
             }
         }
-        self.request_with_error_handler::<EmptyBody, _, Disable2faErrorHandler>(
-            Method::DELETE,
-            "account/two-factor",
-            &Disable2faBody {
-                password: password.into(),
-            },
-        )
-        .await?;
+        self.http
+            .request_with_error_handler::<EmptyBody, _, Disable2faErrorHandler>(
+                Method::DELETE,
+                "account/two-factor",
+                &Disable2faBody {
+                    password: password.into(),
+                },
+            )
+            .await?;
         Ok(())
     }
 
@@ -146,20 +150,19 @@ impl Client {
     pub async fn update_email(
         &self,
         email: impl Into<String>,
-        password: impl Into<String>,
+        password: impl Into<Secret>,
     ) -> crate::Result<()> {
         #[derive(Serialize)]
         struct UpdateEmailBody {
             email: String,
-            password: String,
+            password: Secret,
         }
         struct UpdateEmailErrorHandler;
         impl ErrorHandler for UpdateEmailErrorHandler {
-            async fn get_error(response: Response) -> Option<crate::Error> {
-                if response.status() != StatusCode::BAD_REQUEST {
+            fn get_error(status: StatusCode, error: &ErrorResponse) -> Option<crate::Error> {
+                if status != StatusCode::BAD_REQUEST {
                     return None;
                 }
-                let error: ErrorResponse = response.json().await.ok()?;
                 if error.is_error("email") {
                     Some(crate::Error::InvalidEmail)
                 } else if error.is_error("InvalidPasswordProvidedException") {
@@ -169,15 +172,16 @@ impl Client {
                 }
             }
         }
-        self.request_with_error_handler::<EmptyBody, _, UpdateEmailErrorHandler>(
-            Method::PUT,
-            "account/email",
-            &UpdateEmailBody {
-                email: email.into(),
-                password: password.into(),
-            },
-        )
-        .await?;
+        self.http
+            .request_with_error_handler::<EmptyBody, _, UpdateEmailErrorHandler>(
+                Method::PUT,
+                "account/email",
+                &UpdateEmailBody {
+                    email: email.into(),
+                    password: password.into(),
+                },
+            )
+            .await?;
         Ok(())
     }
 
@@ -185,41 +189,43 @@ impl Client {
     /// if the existing password is incorrect
     pub async fn update_password(
         &self,
-        current_password: impl Into<String>,
-        new_password: impl Into<String>,
+        current_password: impl Into<Secret>,
+        new_password: impl Into<Secret>,
     ) -> crate::Result<()> {
         #[derive(Serialize)]
-        struct UpdatePasswordBody {
-            current_password: String,
-            password: String,
-            password_confirmation: String,
+        struct UpdatePasswordBody<'a> {
+            current_password: Secret,
+            password: &'a Secret,
+            password_confirmation: &'a Secret,
         }
         struct UpdatePasswordErrorHandler;
         impl ErrorHandler for UpdatePasswordErrorHandler {
-            async fn get_error(response: Response) -> Option<crate::Error> {
-                if response.status() != StatusCode::BAD_REQUEST {
+            fn get_error(status: StatusCode, _error: &ErrorResponse) -> Option<crate::Error> {
+                if status != StatusCode::BAD_REQUEST {
                     return None;
                 }
                 Some(crate::Error::IncorrectPassword)
             }
         }
         let new_password = new_password.into();
-        self.request_with_error_handler::<EmptyBody, _, UpdatePasswordErrorHandler>(
-            Method::PUT,
-            "account/password",
-            &UpdatePasswordBody {
-                current_password: current_password.into(),
-                password: new_password.clone(),
-                password_confirmation: new_password,
-            },
-        )
-        .await?;
+        self.http
+            .request_with_error_handler::<EmptyBody, _, UpdatePasswordErrorHandler>(
+                Method::PUT,
+                "account/password",
+                &UpdatePasswordBody {
+                    current_password: current_password.into(),
+                    password: &new_password,
+                    password_confirmation: &new_password,
+                },
+            )
+            .await?;
         Ok(())
     }
 
     /// Gets the list of metadata for API keys that can be used to connect with this account
     pub async fn get_api_keys(&self) -> crate::Result<Vec<ApiKey>> {
-        self.request::<PteroList<ApiKey>>(Method::GET, "account/api-keys")
+        self.http
+            .request::<PteroList<ApiKey>>(Method::GET, "account/api-keys")
             .await
             .map(|keys| keys.data)
     }
@@ -253,7 +259,7 @@ impl Client {
     ) -> crate::Result<CreatedApiKey> {
         #[derive(Deserialize)]
         struct Meta {
-            secret_token: String,
+            secret_token: Secret,
         }
         #[derive(Deserialize)]
         struct CreatedApiKeyObj {
@@ -265,24 +271,26 @@ impl Client {
             description: String,
             allowed_ips: Option<Vec<String>>,
         }
-        self.request_with_body::<CreatedApiKeyObj, _>(
-            Method::POST,
-            "account/api-keys",
-            &CreateApiKeyBody {
-                description: description.into(),
-                allowed_ips,
-            },
-        )
-        .await
-        .map(|obj| CreatedApiKey {
-            key: obj.attributes,
-            secret_token: obj.meta.secret_token,
-        })
+        self.http
+            .request_with_body::<CreatedApiKeyObj, _>(
+                Method::POST,
+                "account/api-keys",
+                &CreateApiKeyBody {
+                    description: description.into(),
+                    allowed_ips,
+                },
+            )
+            .await
+            .map(|obj| CreatedApiKey {
+                key: obj.attributes,
+                secret_token: obj.meta.secret_token,
+            })
     }
 
     /// Deletes an API key that can be used to connect to this account
     pub async fn delete_api_key(&self, id: impl Into<String>) -> crate::Result<()> {
-        self.request::<EmptyBody>(Method::DELETE, &format!("account/api-keys/{}", id.into()))
+        self.http
+            .request::<EmptyBody>(Method::DELETE, &format!("account/api-keys/{}", id.into()))
             .await?;
         Ok(())
     }
@@ -86,6 +86,7 @@ impl Server<'_> {
     /// Gets the list of backups for this server
     pub async fn list_backups(&self) -> crate::Result<Vec<Backup>> {
         self.client
+            .http
             .request::<PteroList<Backup>>(Method::GET, &format!("servers/{}/backups", self.id))
             .await
             .map(|backups| backups.data)
@@ -112,6 +113,7 @@ impl Server<'_> {
         options: impl Into<BackupParams>,
     ) -> crate::Result<Backup> {
         self.client
+            .http
             .request_with_body::<PteroObject<Backup>, _>(
                 Method::POST,
                 &format!("servers/{}/backups", self.id),
@@ -124,6 +126,7 @@ impl Server<'_> {
     /// Gets the backup with the given ID
     pub async fn get_backup(&self, id: Uuid) -> crate::Result<Backup> {
         self.client
+            .http
             .request::<PteroObject<Backup>>(
                 Method::GET,
                 &format!("servers/{}/backups/{}", self.id, id),
@@ -139,6 +142,7 @@ impl Server<'_> {
             url: String,
         }
         self.client
+            .http
             .request::<PteroObject<Url>>(
                 Method::GET,
                 &format!("servers/{}/backups/{}/download", self.id, id),
@@ -150,6 +154,7 @@ impl Server<'_> {
     /// Deletes the backup with the given ID
     pub async fn delete_backup(&self, id: Uuid) -> crate::Result<()> {
         self.client
+            .http
             .request::<EmptyBody>(
                 Method::DELETE,
                 &format!("servers/{}/backups/{}", self.id, id),
@@ -3,6 +3,7 @@
 use crate::client::{IpAndPort, Server};
 use crate::http::EmptyBody;
 use crate::structs::{PteroList, PteroObject};
+use crate::Secret;
 use reqwest::Method;
 use serde::{Deserialize, Deserializer, Serialize};
 
@@ -35,16 +36,16 @@ pub struct DatabaseRelationships {
     /// doesn't return the password.
     #[serde(deserialize_with = "deserialize_password")]
     #[serde(default)]
-    pub password: Option<String>,
+    pub password: Option<Secret>,
 }
 
-fn deserialize_password<'de, D>(deserializer: D) -> Result<Option<String>, D::Error>
+pub(crate) fn deserialize_password<'de, D>(deserializer: D) -> Result<Option<Secret>, D::Error>
 where
     D: Deserializer<'de>,
 {
     #[derive(Deserialize)]
     struct PasswordObj {
-        password: String,
+        password: Secret,
     }
     let password: PteroObject<PasswordObj> = Deserialize::deserialize(deserializer)?;
     Ok(Some(password.attributes.password))
@@ -54,6 +55,7 @@ impl Server<'_> {
     /// Lists the databases on a server
     pub async fn list_databases(&self) -> crate::Result<Vec<ServerDatabase>> {
         self.client
+            .http
             .request::<PteroList<ServerDatabase>>(
                 Method::GET,
                 &format!("servers/{}/databases", self.id),
@@ -75,6 +77,7 @@ impl Server<'_> {
             remote: String,
         }
         self.client
+            .http
             .request_with_body::<PteroObject<ServerDatabase>, _>(
                 Method::POST,
                 &format!("servers/{}/databases", self.id),
@@ -93,6 +96,7 @@ impl Server<'_> {
         id: impl Into<String>,
     ) -> crate::Result<ServerDatabase> {
         self.client
+            .http
             .request::<PteroObject<ServerDatabase>>(
                 Method::POST,
                 &format!(
@@ -108,6 +112,7 @@ impl Server<'_> {
     /// Deletes the given database
     pub async fn delete_database(&self, id: impl Into<String>) -> crate::Result<()> {
         self.client
+            .http
             .request::<EmptyBody>(
                 Method::DELETE,
                 &format!("servers/{}/databases/{}", self.id, id.into()),
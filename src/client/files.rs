@@ -210,6 +210,7 @@ impl Server<'_> {
     /// Lists the files in a directory on the server
     pub async fn list_files(&self, directory: impl AsRef<str>) -> crate::Result<Vec<PteroFile>> {
         self.client
+            .http
             .request::<PteroList<PteroFile>>(
                 Method::GET,
                 &format!(
@@ -224,8 +225,8 @@ impl Server<'_> {
 
     /// Gets the file contents of a file on the server, as a [`Bytes`]
     pub async fn file_contents(&self, file: impl AsRef<str>) -> crate::Result<Bytes> {
-        Ok(self
-            .client
+        self.client
+            .http
             .get_response::<_, NullErrorHandler>(
                 Method::GET,
                 &format!(
@@ -237,13 +238,13 @@ impl Server<'_> {
             )
             .await?
             .bytes()
-            .await?)
+            .await
     }
 
     /// Gets the file contents of a UTF8-encoded file on the server, as a [`String`]
     pub async fn file_contents_text(&self, file: impl AsRef<str>) -> crate::Result<String> {
-        Ok(self
-            .client
+        self.client
+            .http
             .get_response::<_, NullErrorHandler>(
                 Method::GET,
                 &format!(
@@ -255,7 +256,7 @@ impl Server<'_> {
             )
             .await?
             .text()
-            .await?)
+            .await
     }
 
     /// Streams the file contents of a  file on the server, as an async stream
@@ -266,6 +267,7 @@ impl Server<'_> {
     ) -> crate::Result<impl futures_core::Stream<Item = reqwest::Result<Bytes>>> {
         Ok(self
             .client
+            .http
             .get_response::<_, NullErrorHandler>(
                 Method::GET,
                 &format!(
@@ -286,6 +288,7 @@ impl Server<'_> {
             url: String,
         }
         self.client
+            .http
             .request::<PteroObject<Url>>(
                 Method::GET,
                 &format!(
@@ -321,6 +324,7 @@ impl Server<'_> {
             files: Vec<FileRename>,
         }
         self.client
+            .http
             .request_with_body::<EmptyBody, _>(
                 Method::PUT,
                 &format!("servers/{}/files/rename", self.id),
@@ -349,6 +353,7 @@ impl Server<'_> {
             location: String,
         }
         self.client
+            .http
             .request_with_body::<EmptyBody, _>(
                 Method::POST,
                 &format!("servers/{}/files/copy", self.id),
@@ -393,6 +398,7 @@ impl Server<'_> {
         data: impl Into<Body>,
     ) -> crate::Result<()> {
         self.client
+            .http
             .request_with_text_body::<EmptyBody, _>(
                 Method::POST,
                 &format!(
@@ -425,6 +431,7 @@ impl Server<'_> {
             files: Vec<String>,
         }
         self.client
+            .http
             .request_with_body::<PteroObject<PteroFile>, _>(
                 Method::POST,
                 &format!("servers/{}/files/compress", self.id),
@@ -451,6 +458,7 @@ impl Server<'_> {
         let dest = dest.into();
         let file = relativize(&file.into(), &dest);
         self.client
+            .http
             .request_with_body::<EmptyBody, _>(
                 Method::POST,
                 &format!("servers/{}/files/decompress", self.id),
@@ -473,6 +481,7 @@ impl Server<'_> {
             files: Vec<String>,
         }
         self.client
+            .http
             .request_with_body::<EmptyBody, _>(
                 Method::POST,
                 &format!("servers/{}/files/delete", self.id),
@@ -496,6 +505,7 @@ impl Server<'_> {
         }
         let (dir, folder_name) = split_dir_filename(folder_ref);
         self.client
+            .http
             .request_with_body::<EmptyBody, _>(
                 Method::POST,
                 &format!("servers/{}/files/create-folder", self.id),
@@ -515,6 +525,7 @@ impl Server<'_> {
             url: String,
         }
         self.client
+            .http
             .request::<PteroObject<Url>>(Method::GET, &format!("servers/{}/files/upload", self.id,))
             .await
             .map(|url| url.attributes.url)
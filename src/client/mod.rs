@@ -1,11 +1,12 @@
 //! Pterodactyl Client API implementation, for all endpoints under `api/client`
 
-use crate::http::EmptyBody;
-use crate::structs::{PteroList, PteroObject};
+use crate::http::{collect_pages, Api, EmptyBody, HttpCore};
+use crate::structs::{Page, PteroList, PteroObject};
 use reqwest::Method;
 use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
 use std::collections::HashMap;
-use std::sync::RwLock;
+use std::sync::Arc;
 pub use structs::*;
 
 pub mod account;
@@ -21,44 +22,103 @@ pub mod users;
 #[cfg(feature = "websocket")]
 pub mod websocket;
 
-/// The rate limits of the API key
-#[derive(Debug, Copy, Clone)]
-pub struct RateLimits {
-    /// The request limit per minute
-    pub limit: u32,
-    /// The number of requests remaining in this minute
-    pub limit_remaining: u32,
-}
+pub use crate::RateLimits;
+use crate::{Diagnosis, KeyType};
 
-/// A Pterodactyl client, to make requests to the Pterodactyl client API
-#[derive(Debug)]
+/// A Pterodactyl client, to make requests to the Pterodactyl client API. Cloning the client is
+/// cheap, and the clones share their connection and rate limit state
+#[derive(Debug, Clone)]
 pub struct Client {
-    pub(crate) url: String,
-    pub(crate) client: reqwest::Client,
-    pub(crate) api_key: String,
-    pub(crate) rate_limits: RwLock<Option<RateLimits>>,
+    pub(crate) http: Arc<HttpCore>,
 }
 
 impl Client {
     /// Gets the rate limit information after the previous request
     pub fn get_rate_limits(&self) -> Option<RateLimits> {
-        *self.rate_limits.read().unwrap()
+        self.http.rate_limits()
+    }
+
+    /// Checks that the panel is reachable and accepts the API key, detects whether the key is a
+    /// client or application key and which features the panel supports, and reports the problems
+    /// it found. Unlike other requests, problems are reported in the diagnosis rather than as
+    /// errors
+    pub async fn diagnose(&self) -> Diagnosis {
+        crate::diagnostics::diagnose(&self.http, KeyType::Client, "account").await
     }
 
-    /// Lists the servers that this account has access to
+    /// Lists the servers that this account has access to, on the first page of servers. Use
+    /// [`Client::list_servers_all`] to list every server
     pub async fn list_servers(&self) -> crate::Result<Vec<ServerStruct>> {
-        self.request::<PteroList<ServerStruct>>(Method::GET, "")
+        self.http
+            .request::<PteroList<ServerStruct>>(Method::GET, "")
             .await
             .map(|servers| servers.data)
     }
 
+    /// Gets a single page of the servers matching the given query, along with the pagination
+    /// metadata
+    ///
+    /// ```no_run
+    /// # use pterodactyl_api::client::{Client, ClientServerQuery, ServerListType};
+    /// # let client: Client = todo!();
+    /// # async {
+    /// let page = client
+    ///     .list_servers_filtered(
+    ///         &ClientServerQuery::new()
+    ///             .with_type(ServerListType::AdminAll)
+    ///             .with_name("lobby")
+    ///             .with_page(2),
+    ///     )
+    ///     .await?;
+    /// # pterodactyl_api::Result::Ok(())
+    /// # };
+    /// ```
+    pub async fn list_servers_filtered(
+        &self,
+        query: &ClientServerQuery,
+    ) -> crate::Result<Page<ServerStruct>> {
+        self.http
+            .request::<Page<ServerStruct>>(Method::GET, &query.endpoint())
+            .await
+    }
+
+    /// Lists every server matching the given query, fetching every page
+    pub async fn list_servers_all(
+        &self,
+        query: &ClientServerQuery,
+    ) -> crate::Result<Vec<ServerStruct>> {
+        collect_pages(|page| self.list_servers_filtered_page(query, page)).await
+    }
+
+    /// Lazily streams every server matching the given query, fetching each page as it is needed
+    #[cfg(feature = "stream")]
+    pub fn list_servers_stream(
+        &self,
+        query: ClientServerQuery,
+    ) -> impl futures_core::Stream<Item = crate::Result<ServerStruct>> + '_ {
+        crate::http::stream_pages(move |page| {
+            let query = query.clone();
+            async move { self.list_servers_filtered_page(&query, page).await }
+        })
+    }
+
+    async fn list_servers_filtered_page(
+        &self,
+        query: &ClientServerQuery,
+        page: u32,
+    ) -> crate::Result<Page<ServerStruct>> {
+        self.list_servers_filtered(&query.clone().with_page(page))
+            .await
+    }
+
     /// Gets all available permissions on this instance of Pterodactyl
     pub async fn get_permissions(&self) -> crate::Result<HashMap<String, PermissionGroup>> {
         #[derive(Deserialize)]
         struct Permissions {
             permissions: HashMap<String, PermissionGroup>,
         }
-        self.request::<PteroObject<Permissions>>(Method::GET, "permissions")
+        self.http
+            .request::<PteroObject<Permissions>>(Method::GET, "permissions")
             .await
             .map(|permissions| permissions.attributes.permissions)
     }
@@ -67,10 +127,23 @@ impl Client {
     pub fn get_server(&self, id: impl Into<String>) -> Server<'_> {
         Server {
             id: id.into(),
-            client: self,
+            client: Cow::Borrowed(self),
         }
     }
 
+    /// Gets a server with a specific ID which owns a clone of this client, so that it can be
+    /// moved into spawned tasks or stored without borrowing the client
+    ///
+    /// ```no_run
+    /// # use pterodactyl_api::client::{Client, OwnedServer};
+    /// # let client: Client = todo!();
+    /// let server: OwnedServer = client.get_owned_server("1a7ce997");
+    /// tokio::spawn(async move { server.send_command("say hi").await });
+    /// ```
+    pub fn get_owned_server(&self, id: impl Into<String>) -> OwnedServer {
+        self.get_server(id).into_owned()
+    }
+
     #[cfg(test)]
     pub(crate) fn get_test_server(&self) -> Server<'_> {
         self.get_server(
@@ -79,60 +152,44 @@ impl Client {
     }
 }
 
-/// A builder for a client
-#[derive(Debug)]
-pub struct ClientBuilder {
-    url: String,
-    client: Option<reqwest::Client>,
-    api_key: String,
-}
-
-impl ClientBuilder {
-    /// Creates a new client builder, connecting to the given URL where a Pterodactyl server is
-    /// hosted, using the given API key for authentication
-    pub fn new(url: impl Into<String>, api_key: impl Into<String>) -> Self {
-        let mut url = url.into();
-        if !url.ends_with('/') {
-            url.push('/');
-        }
-        url.push_str("api/client/");
-        Self {
-            url,
-            client: None,
-            api_key: api_key.into(),
-        }
-    }
+impl Api for Client {
+    const PATH: &'static str = "api/client/";
 
-    /// Uses the specified [`reqwest::Client`] for requests instead of making a default one
-    pub fn with_client(self, client: reqwest::Client) -> Self {
+    fn from_http(http: HttpCore) -> Self {
         Self {
-            client: Some(client),
-            ..self
-        }
-    }
-
-    /// Builds a client
-    pub fn build(self) -> Client {
-        Client {
-            url: self.url,
-            client: self.client.unwrap_or_default(),
-            api_key: self.api_key,
-            rate_limits: RwLock::new(None),
+            http: Arc::new(http),
         }
     }
 }
 
-/// Represents a Pterodactyl server, with which requests specific to a server can be made
-#[derive(Debug)]
+/// A builder for a client
+pub type ClientBuilder = crate::ClientBuilder<Client>;
+
+/// Represents a Pterodactyl server, with which requests specific to a server can be made. The
+/// server either borrows the client it was created from, or owns a clone of it
+#[derive(Debug, Clone)]
 pub struct Server<'a> {
     pub(crate) id: String,
-    pub(crate) client: &'a Client,
+    pub(crate) client: Cow<'a, Client>,
 }
 
+/// A server which owns its client, and so can be moved into spawned tasks, stored alongside the
+/// client or returned from the function that created the client
+pub type OwnedServer = Server<'static>;
+
 impl Server<'_> {
+    /// Converts this server into one which owns a clone of its client
+    pub fn into_owned(self) -> OwnedServer {
+        Server {
+            id: self.id,
+            client: Cow::Owned(self.client.into_owned()),
+        }
+    }
+
     /// Gets information about this server
     pub async fn get_details(&self) -> crate::Result<ServerStruct> {
         self.client
+            .http
             .request::<PteroObject<ServerStruct>>(Method::GET, &format!("servers/{}", self.id))
             .await
             .map(|server| server.attributes)
@@ -141,6 +198,7 @@ impl Server<'_> {
     /// Gets resources for this server
     pub async fn get_resources(&self) -> crate::Result<ServerResources> {
         self.client
+            .http
             .request::<PteroObject<ServerResources>>(
                 Method::GET,
                 &format!("servers/{}/resources", self.id),
@@ -156,6 +214,7 @@ impl Server<'_> {
             command: String,
         }
         self.client
+            .http
             .request_with_body::<EmptyBody, _>(
                 Method::POST,
                 &format!("servers/{}/command", self.id),
@@ -174,6 +233,7 @@ impl Server<'_> {
             signal: PowerSignal,
         }
         self.client
+            .http
             .request_with_body::<EmptyBody, _>(
                 Method::POST,
                 &format!("servers/{}/power", self.id),
@@ -186,7 +246,7 @@ impl Server<'_> {
 
 #[cfg(test)]
 mod test {
-    use crate::client::{Client, ClientBuilder};
+    use crate::client::{Client, ClientBuilder, ClientServerQuery, ServerListType};
 
     fn make_test_client() -> Client {
         ClientBuilder::new(
@@ -196,6 +256,38 @@ mod test {
         .build()
     }
 
+    #[cfg(feature = "mock-server")]
+    #[tokio::test]
+    async fn test_owned_server() {
+        let panel = crate::mock::MockPanel::start().await.unwrap();
+        let id = panel.servers().remove(0);
+        let server = {
+            let client = ClientBuilder::new(panel.url(), "key").build();
+            client.get_owned_server(&id)
+        };
+        let details = tokio::spawn(async move { server.get_details().await })
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(details.identifier, id);
+    }
+
+    #[test]
+    fn test_server_query_endpoint() {
+        assert_eq!(ClientServerQuery::new().endpoint(), "");
+        assert_eq!(
+            ClientServerQuery::new()
+                .with_type(ServerListType::AdminAll)
+                .with_search("lobby 1")
+                .with_name("old")
+                .with_name("new")
+                .with_per_page(100)
+                .with_page(3)
+                .endpoint(),
+            "?type=admin-all&filter%5B%2A%5D=lobby%201&filter%5Bname%5D=new&per_page=100&page=3"
+        );
+    }
+
     #[tokio::test]
     async fn test_list_servers() {
         println!("{:?}", make_test_client().list_servers().await);
@@ -1,9 +1,9 @@
 //! API for endpoints under `api/client/server/{server}/network`
 
-use crate::client::{ErrorResponse, Server};
+use crate::client::Server;
 use crate::http::{EmptyBody, ErrorHandler};
-use crate::structs::{PteroList, PteroObject};
-use reqwest::{Method, Response, StatusCode};
+use crate::structs::{ErrorResponse, PteroList, PteroObject};
+use reqwest::{Method, StatusCode};
 use serde::{Deserialize, Serialize};
 
 /// A network allocation on the server
@@ -28,6 +28,7 @@ impl Server<'_> {
     /// Lists the network allocations on this server
     pub async fn list_network_allocations(&self) -> crate::Result<Vec<Allocation>> {
         self.client
+            .http
             .request::<PteroList<Allocation>>(
                 Method::GET,
                 &format!("servers/{}/network/allocations", self.id),
@@ -39,6 +40,7 @@ impl Server<'_> {
     /// Automatically assigns a new network allocation if auto-assign is enabled on the instance
     pub async fn create_network_allocation(&self) -> crate::Result<Allocation> {
         self.client
+            .http
             .request::<PteroObject<Allocation>>(
                 Method::POST,
                 &format!("servers/{}/network/allocations", self.id),
@@ -58,6 +60,7 @@ impl Server<'_> {
             notes: String,
         }
         self.client
+            .http
             .request_with_body::<PteroObject<Allocation>, _>(
                 Method::POST,
                 &format!("servers/{}/network/allocations/{}", self.id, allocation_id),
@@ -75,6 +78,7 @@ impl Server<'_> {
         allocation_id: u64,
     ) -> crate::Result<Allocation> {
         self.client
+            .http
             .request::<PteroObject<Allocation>>(
                 Method::POST,
                 &format!(
@@ -91,11 +95,10 @@ impl Server<'_> {
     pub async fn delete_network_allocation(&self, allocation_id: u64) -> crate::Result<()> {
         struct DeleteNetworkAllocationErrorHandler;
         impl ErrorHandler for DeleteNetworkAllocationErrorHandler {
-            async fn get_error(response: Response) -> Option<crate::Error> {
-                if response.status() != StatusCode::BAD_REQUEST {
+            fn get_error(status: StatusCode, error: &ErrorResponse) -> Option<crate::Error> {
+                if status != StatusCode::BAD_REQUEST {
                     return None;
                 }
-                let error: ErrorResponse = response.json().await.ok()?;
                 if error.is_error("DisplayException") {
                     Some(crate::Error::PrimaryAllocation)
                 } else {
@@ -104,6 +107,7 @@ impl Server<'_> {
             }
         }
         self.client
+            .http
             .request_with_error_handler::<EmptyBody, _, DeleteNetworkAllocationErrorHandler>(
                 Method::DELETE,
                 &format!("servers/{}/network/allocations/{}", self.id, allocation_id),
@@ -508,6 +508,7 @@ impl Server<'_> {
     /// Lists the schedules on this server
     pub async fn list_schedules(&self) -> crate::Result<Vec<Schedule>> {
         self.client
+            .http
             .request::<PteroList<Schedule>>(Method::GET, &format!("servers/{}/schedules", self.id))
             .await
             .map(|schedules| schedules.data)
@@ -519,6 +520,7 @@ impl Server<'_> {
         schedule: impl Into<ScheduleParams>,
     ) -> crate::Result<Schedule> {
         self.client
+            .http
             .request_with_body::<PteroObject<Schedule>, _>(
                 Method::POST,
                 &format!("servers/{}/schedules", self.id),
@@ -531,6 +533,7 @@ impl Server<'_> {
     /// Gets the schedule with the given ID
     pub async fn get_schedule(&self, id: u64) -> crate::Result<Schedule> {
         self.client
+            .http
             .request::<PteroObject<Schedule>>(
                 Method::GET,
                 &format!("servers/{}/schedules/{}", self.id, id),
@@ -546,6 +549,7 @@ impl Server<'_> {
         schedule: impl Into<ScheduleParams>,
     ) -> crate::Result<Schedule> {
         self.client
+            .http
             .request_with_body::<PteroObject<Schedule>, _>(
                 Method::POST,
                 &format!("servers/{}/schedules/{}", self.id, id),
@@ -558,6 +562,7 @@ impl Server<'_> {
     /// Deletes the schedule with the given ID
     pub async fn delete_schedule(&self, id: u64) -> crate::Result<()> {
         self.client
+            .http
             .request::<EmptyBody>(
                 Method::DELETE,
                 &format!("servers/{}/schedules/{}", self.id, id),
@@ -573,6 +578,7 @@ impl Server<'_> {
         task: impl Into<TaskParams>,
     ) -> crate::Result<ScheduleTask> {
         self.client
+            .http
             .request_with_body::<PteroObject<ScheduleTask>, _>(
                 Method::POST,
                 &format!("servers/{}/schedules/{}/tasks", self.id, schedule_id),
@@ -590,6 +596,7 @@ impl Server<'_> {
         task: impl Into<TaskParams>,
     ) -> crate::Result<ScheduleTask> {
         self.client
+            .http
             .request_with_body::<PteroObject<ScheduleTask>, _>(
                 Method::POST,
                 &format!(
@@ -605,6 +612,7 @@ impl Server<'_> {
     /// Deletes a task from a schedule
     pub async fn delete_task(&self, schedule_id: u64, task_id: u64) -> crate::Result<()> {
         self.client
+            .http
             .request::<EmptyBody>(
                 Method::DELETE,
                 &format!(
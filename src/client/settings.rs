@@ -13,6 +13,7 @@ impl Server<'_> {
             name: String,
         }
         self.client
+            .http
             .request_with_body::<EmptyBody, _>(
                 Method::POST,
                 &format!("servers/{}/settings/rename", self.id),
@@ -25,6 +26,7 @@ impl Server<'_> {
     /// Reinstalls this server
     pub async fn reinstall(&self) -> crate::Result<()> {
         self.client
+            .http
             .request::<EmptyBody>(
                 Method::POST,
                 &format!("servers/{}/settings/reinstall", self.id),
@@ -56,6 +56,7 @@ impl Server<'_> {
             meta: StartupMeta,
         }
         self.client
+            .http
             .request::<StartupDataObj>(Method::GET, &format!("servers/{}/startup", self.id))
             .await
             .map(|data| StartupData {
@@ -78,6 +79,7 @@ impl Server<'_> {
             value: String,
         }
         self.client
+            .http
             .request_with_body::<PteroObject<Variable>, _>(
                 Method::PUT,
                 &format!("servers/{}/startup/variable", self.id),
@@ -98,6 +100,7 @@ impl Server<'_> {
         }
         match self
             .client
+            .http
             .request_with_body::<EmptyBody, _>(
                 Method::PUT,
                 &format!("servers/{}/settings/docker-image", self.id),
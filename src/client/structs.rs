@@ -1,4 +1,5 @@
 use crate::client::network::Allocation;
+use crate::http::QueryString;
 use serde::{Deserialize, Deserializer, Serialize};
 use std::collections::HashMap;
 use std::fmt::{Display, Formatter};
@@ -210,18 +211,112 @@ impl Display for PowerSignal {
     }
 }
 
-#[derive(Deserialize)]
-pub(crate) struct ErrorResponse {
-    pub(crate) errors: Vec<ErrorResponseError>,
+/// Which servers to list when listing servers through the client API
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Hash, Copy, Clone)]
+#[serde(rename_all = "kebab-case")]
+pub enum ServerListType {
+    /// Servers which the account can access as an admin, but is not the owner or a subuser of
+    Admin,
+    /// Every server on the panel. Requires the account to be an admin
+    AdminAll,
+    /// Only servers owned by the account
+    Owner,
 }
 
-impl ErrorResponse {
-    pub(crate) fn is_error(&self, error: &str) -> bool {
-        self.errors.iter().any(|e| e.code == error)
+impl Display for ServerListType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ServerListType::Admin => f.write_str("admin"),
+            ServerListType::AdminAll => f.write_str("admin-all"),
+            ServerListType::Owner => f.write_str("owner"),
+        }
     }
 }
 
-#[derive(Deserialize)]
-pub(crate) struct ErrorResponseError {
-    pub(crate) code: String,
+/// The parameters to list servers through the client API
+#[derive(Debug, Default, PartialEq, Eq, Hash, Clone)]
+pub struct ClientServerQuery {
+    list_type: Option<ServerListType>,
+    filters: Vec<(&'static str, String)>,
+    per_page: Option<u32>,
+    page: Option<u32>,
+}
+
+impl ClientServerQuery {
+    /// Creates the default parameters, which list the servers the account owns or is a subuser of
+    pub fn new() -> Self {
+        ClientServerQuery::default()
+    }
+
+    /// Sets which servers to list
+    pub fn with_type(self, list_type: ServerListType) -> Self {
+        ClientServerQuery {
+            list_type: Some(list_type),
+            ..self
+        }
+    }
+
+    /// Only lists servers where the name, UUID, external ID or allocation contains the given text
+    pub fn with_search(self, search: impl Into<String>) -> Self {
+        self.with_filter("*", search)
+    }
+
+    /// Only lists servers with the given UUID
+    pub fn with_uuid(self, uuid: impl Into<String>) -> Self {
+        self.with_filter("uuid", uuid)
+    }
+
+    /// Only lists servers where the name contains the given text
+    pub fn with_name(self, name: impl Into<String>) -> Self {
+        self.with_filter("name", name)
+    }
+
+    /// Only lists servers where the description contains the given text
+    pub fn with_description(self, description: impl Into<String>) -> Self {
+        self.with_filter("description", description)
+    }
+
+    /// Only lists servers with the given external ID
+    pub fn with_external_id(self, external_id: impl Into<String>) -> Self {
+        self.with_filter("external_id", external_id)
+    }
+
+    /// Sets the maximum number of servers on each page
+    pub fn with_per_page(self, per_page: u32) -> Self {
+        ClientServerQuery {
+            per_page: Some(per_page),
+            ..self
+        }
+    }
+
+    /// Sets which page to get, starting from 1
+    pub fn with_page(self, page: u32) -> Self {
+        ClientServerQuery {
+            page: Some(page),
+            ..self
+        }
+    }
+
+    fn with_filter(mut self, filter: &'static str, value: impl Into<String>) -> Self {
+        self.filters.retain(|(key, _)| *key != filter);
+        self.filters.push((filter, value.into()));
+        self
+    }
+
+    pub(crate) fn endpoint(&self) -> String {
+        let mut query = QueryString::default();
+        if let Some(list_type) = self.list_type {
+            query.push("type", list_type);
+        }
+        for (filter, value) in &self.filters {
+            query.push(format!("filter[{filter}]"), value);
+        }
+        if let Some(per_page) = self.per_page {
+            query.push("per_page", per_page);
+        }
+        if let Some(page) = self.page {
+            query.push("page", page);
+        }
+        query.endpoint("")
+    }
 }
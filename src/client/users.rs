@@ -40,6 +40,7 @@ impl Server<'_> {
     /// Lists the users with permissions on this server
     pub async fn list_users(&self) -> crate::Result<Vec<User>> {
         self.client
+            .http
             .request::<PteroList<User>>(Method::GET, &format!("servers/{}/users", self.id))
             .await
             .map(|users| users.data)
@@ -57,6 +58,7 @@ impl Server<'_> {
             permissions: Vec<String>,
         }
         self.client
+            .http
             .request_with_body::<PteroObject<User>, _>(
                 Method::POST,
                 &format!("servers/{}/users", self.id),
@@ -72,6 +74,7 @@ impl Server<'_> {
     /// Gets the user with permissions with the given UUID
     pub async fn get_user(&self, id: Uuid) -> crate::Result<User> {
         self.client
+            .http
             .request::<PteroObject<User>>(Method::GET, &format!("servers/{}/users/{}", self.id, id))
             .await
             .map(|user| user.attributes)
@@ -88,6 +91,7 @@ impl Server<'_> {
             permissions: Vec<String>,
         }
         self.client
+            .http
             .request_with_body::<PteroObject<User>, _>(
                 Method::POST,
                 &format!("servers/{}/users/{}", self.id, id),
@@ -100,6 +104,7 @@ impl Server<'_> {
     /// Removes a user from the server
     pub async fn delete_user(&self, id: Uuid) -> crate::Result<()> {
         self.client
+            .http
             .request::<EmptyBody>(Method::DELETE, &format!("servers/{}/users/{}", self.id, id))
             .await?;
         Ok(())
@@ -1,7 +1,9 @@
 //! A Pterodactyl websocket client
 
 use crate::client::{PowerSignal, Server, ServerState};
+use crate::transport::{MessageDirection, Transport};
 use crate::Error::WebsocketTokenExpired;
+use crate::Secret;
 use async_tungstenite::tungstenite::Message;
 use async_tungstenite::WebSocketStream;
 use futures_io::{AsyncRead, AsyncWrite};
@@ -31,6 +33,7 @@ struct WebSocketImpl<'a, S, L> {
 #[derive(Debug)]
 pub struct WebSocketHandleImpl<'a, S> {
     socket: &'a mut WebSocketStream<S>,
+    transport: &'a dyn Transport,
     stop: bool,
 }
 
@@ -128,7 +131,7 @@ pub struct ServerNetworkStats {
 
 #[derive(Deserialize)]
 struct WebSocketLink {
-    token: String,
+    token: Secret,
     socket: String,
 }
 
@@ -162,6 +165,7 @@ impl<'a> Server<'a> {
             data: WebSocketLink,
         }
         self.client
+            .http
             .request::<Data>(Method::GET, &format!("servers/{}/websocket", self.id))
             .await
             .map(|link| link.data)
@@ -194,11 +198,16 @@ where
     S: AsyncRead + AsyncWrite + Unpin + Send,
     L: for<'b> PteroWebSocketListener<WebSocketHandleImpl<'b, S>>,
 {
-    async fn run_loop(mut self, token: String) -> crate::Result<()> {
+    async fn run_loop(mut self, token: Secret) -> crate::Result<()> {
         self.auth(token).await?;
         while let Some(message) = self.socket.next().await {
             match message? {
                 Message::Text(text) => {
+                    self.server
+                        .client
+                        .http
+                        .transport()
+                        .websocket_message(MessageDirection::Received, &text);
                     if self.handle_message(text.to_string()).await? {
                         break;
                     }
@@ -238,6 +247,7 @@ where
             IncomingEvent::AuthSuccess => {
                 let mut handle = WebSocketHandleImpl {
                     socket: &mut self.socket,
+                    transport: self.server.client.http.transport(),
                     stop: false,
                 };
                 if !self.ready {
@@ -249,6 +259,7 @@ where
             IncomingEvent::Status => {
                 let mut handle = WebSocketHandleImpl {
                     socket: &mut self.socket,
+                    transport: self.server.client.http.transport(),
                     stop: false,
                 };
                 self.listener
@@ -267,6 +278,7 @@ where
             IncomingEvent::ConsoleOutput => {
                 let mut handle = WebSocketHandleImpl {
                     socket: &mut self.socket,
+                    transport: self.server.client.http.transport(),
                     stop: false,
                 };
                 for output in message.args {
@@ -279,6 +291,7 @@ where
             IncomingEvent::InstallOutput => {
                 let mut handle = WebSocketHandleImpl {
                     socket: &mut self.socket,
+                    transport: self.server.client.http.transport(),
                     stop: false,
                 };
                 for output in message.args {
@@ -291,6 +304,7 @@ where
             IncomingEvent::Stats => {
                 let mut handle = WebSocketHandleImpl {
                     socket: &mut self.socket,
+                    transport: self.server.client.http.transport(),
                     stop: false,
                 };
                 let json = message
@@ -311,36 +325,58 @@ where
         }
     }
 
-    async fn auth(&mut self, token: String) -> crate::Result<()> {
+    async fn auth(&mut self, token: Secret) -> crate::Result<()> {
         #[derive(Serialize)]
         struct AuthEvent {
             event: &'static str,
-            args: [String; 1],
+            args: [Secret; 1],
         }
         let payload = serde_json::to_string(&AuthEvent {
             event: "auth",
             args: [token],
         })?;
-        Ok(self.socket.send(Message::text(payload)).await?)
+        send_text(
+            &mut self.socket,
+            self.server.client.http.transport(),
+            payload,
+        )
+        .await
     }
 }
 
+/// Sends a text message over the websocket, reporting it to the transport of the client
+async fn send_text<S>(
+    socket: &mut WebSocketStream<S>,
+    transport: &dyn Transport,
+    payload: String,
+) -> crate::Result<()>
+where
+    S: AsyncRead + AsyncWrite + Unpin + Send,
+{
+    transport.websocket_message(MessageDirection::Sent, &payload);
+    Ok(socket.send(Message::text(payload)).await?)
+}
+
 impl<S> PteroWebSocketHandle for WebSocketHandleImpl<'_, S>
 where
     S: AsyncRead + AsyncWrite + Unpin + Send,
 {
     async fn request_stats(&mut self) -> crate::Result<()> {
-        Ok(self
-            .socket
-            .send(Message::text("{\"event\":\"send stats\",\"args\":[null]}"))
-            .await?)
+        send_text(
+            self.socket,
+            self.transport,
+            "{\"event\":\"send stats\",\"args\":[null]}".to_owned(),
+        )
+        .await
     }
 
     async fn request_logs(&mut self) -> crate::Result<()> {
-        Ok(self
-            .socket
-            .send(Message::text("{\"event\":\"send logs\",\"args\":[null]}"))
-            .await?)
+        send_text(
+            self.socket,
+            self.transport,
+            "{\"event\":\"send logs\",\"args\":[null]}".to_owned(),
+        )
+        .await
     }
 
     async fn send_power_signal(&mut self, signal: PowerSignal) -> crate::Result<()> {
@@ -353,7 +389,7 @@ where
             event: "set state",
             args: [signal],
         })?;
-        Ok(self.socket.send(Message::text(payload)).await?)
+        send_text(self.socket, self.transport, payload).await
     }
 
     async fn send_command(&mut self, command: impl Into<String> + Send) -> crate::Result<()> {
@@ -366,7 +402,7 @@ where
             event: "send command",
             args: [command.into()],
         })?;
-        Ok(self.socket.send(Message::text(payload)).await?)
+        send_text(self.socket, self.transport, payload).await
     }
 
     fn disconnect(&mut self) {
@@ -0,0 +1,291 @@
+//! Providers of the API key a client authenticates with, which is read again for every request so
+//! that rotated keys are picked up without rebuilding the client. A fixed key is given to
+//! [`crate::ClientBuilder::new`], and any other provider to
+//! [`crate::ClientBuilder::with_credentials`]:
+//!
+//! ```no_run
+//! use pterodactyl_api::client::ClientBuilder;
+//! use pterodactyl_api::credentials::FileCredentials;
+//!
+//! let client = ClientBuilder::new("https://pterodactyl.example.com", "")
+//!     .with_credentials(FileCredentials::new("/run/secrets/pterodactyl_key"))
+//!     .build();
+//! ```
+
+use crate::Secret;
+use std::fmt;
+use std::path::PathBuf;
+use std::sync::{Mutex, PoisonError};
+use std::time::{Duration, Instant, SystemTime};
+
+/// Provides the API key a client authenticates with
+pub trait CredentialProvider: Send + Sync + fmt::Debug {
+    /// Gets the API key to authenticate a request with. Called before every request
+    fn api_key(&self) -> crate::Result<Secret>;
+
+    /// Called when the panel rejected the API key with `401 Unauthorized`, after which the key is
+    /// read again, and the request is sent again if the key changed. Providers which cache the key
+    /// should reload it. Does nothing by default
+    fn refresh(&self) {}
+}
+
+/// A fixed API key
+impl CredentialProvider for Secret {
+    fn api_key(&self) -> crate::Result<Secret> {
+        Ok(self.clone())
+    }
+}
+
+/// Reads the API key from an environment variable
+#[derive(Debug, Clone)]
+pub struct EnvCredentials {
+    variable: String,
+}
+
+impl EnvCredentials {
+    /// Reads the API key from the environment variable with the given name
+    pub fn new(variable: impl Into<String>) -> Self {
+        EnvCredentials {
+            variable: variable.into(),
+        }
+    }
+}
+
+impl CredentialProvider for EnvCredentials {
+    fn api_key(&self) -> crate::Result<Secret> {
+        std::env::var(&self.variable)
+            .map(Secret::from)
+            .map_err(|err| crate::Error::Credentials(err.into()))
+    }
+}
+
+/// Reads the API key from a file, such as one mounted by a secrets manager. The key is cached,
+/// and reloaded when the modification time, size or inode of the file changes, or the panel
+/// rejects the key. Whitespace around the key is ignored
+///
+/// Checking the file is a blocking filesystem call made on the task sending the request, so the
+/// file is checked at most once per second by default, see [`FileCredentials::with_check_interval`]
+#[derive(Debug)]
+pub struct FileCredentials {
+    path: PathBuf,
+    check_interval: Duration,
+    cached: Mutex<Option<CachedKey>>,
+}
+
+/// The key read from a file, along with the version of the file it was read from
+#[derive(Debug)]
+struct CachedKey {
+    version: FileVersion,
+    checked: Instant,
+    key: Secret,
+}
+
+/// Identifies a version of a file. Files replaced by renaming another file over them, as secrets
+/// managers do, get a new inode even if their size and modification time are unchanged
+#[derive(Debug, PartialEq, Eq)]
+struct FileVersion {
+    modified: Option<SystemTime>,
+    len: u64,
+    inode: Option<u64>,
+}
+
+impl FileVersion {
+    fn new(metadata: &std::fs::Metadata) -> Self {
+        #[cfg(unix)]
+        let inode = Some(std::os::unix::fs::MetadataExt::ino(metadata));
+        #[cfg(not(unix))]
+        let inode = None;
+        FileVersion {
+            modified: metadata.modified().ok(),
+            len: metadata.len(),
+            inode,
+        }
+    }
+}
+
+impl FileCredentials {
+    /// Reads the API key from the file at the given path
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        FileCredentials {
+            path: path.into(),
+            check_interval: Duration::from_secs(1),
+            cached: Mutex::new(None),
+        }
+    }
+
+    /// Sets how long the cached key is used before the file is checked for changes again. A
+    /// shorter interval picks up rotated keys sooner, at the cost of a blocking filesystem call
+    /// on more requests. Keys rejected by the panel are always reloaded
+    pub fn with_check_interval(self, check_interval: Duration) -> Self {
+        Self {
+            check_interval,
+            ..self
+        }
+    }
+}
+
+impl CredentialProvider for FileCredentials {
+    fn api_key(&self) -> crate::Result<Secret> {
+        let error = |err: std::io::Error| crate::Error::Credentials(err.into());
+        let mut cached = self.cached.lock().unwrap_or_else(PoisonError::into_inner);
+        if let Some(cached) = &*cached {
+            if cached.checked.elapsed() < self.check_interval {
+                return Ok(cached.key.clone());
+            }
+        }
+        let version = FileVersion::new(&std::fs::metadata(&self.path).map_err(error)?);
+        let checked = Instant::now();
+        if let Some(cached) = &mut *cached {
+            if version.modified.is_some() && cached.version == version {
+                cached.checked = checked;
+                return Ok(cached.key.clone());
+            }
+        }
+        let contents = Secret::from(std::fs::read_to_string(&self.path).map_err(error)?);
+        let key = Secret::from(contents.expose().trim());
+        *cached = Some(CachedKey {
+            version,
+            checked,
+            key: key.clone(),
+        });
+        Ok(key)
+    }
+
+    fn refresh(&self) {
+        *self.cached.lock().unwrap_or_else(PoisonError::into_inner) = None;
+    }
+}
+
+/// Gets the API key by calling a function, for example to fetch it from a secrets manager
+pub struct FnCredentials<F>(F);
+
+impl<F> FnCredentials<F>
+where
+    F: Fn() -> crate::Result<Secret> + Send + Sync,
+{
+    /// Gets the API key by calling the given function
+    pub fn new(function: F) -> Self {
+        FnCredentials(function)
+    }
+}
+
+impl<F> fmt::Debug for FnCredentials<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("FnCredentials").finish_non_exhaustive()
+    }
+}
+
+impl<F> CredentialProvider for FnCredentials<F>
+where
+    F: Fn() -> crate::Result<Secret> + Send + Sync,
+{
+    fn api_key(&self) -> crate::Result<Secret> {
+        (self.0)()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{CredentialProvider, EnvCredentials, FileCredentials, FnCredentials};
+    use crate::client::ClientBuilder;
+    use crate::transport::{BoxFuture, Request, Response, Transport};
+    use crate::Secret;
+    use reqwest::header::AUTHORIZATION;
+    use reqwest::StatusCode;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+
+    /// Accepts only the current key, recording the keys of the requests it received
+    #[derive(Debug, Default)]
+    struct KeyTransport {
+        key: Arc<Mutex<&'static str>>,
+        received: Arc<Mutex<Vec<String>>>,
+    }
+
+    impl Transport for KeyTransport {
+        fn send(&self, request: Request) -> BoxFuture<'_, crate::Result<Response>> {
+            let header = request.headers()[AUTHORIZATION].to_str().unwrap();
+            let key = header.trim_start_matches("Bearer ").to_owned();
+            let accepted = key == *self.key.lock().unwrap();
+            self.received.lock().unwrap().push(key);
+            let response = if accepted {
+                Response::new(StatusCode::OK, r#"{"data":[]}"#)
+            } else {
+                Response::new(StatusCode::UNAUTHORIZED, "")
+            };
+            Box::pin(async { Ok(response) })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_refresh_on_unauthorized() {
+        let transport = KeyTransport::default();
+        let key = Arc::clone(&transport.key);
+        let received = Arc::clone(&transport.received);
+        let calls = AtomicUsize::new(0);
+        let keys = ["ptlc_old", "ptlc_old", "ptlc_new"];
+        let client = ClientBuilder::new("https://panel.example.com", "")
+            .with_transport(transport)
+            .with_credentials(FnCredentials::new(move || {
+                let call = calls.fetch_add(1, Ordering::SeqCst);
+                Ok(Secret::from(keys[call.min(keys.len() - 1)]))
+            }))
+            .build();
+
+        *key.lock().unwrap() = "ptlc_old";
+        client.list_servers().await.unwrap();
+        *key.lock().unwrap() = "ptlc_new";
+        client.list_servers().await.unwrap();
+        assert_eq!(
+            *received.lock().unwrap(),
+            ["ptlc_old", "ptlc_old", "ptlc_new"]
+        );
+
+        *key.lock().unwrap() = "ptlc_newer";
+        let result = client.list_servers().await;
+        assert!(matches!(result, Err(crate::Error::Http(_))));
+        assert_eq!(received.lock().unwrap().len(), 4);
+    }
+
+    #[test]
+    fn test_file_credentials() {
+        let path = std::env::temp_dir().join(format!("ptero_key_{}", std::process::id()));
+        std::fs::write(&path, "ptla_first\n").unwrap();
+        let credentials = FileCredentials::new(&path);
+        assert_eq!(credentials.api_key().unwrap().expose(), "ptla_first");
+        // the file isn't checked again within the check interval, unless the key is refreshed
+        std::fs::write(&path, "ptla_second\n").unwrap();
+        assert_eq!(credentials.api_key().unwrap().expose(), "ptla_first");
+        credentials.refresh();
+        assert_eq!(credentials.api_key().unwrap().expose(), "ptla_second");
+
+        let credentials = credentials.with_check_interval(Duration::ZERO);
+
+        // rewritten files are reloaded without a refresh, even within the same modification time
+        std::fs::write(&path, "ptla_third_key\n").unwrap();
+        assert_eq!(credentials.api_key().unwrap().expose(), "ptla_third_key");
+        #[cfg(unix)]
+        {
+            let replacement = path.with_extension("new");
+            std::fs::write(&replacement, "ptla_fifth_key\n").unwrap();
+            std::fs::rename(&replacement, &path).unwrap();
+            assert_eq!(credentials.api_key().unwrap().expose(), "ptla_fifth_key");
+        }
+        std::fs::remove_file(&path).unwrap();
+        credentials.refresh();
+        assert!(matches!(
+            credentials.api_key(),
+            Err(crate::Error::Credentials(_))
+        ));
+    }
+
+    #[test]
+    fn test_env_credentials() {
+        let credentials = EnvCredentials::new("PTERODACTYL_API_TEST_UNSET_KEY");
+        assert!(matches!(
+            credentials.api_key(),
+            Err(crate::Error::Credentials(_))
+        ));
+    }
+}
@@ -0,0 +1,229 @@
+use crate::http::{EmptyBody, HttpCore};
+use crate::RateLimits;
+use reqwest::{Method, StatusCode};
+
+/// The type of an API key, detected from its prefix
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum KeyType {
+    /// A client API key, starting with `ptlc_`, for the client API
+    Client,
+    /// An application API key, starting with `ptla_`, for the application API
+    Application,
+    /// A key without a known prefix, such as a key created before the panel added prefixes
+    Unknown,
+}
+
+impl KeyType {
+    /// Detects the type of the given API key from its prefix
+    pub fn detect(api_key: &str) -> Self {
+        if api_key.starts_with("ptlc_") {
+            KeyType::Client
+        } else if api_key.starts_with("ptla_") {
+            KeyType::Application
+        } else {
+            KeyType::Unknown
+        }
+    }
+}
+
+/// The result of diagnosing the connection of a client to the panel, see
+/// [`crate::client::Client::diagnose`] and [`crate::application::Client::diagnose`]
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct Diagnosis {
+    /// Whether the panel answered a request
+    pub reachable: bool,
+    /// Whether the panel accepted the API key
+    pub authenticated: bool,
+    /// The type of the API key, detected from its prefix
+    pub key_type: KeyType,
+    /// The features of the panel the client API supports, or [`None`] if they couldn't be
+    /// detected, which is always the case for the application API
+    pub features: Option<PanelFeatures>,
+    /// The rate limits reported by the panel
+    pub rate_limits: Option<RateLimits>,
+    /// Problems which were found, such as a key used against the wrong API
+    pub warnings: Vec<String>,
+}
+
+impl Diagnosis {
+    /// Whether the panel is reachable and accepted the key, without any warnings
+    pub fn is_healthy(&self) -> bool {
+        self.reachable && self.authenticated && self.warnings.is_empty()
+    }
+}
+
+/// The features supported by a panel. Pterodactyl doesn't expose its version through the API, so
+/// the supported features imply the oldest version the panel can be
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub struct PanelFeatures {
+    /// Whether the panel keeps an activity log of the account, which was added in 1.11
+    pub activity_log: bool,
+}
+
+impl PanelFeatures {
+    /// Gets the oldest panel version with these features, or [`None`] if they don't narrow down
+    /// the version
+    pub fn minimum_version(&self) -> Option<&'static str> {
+        self.activity_log.then_some("1.11.0")
+    }
+}
+
+/// Diagnoses the connection of a client of the given API, by requesting the given endpoint which
+/// any key of that API can read
+pub(crate) async fn diagnose(http: &HttpCore, api: KeyType, probe: &str) -> Diagnosis {
+    let mut diagnosis = Diagnosis {
+        reachable: false,
+        authenticated: false,
+        key_type: KeyType::Unknown,
+        features: None,
+        rate_limits: None,
+        warnings: Vec::new(),
+    };
+    let (api_name, other_name) = match api {
+        KeyType::Client => ("client", "application"),
+        _ => ("application", "client"),
+    };
+    match http.api_key() {
+        Ok(api_key) => diagnosis.key_type = KeyType::detect(api_key.expose()),
+        Err(err) => {
+            diagnosis
+                .warnings
+                .push(format!("The API key couldn't be read: {err}"));
+            return diagnosis;
+        }
+    }
+    let wrong_key = !matches!(diagnosis.key_type, KeyType::Unknown) && diagnosis.key_type != api;
+    if wrong_key {
+        diagnosis.warnings.push(format!(
+            "The key is for the {other_name} API, but is used against the {api_name} API"
+        ));
+    }
+
+    match http.request::<serde_json::Value>(Method::GET, probe).await {
+        Ok(_) => {
+            diagnosis.reachable = true;
+            diagnosis.authenticated = true;
+        }
+        Err(crate::Error::Reqwest(err)) if err.is_connect() || err.is_timeout() => {
+            diagnosis
+                .warnings
+                .push(format!("The panel isn't reachable: {err}"));
+        }
+        Err(
+            crate::Error::Http(StatusCode::UNAUTHORIZED)
+            | crate::Error::Api {
+                status: StatusCode::UNAUTHORIZED,
+                ..
+            }
+            | crate::Error::PermissionError { .. },
+        ) => {
+            diagnosis.reachable = true;
+            if !wrong_key {
+                diagnosis.warnings.push(format!(
+                    "The panel rejected the key, which may be revoked, lack permissions, or not be \
+                     for the {api_name} API"
+                ));
+            }
+        }
+        Err(crate::Error::ResourceNotFound { .. }) => {
+            diagnosis.reachable = true;
+            diagnosis.warnings.push(format!(
+                "The {api_name} API wasn't found, so the URL may not point to the panel"
+            ));
+        }
+        Err(crate::Error::Json(_)) => {
+            diagnosis.reachable = true;
+            diagnosis.warnings.push(format!(
+                "The {api_name} API didn't respond with JSON, so the URL may not point to the panel"
+            ));
+        }
+        Err(err) => {
+            diagnosis
+                .warnings
+                .push(format!("The {api_name} API failed: {err}"));
+        }
+    }
+
+    if diagnosis.authenticated && api == KeyType::Client {
+        let activity = http
+            .request::<EmptyBody>(Method::GET, "account/activity?per_page=1")
+            .await;
+        diagnosis.features = match activity {
+            Ok(_) => Some(PanelFeatures { activity_log: true }),
+            Err(crate::Error::ResourceNotFound { .. }) => Some(PanelFeatures {
+                activity_log: false,
+            }),
+            Err(_) => None,
+        };
+    }
+    diagnosis.rate_limits = http.rate_limits();
+    diagnosis
+}
+
+#[cfg(all(test, feature = "mock-server"))]
+mod test {
+    use super::KeyType;
+    use crate::mock::MockPanel;
+    use crate::{application, client};
+
+    #[tokio::test]
+    async fn test_diagnose() {
+        let panel = MockPanel::start().await.unwrap();
+        let diagnosis = client::ClientBuilder::new(panel.url(), "ptlc_key")
+            .build()
+            .diagnose()
+            .await;
+        assert!(diagnosis.is_healthy(), "{diagnosis:?}");
+        assert_eq!(diagnosis.key_type, KeyType::Client);
+        let features = diagnosis.features.unwrap();
+        assert!(features.activity_log);
+        assert_eq!(features.minimum_version(), Some("1.11.0"));
+        let features = super::PanelFeatures {
+            activity_log: false,
+        };
+        assert_eq!(features.minimum_version(), None);
+
+        let diagnosis = application::ClientBuilder::new(panel.url(), "ptlc_key")
+            .build()
+            .diagnose()
+            .await;
+        assert!(diagnosis.authenticated);
+        assert_eq!(diagnosis.key_type, KeyType::Client);
+        assert_eq!(
+            diagnosis.warnings,
+            ["The key is for the client API, but is used against the application API"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_diagnose_failures() {
+        let panel = MockPanel::start().await.unwrap();
+        let diagnosis = client::ClientBuilder::new(format!("{}/panel", panel.url()), "ptlc_key")
+            .build()
+            .diagnose()
+            .await;
+        assert!(diagnosis.reachable);
+        assert!(!diagnosis.authenticated);
+        assert_eq!(diagnosis.warnings.len(), 1);
+        assert!(diagnosis.warnings[0].contains("URL"));
+
+        let diagnosis = application::ClientBuilder::new(panel.url(), " ")
+            .build()
+            .diagnose()
+            .await;
+        assert!(diagnosis.reachable);
+        assert!(!diagnosis.authenticated);
+        assert_eq!(diagnosis.key_type, KeyType::Unknown);
+
+        drop(panel);
+        let diagnosis = client::ClientBuilder::new("http://127.0.0.1:1", "ptlc_key")
+            .build()
+            .diagnose()
+            .await;
+        assert!(!diagnosis.reachable);
+        assert!(diagnosis.warnings[0].starts_with("The panel isn't reachable"));
+    }
+}
@@ -0,0 +1,174 @@
+use reqwest::Method;
+use std::sync::{Arc, Mutex, PoisonError};
+
+/// The plan log of a client in dry run mode. Every request of the client which would change
+/// something, which is every request except `GET` requests, is recorded in the plan instead of
+/// being sent, and answered with a synthetic success. `GET` requests are still sent to the panel.
+///
+/// Requests whose result is read from the response of the panel, such as
+/// [`crate::application::Client::create_server`], return [`crate::Error::DryRun`] instead, which
+/// carries the recorded request.
+///
+/// The plan is shared between its clones, so a clone can be given to a client builder:
+///
+/// ```
+/// # use pterodactyl_api::client::{ClientBuilder, PowerSignal};
+/// # use pterodactyl_api::DryRunPlan;
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() -> pterodactyl_api::Result<()> {
+/// let plan = DryRunPlan::new();
+/// let client = ClientBuilder::new("https://pterodactyl.example.com", "key")
+///     .with_dry_run(plan.clone())
+///     .build();
+/// let server = client.get_server("1a7ce997");
+/// server.send_power_signal(PowerSignal::Restart).await?;
+/// for request in plan.requests() {
+///     println!("{} {} {:?}", request.method, request.endpoint, request.body);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct DryRunPlan {
+    requests: Arc<Mutex<Vec<PlannedRequest>>>,
+}
+
+/// A request which was recorded in a [`DryRunPlan`] instead of being sent
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct PlannedRequest {
+    /// The method of the request
+    pub method: Method,
+    /// The endpoint of the request, relative to the API path of the panel
+    pub endpoint: String,
+    /// The serialized body of the request, or [`None`] if it has no body or the body is streamed
+    pub body: Option<String>,
+}
+
+impl DryRunPlan {
+    /// Creates an empty plan
+    pub fn new() -> Self {
+        DryRunPlan::default()
+    }
+
+    /// Gets the requests recorded so far, in the order they were made
+    pub fn requests(&self) -> Vec<PlannedRequest> {
+        self.lock().clone()
+    }
+
+    /// Removes the requests recorded so far from the plan, and returns them
+    pub fn take(&self) -> Vec<PlannedRequest> {
+        std::mem::take(&mut *self.lock())
+    }
+
+    /// Records the given request in the plan, if it would change something, and returns the
+    /// recorded request
+    pub(crate) fn record(
+        &self,
+        method: &Method,
+        endpoint: &str,
+        body: Option<&[u8]>,
+    ) -> Option<PlannedRequest> {
+        if method == Method::GET {
+            return None;
+        }
+        let request = PlannedRequest {
+            method: method.clone(),
+            endpoint: endpoint.to_owned(),
+            body: body.map(|body| String::from_utf8_lossy(body).into_owned()),
+        };
+        self.lock().push(request.clone());
+        Some(request)
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Vec<PlannedRequest>> {
+        self.requests.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+#[cfg(all(test, feature = "mock-server"))]
+mod test {
+    use super::{DryRunPlan, PlannedRequest};
+    use crate::application::structs::CreateLocationRequest;
+    use crate::client::{ClientBuilder, PowerSignal};
+    use crate::mock::MockPanel;
+    use reqwest::Method;
+
+    #[tokio::test]
+    async fn test_dry_run() {
+        let panel = MockPanel::start().await.unwrap();
+        let plan = DryRunPlan::new();
+        let client = ClientBuilder::new(panel.url(), "key")
+            .with_dry_run(plan.clone())
+            .build();
+        let id = client.list_servers().await.unwrap().remove(0).identifier;
+        let server = client.get_server(&id);
+        server.send_power_signal(PowerSignal::Kill).await.unwrap();
+        server
+            .delete_files(vec!["eula.txt".to_owned()])
+            .await
+            .unwrap();
+        let Err(crate::Error::DryRun(backup)) = server.create_backup_with_params("Nightly").await
+        else {
+            panic!("creating a backup in a dry run should return the planned request");
+        };
+        assert_eq!(backup.method, Method::POST);
+        assert_eq!(backup.body.as_deref(), Some(r#"{"name":"Nightly"}"#));
+
+        assert!(panel.power_signals(&id).is_empty());
+        assert!(panel.requests().iter().all(|r| r.starts_with("GET ")));
+        let requests = plan.take();
+        assert_eq!(
+            requests[0],
+            PlannedRequest {
+                method: Method::POST,
+                endpoint: format!("servers/{id}/power"),
+                body: Some(r#"{"signal":"kill"}"#.to_owned()),
+            }
+        );
+        assert_eq!(requests[1].endpoint, format!("servers/{id}/files/delete"));
+        assert_eq!(requests[2].endpoint, format!("servers/{id}/backups"));
+        assert_eq!(requests.len(), 3);
+        assert!(plan.requests().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_dry_run_application() {
+        let panel = MockPanel::start().await.unwrap();
+        let plan = DryRunPlan::new();
+        let client = crate::application::ClientBuilder::new(panel.url(), "key")
+            .with_dry_run(plan.clone())
+            .build();
+        let server = client.list_servers().await.unwrap().remove(0);
+        let mut limits = server.limits.clone();
+        limits.memory *= 2;
+        client.update_server_build(server.id, limits).await.unwrap();
+        client.suspend_server(server.id).await.unwrap();
+        let location = client
+            .create_location(CreateLocationRequest {
+                short: "eu.ams".to_owned(),
+                long: Some("Amsterdam".to_owned()),
+            })
+            .await;
+        let Err(crate::Error::DryRun(location)) = location else {
+            panic!("creating a location in a dry run should return the planned request");
+        };
+        assert_eq!(location.endpoint, "locations");
+
+        let current = client.get_server(server.id).await.unwrap();
+        assert_eq!(current.limits.memory, server.limits.memory);
+        assert!(!current.suspended);
+        assert_eq!(client.list_locations().await.unwrap().len(), 1);
+        let requests = plan.requests();
+        assert_eq!(requests.len(), 3);
+        assert_eq!(requests[0].method, Method::PATCH);
+        let body = requests[0].body.as_deref().unwrap_or_default();
+        let memory = format!(r#""memory":{}"#, server.limits.memory * 2);
+        assert!(body.contains(&memory));
+        assert_eq!(
+            requests[1].endpoint,
+            format!("servers/{}/suspend", server.id)
+        );
+        assert_eq!(requests[1].body, None);
+    }
+}
@@ -1,9 +1,59 @@
-use crate::client::{Client, RateLimits};
-use reqwest::{Body, Method, RequestBuilder, Response, StatusCode};
+use crate::application::structs::{ListQuery, QueryParam};
+use crate::credentials::CredentialProvider;
+use crate::middleware::{Middleware, ResponseInfo};
+use crate::structs::{ErrorResponse, Page, ValidationErrors};
+use crate::transport::{BoxFuture, Request, ReqwestTransport, Response, Transport};
+use crate::{DryRunPlan, PlannedRequest, RateLimiter, RetryPolicy, Secret};
+use reqwest::header::{HeaderValue, ACCEPT, AUTHORIZATION, CONTENT_TYPE};
+use reqwest::{Body, Method, StatusCode};
 use serde::de::DeserializeOwned;
 use serde::Serialize;
+use std::future::Future;
+use std::marker::PhantomData;
+use std::sync::RwLock;
+use std::time::Instant;
 
-impl Client {
+/// The rate limits of the API key
+#[derive(Debug, Copy, Clone)]
+pub struct RateLimits {
+    /// The request limit per minute
+    pub limit: u32,
+    /// The number of requests remaining in this minute
+    pub limit_remaining: u32,
+}
+
+/// The HTTP transport shared by the client and application APIs, which authenticates, sends,
+/// retries and traces requests, and translates failed responses into errors
+#[derive(Debug)]
+pub(crate) struct HttpCore {
+    url: String,
+    transport: Box<dyn Transport>,
+    credentials: Box<dyn CredentialProvider>,
+    rate_limits: RwLock<Option<RateLimits>>,
+    retry_policy: Option<RetryPolicy>,
+    rate_limiter: Option<RateLimiter>,
+    middleware: Vec<Box<dyn Middleware>>,
+    dry_run: Option<DryRunPlan>,
+}
+
+impl HttpCore {
+    /// Gets the transport which sends requests to the panel
+    #[cfg(feature = "websocket")]
+    pub(crate) fn transport(&self) -> &dyn Transport {
+        &*self.transport
+    }
+
+    /// Gets the API key the next request is authenticated with
+    pub(crate) fn api_key(&self) -> crate::Result<Secret> {
+        self.credentials.api_key()
+    }
+
+    /// Gets the rate limit information after the previous request
+    pub(crate) fn rate_limits(&self) -> Option<RateLimits> {
+        *self.rate_limits.read().unwrap()
+    }
+
+    /// Makes a request to the API
     pub(crate) async fn request<Response: ResponseBody>(
         &self,
         method: Method,
@@ -13,32 +63,33 @@ impl Client {
             .await
     }
 
+    /// Makes a request with a JSON body to the API
     pub(crate) async fn request_with_body<Response: ResponseBody, Body: RequestBody>(
         &self,
         method: Method,
         endpoint: &str,
         body: Body,
     ) -> crate::Result<Response> {
-        Response::decode(
-            self.get_response::<_, NullErrorHandler>(method, endpoint, body)
-                .await?,
-        )
-        .await
+        self.request_with_error_handler::<Response, _, NullErrorHandler>(method, endpoint, body)
+            .await
     }
 
+    /// Makes a request with a plain text body to the API
     pub(crate) async fn request_with_text_body<Response: ResponseBody, Body: RequestBody>(
         &self,
         method: Method,
         endpoint: &str,
         body: Body,
     ) -> crate::Result<Response> {
-        Response::decode(
-            self.get_response_with_text_body::<_, NullErrorHandler>(method, endpoint, body)
-                .await?,
+        decode(
+            self.send::<_, NullErrorHandler>(method, endpoint, body, "text/plain")
+                .await,
         )
         .await
     }
 
+    /// Makes a request with a JSON body to the API, translating failed responses with the given
+    /// error handler
     pub(crate) async fn request_with_error_handler<
         Response: ResponseBody,
         Body: RequestBody,
@@ -49,81 +100,108 @@ impl Client {
         endpoint: &str,
         body: Body,
     ) -> crate::Result<Response> {
-        Response::decode(
+        decode(
             self.get_response::<_, EHandler>(method, endpoint, body)
-                .await?,
+                .await,
         )
         .await
     }
 
+    /// Gets the response of a request with a JSON body to the API
     pub(crate) async fn get_response<Body: RequestBody, EHandler: ErrorHandler>(
         &self,
         method: Method,
         endpoint: &str,
         body: Body,
     ) -> crate::Result<Response> {
-        let request = self
-            .client
-            .request(method.clone(), format!("{}{}", self.url, endpoint))
-            .header("Accept", "application/json")
-            .header("Content-Type", "application/json")
-            .header("Authorization", format!("Bearer {}", self.api_key));
-        let request = body.encode(request)?;
-
-        let response = request.send().await?;
-
-        if !response.status().is_success() {
-            let status = response.status();
-            if let Ok(st) = response.text().await {
-                println!("{}", st);
-            }
-            return Err(Self::translate_error(status));
-        }
-
-        if let Some(limit) = response
-            .headers()
-            .get("x-ratelimit-limit")
-            .and_then(|header| header.to_str().ok())
-            .and_then(|header| header.parse().ok())
-        {
-            if let Some(limit_remaining) = response
-                .headers()
-                .get("x-ratelimit-remaining")
-                .and_then(|header| header.to_str().ok())
-                .and_then(|header| header.parse().ok())
-            {
-                *self.rate_limits.write().unwrap() = Some(RateLimits {
-                    limit,
-                    limit_remaining,
-                });
-            }
-        }
-
-        Ok(response)
+        self.send::<_, EHandler>(method, endpoint, body, "application/json")
+            .await
     }
 
-    pub(crate) async fn get_response_with_text_body<Body: RequestBody, EHandler: ErrorHandler>(
+    async fn send<Body: RequestBody, EHandler: ErrorHandler>(
         &self,
         method: Method,
         endpoint: &str,
         body: Body,
+        content_type: &'static str,
     ) -> crate::Result<Response> {
-        let request = self
-            .client
-            .request(method.clone(), format!("{}{}", self.url, endpoint))
-            .header("Accept", "application/json")
-            .header("Content-Type", "text/plain")
-            .header("Authorization", format!("Bearer {}", self.api_key));
-        let request = body.encode(request)?;
+        let mut request = Request::new(method.clone(), format!("{}{}", self.url, endpoint));
+        let headers = request.headers_mut();
+        headers.insert(ACCEPT, HeaderValue::from_static("application/json"));
+        headers.insert(CONTENT_TYPE, HeaderValue::from_static(content_type));
+        let api_key = self.credentials.api_key()?;
+        headers.insert(AUTHORIZATION, authorization(&api_key)?);
+        let mut request = body.encode(request)?;
+
+        let mut seen = 0;
+        let mut short_circuit = None;
+        for middleware in &self.middleware {
+            seen += 1;
+            short_circuit = middleware.on_request(&mut request);
+            if short_circuit.is_some() {
+                break;
+            }
+        }
+        let mut planned = None;
+        if let Some(plan) = &self.dry_run {
+            if short_circuit.is_none() {
+                planned = plan.record(&method, endpoint, request.body());
+                if planned.is_some() {
+                    short_circuit = Some(Response::new(StatusCode::NO_CONTENT, ""));
+                }
+            }
+        }
+        let url = request.url().to_owned();
+
+        let trace = RequestTrace::new(&method, endpoint);
+        let start = Instant::now();
+        let short_circuited = short_circuit.is_some();
+        let mut response = if let Some(response) = short_circuit {
+            response
+        } else {
+            let retry = request.try_clone();
+            let response = self.dispatch(&trace, request).await?;
+            match retry {
+                // the key may have been rotated since it was read, so the request is sent again
+                // if the provider has a new key
+                Some(mut retry) if response.status() == StatusCode::UNAUTHORIZED => {
+                    self.credentials.refresh();
+                    let new_key = self.credentials.api_key()?;
+                    if new_key == api_key {
+                        response
+                    } else {
+                        let headers = retry.headers_mut();
+                        headers.insert(AUTHORIZATION, authorization(&new_key)?);
+                        self.dispatch(&trace, retry).await?
+                    }
+                }
+                _ => response,
+            }
+        };
+        trace.record_response(&response);
 
-        let response = request.send().await?;
+        if seen > 0 {
+            let info = ResponseInfo {
+                method,
+                url,
+                endpoint: endpoint.to_owned(),
+                elapsed: start.elapsed(),
+                short_circuited,
+            };
+            for middleware in self.middleware[..seen].iter().rev() {
+                middleware.on_response(&info, &mut response);
+            }
+        }
+        if let Some(request) = planned {
+            return Err(crate::Error::DryRun(request));
+        }
 
         if !response.status().is_success() {
-            let status = response.status();
-            if let Ok(st) = response.text().await {
-                println!("{}", st);
+            let error = ErrorBody::read(response).await;
+            if let Some(err) = EHandler::get_error(error.status, &error.response) {
+                return Err(trace.error(err));
             }
-            return Err(Self::translate_error(status));
+            return Err(trace.error(error.into_error()));
         }
 
         if let Some(limit) = response
@@ -148,13 +226,60 @@ impl Client {
         Ok(response)
     }
 
-    fn translate_error(status: StatusCode) -> crate::Error {
-        match status {
-            StatusCode::FORBIDDEN => crate::Error::PermissionError,
-            StatusCode::NOT_FOUND => crate::Error::ResourceNotFound,
-            StatusCode::TOO_MANY_REQUESTS => crate::Error::RateLimit,
-            status => crate::Error::Http(status),
-        }
+    /// Fetches every page of a list of the application API matching the given query
+    pub(crate) async fn collect_query<T, F, S, I>(
+        &self,
+        path: &str,
+        query: &ListQuery<F, S, I>,
+    ) -> crate::Result<Vec<T>>
+    where
+        T: DeserializeOwned,
+        F: QueryParam + PartialEq,
+        S: QueryParam,
+        I: QueryParam + PartialEq,
+    {
+        collect_pages(|page| {
+            let endpoint = query.clone().with_page(page).endpoint(path);
+            async move { self.request::<Page<T>>(Method::GET, &endpoint).await }
+        })
+        .await
+    }
+
+    /// Lazily fetches every page of a list of the application API matching the given query, and
+    /// streams the items of each page
+    #[cfg(feature = "stream")]
+    pub(crate) fn stream_query<T, F, S, I>(
+        &self,
+        path: String,
+        query: ListQuery<F, S, I>,
+    ) -> impl futures_core::Stream<Item = crate::Result<T>> + '_
+    where
+        T: DeserializeOwned + 'static,
+        F: QueryParam + PartialEq + 'static,
+        S: QueryParam + 'static,
+        I: QueryParam + PartialEq + 'static,
+    {
+        stream_pages(move |page| {
+            let endpoint = query.clone().with_page(page).endpoint(&path);
+            async move { self.request::<Page<T>>(Method::GET, &endpoint).await }
+        })
+    }
+
+    /// Sends a request using the transport, waiting for the rate limiter before each attempt
+    async fn dispatch(&self, trace: &RequestTrace, request: Request) -> crate::Result<Response> {
+        let transport = RateLimited {
+            transport: &*self.transport,
+            limiter: self.rate_limiter.as_ref(),
+        };
+        trace
+            .instrument(async {
+                match &self.retry_policy {
+                    Some(policy) => policy.send(&transport, request).await,
+                    None => transport.send(request).await,
+                }
+            })
+            .await
+            .map_err(|err| trace.error(err))
     }
 
     #[cfg(test)]
@@ -173,19 +298,185 @@ impl Client {
     }
 }
 
+/// A transport which waits for the rate limiter before sending each request, including each retry
+/// of a request, and updates the rate limiter from each response
+#[derive(Debug)]
+struct RateLimited<'a> {
+    transport: &'a dyn Transport,
+    limiter: Option<&'a RateLimiter>,
+}
+
+impl Transport for RateLimited<'_> {
+    fn send(&self, request: Request) -> BoxFuture<'_, crate::Result<Response>> {
+        Box::pin(async move {
+            if let Some(limiter) = self.limiter {
+                limiter.acquire().await;
+            }
+            let response = self.transport.send(request).await?;
+            if let Some(limiter) = self.limiter {
+                limiter.observe(&response);
+            }
+            Ok(response)
+        })
+    }
+}
+
+/// Creates the authorization header for the given API key
+fn authorization(api_key: &Secret) -> crate::Result<HeaderValue> {
+    let mut header = HeaderValue::try_from(format!("Bearer {}", api_key.expose()))?;
+    header.set_sensitive(true);
+    Ok(header)
+}
+
+/// One of the APIs of the panel, which a [`ClientBuilder`] can build a client for
+pub(crate) trait Api {
+    /// The path of the API, relative to the panel URL
+    const PATH: &'static str;
+
+    /// Creates a client of this API which sends requests using the given transport
+    fn from_http(http: HttpCore) -> Self;
+}
+
+/// A builder for a client of the client API ([`crate::client::ClientBuilder`]) or the
+/// application API ([`crate::application::ClientBuilder`])
+#[derive(Debug)]
+pub struct ClientBuilder<C> {
+    url: String,
+    transport: Option<Box<dyn Transport>>,
+    credentials: Box<dyn CredentialProvider>,
+    retry_policy: Option<RetryPolicy>,
+    rate_limiter: Option<RateLimiter>,
+    middleware: Vec<Box<dyn Middleware>>,
+    dry_run: Option<DryRunPlan>,
+    api: PhantomData<fn() -> C>,
+}
+
+#[allow(private_bounds)]
+impl<C: Api> ClientBuilder<C> {
+    /// Creates a new client builder, connecting to the given URL where a Pterodactyl server is
+    /// hosted, using the given API key for authentication
+    pub fn new(url: impl Into<String>, api_key: impl Into<Secret>) -> Self {
+        let mut url = url.into();
+        if !url.ends_with('/') {
+            url.push('/');
+        }
+        url.push_str(C::PATH);
+        Self {
+            url,
+            transport: None,
+            credentials: Box::new(api_key.into()),
+            retry_policy: None,
+            rate_limiter: None,
+            middleware: Vec::new(),
+            dry_run: None,
+            api: PhantomData,
+        }
+    }
+
+    /// Uses the specified [`reqwest::Client`] for requests instead of making a default one
+    pub fn with_client(self, client: reqwest::Client) -> Self {
+        self.with_transport(ReqwestTransport::new(client))
+    }
+
+    /// Sends requests using the specified [`Transport`] instead of [`reqwest`], for example to
+    /// return canned responses in tests
+    pub fn with_transport(self, transport: impl Transport + 'static) -> Self {
+        Self {
+            transport: Some(Box::new(transport)),
+            ..self
+        }
+    }
+
+    /// Reads the API key from the given provider before every request, instead of using the fixed
+    /// key the builder was created with. When the panel rejects the key, the provider is refreshed
+    /// and the request is sent again if the key changed
+    pub fn with_credentials(self, credentials: impl CredentialProvider + 'static) -> Self {
+        Self {
+            credentials: Box::new(credentials),
+            ..self
+        }
+    }
+
+    /// Retries requests which failed with a transient error according to the given policy. By
+    /// default, requests are not retried
+    pub fn with_retry_policy(self, retry_policy: RetryPolicy) -> Self {
+        Self {
+            retry_policy: Some(retry_policy),
+            ..self
+        }
+    }
+
+    /// Delays requests using the given rate limiter before the panel starts rejecting them. By
+    /// default, requests are not delayed
+    pub fn with_rate_limiter(self, rate_limiter: RateLimiter) -> Self {
+        Self {
+            rate_limiter: Some(rate_limiter),
+            ..self
+        }
+    }
+
+    /// Adds a middleware which sees every request before it is sent and every response after it
+    /// is received. Middlewares run in the order they were added, see [`Middleware`]
+    pub fn with_middleware(mut self, middleware: impl Middleware + 'static) -> Self {
+        self.middleware.push(Box::new(middleware));
+        self
+    }
+
+    /// Records every request except `GET` requests in the given plan instead of sending it, see
+    /// [`DryRunPlan`]. Middlewares still see the recorded requests, and their synthetic responses
+    pub fn with_dry_run(self, plan: DryRunPlan) -> Self {
+        Self {
+            dry_run: Some(plan),
+            ..self
+        }
+    }
+
+    /// Builds a client
+    pub fn build(self) -> C {
+        C::from_http(HttpCore {
+            url: self.url,
+            transport: self
+                .transport
+                .unwrap_or_else(|| Box::new(ReqwestTransport::default())),
+            credentials: self.credentials,
+            rate_limits: RwLock::new(None),
+            retry_policy: self.retry_policy,
+            rate_limiter: self.rate_limiter,
+            middleware: self.middleware,
+            dry_run: self.dry_run,
+        })
+    }
+}
+
 pub(crate) trait RequestBody {
-    fn encode(self, request: RequestBuilder) -> crate::Result<RequestBuilder>;
+    fn encode(self, request: Request) -> crate::Result<Request>;
 }
 
 pub(crate) trait ResponseBody {
     async fn decode(response: Response) -> crate::Result<Self>
     where
         Self: Sized;
+
+    /// Gets the result of a request which was recorded in a dry run plan instead of being sent
+    fn planned(request: PlannedRequest) -> crate::Result<Self>
+    where
+        Self: Sized,
+    {
+        Err(crate::Error::DryRun(request))
+    }
+}
+
+/// Decodes the response of a request, answering the requests recorded in a dry run plan
+async fn decode<T: ResponseBody>(response: crate::Result<Response>) -> crate::Result<T> {
+    match response {
+        Err(crate::Error::DryRun(request)) => T::planned(request),
+        response => T::decode(response?).await,
+    }
 }
 
 pub(crate) struct EmptyBody;
 impl RequestBody for EmptyBody {
-    fn encode(self, request: RequestBuilder) -> crate::Result<RequestBuilder> {
+    fn encode(self, request: Request) -> crate::Result<Request> {
         Ok(request)
     }
 }
@@ -193,11 +484,15 @@ impl ResponseBody for EmptyBody {
     async fn decode(_: Response) -> crate::Result<Self> {
         Ok(Self)
     }
+
+    fn planned(_: PlannedRequest) -> crate::Result<Self> {
+        Ok(Self)
+    }
 }
 
 impl<T: Serialize> RequestBody for &T {
-    fn encode(self, request: RequestBuilder) -> crate::Result<RequestBuilder> {
-        Ok(request.body(serde_json::to_string(self)?))
+    fn encode(self, request: Request) -> crate::Result<Request> {
+        Ok(request.with_body(serde_json::to_string(self)?))
     }
 }
 
@@ -213,19 +508,480 @@ impl<T> RequestBody for RawBody<T>
 where
     T: Into<Body>,
 {
-    fn encode(self, request: RequestBuilder) -> crate::Result<RequestBuilder> {
-        Ok(request.body(self.0))
+    fn encode(self, request: Request) -> crate::Result<Request> {
+        Ok(request.with_body(self.0))
     }
 }
 
 pub(crate) trait ErrorHandler {
-    async fn get_error(response: Response) -> Option<crate::Error>;
+    fn get_error(status: StatusCode, response: &ErrorResponse) -> Option<crate::Error>;
 }
 
 pub(crate) struct NullErrorHandler;
 impl ErrorHandler for NullErrorHandler {
-    async fn get_error(_response: Response) -> Option<crate::Error> {
-        println!("response: {:?}", _response.text().await.unwrap());
+    fn get_error(_status: StatusCode, _response: &ErrorResponse) -> Option<crate::Error> {
         None
     }
 }
+
+/// The body of an unsuccessful response
+pub(crate) struct ErrorBody {
+    pub(crate) status: StatusCode,
+    pub(crate) request_id: Option<String>,
+    pub(crate) response: ErrorResponse,
+}
+
+impl ErrorBody {
+    /// Reads the error body from the given response. Bodies which are not in the Pterodactyl error
+    /// format are treated as having no errors
+    pub(crate) async fn read(response: Response) -> Self {
+        let status = response.status();
+        let request_id = response
+            .headers()
+            .get("x-request-id")
+            .and_then(|header| header.to_str().ok())
+            .map(str::to_owned);
+        let response = match response.bytes().await {
+            Ok(bytes) => serde_json::from_slice(&bytes).unwrap_or_default(),
+            Err(_) => ErrorResponse::default(),
+        };
+        ErrorBody {
+            status,
+            request_id,
+            response,
+        }
+    }
+
+    /// Translates this error body to an error
+    pub(crate) fn into_error(self) -> crate::Error {
+        let errors = self.response.errors;
+        let request_id = self.request_id;
+        let status = match self.status {
+            StatusCode::FORBIDDEN => return crate::Error::PermissionError { errors, request_id },
+            StatusCode::NOT_FOUND => return crate::Error::ResourceNotFound { errors, request_id },
+            StatusCode::TOO_MANY_REQUESTS => return crate::Error::RateLimit { errors, request_id },
+            status if errors.is_empty() => return crate::Error::Http(status),
+            status => status,
+        };
+        if status == StatusCode::UNPROCESSABLE_ENTITY {
+            if let Some(errors) = ValidationErrors::from_errors(&errors) {
+                return crate::Error::Validation(errors);
+            }
+        }
+        crate::Error::Api {
+            status,
+            errors,
+            request_id,
+        }
+    }
+}
+
+/// Builds the query string of an endpoint
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
+pub(crate) struct QueryString(Vec<(String, String)>);
+
+impl QueryString {
+    /// Adds a parameter to the query
+    pub(crate) fn push(&mut self, key: impl Into<String>, value: impl ToString) {
+        self.0.push((key.into(), value.to_string()));
+    }
+
+    /// Appends the query to the given path
+    pub(crate) fn endpoint(&self, path: &str) -> String {
+        if self.0.is_empty() {
+            return path.to_owned();
+        }
+        let query = self
+            .0
+            .iter()
+            .map(|(key, value)| {
+                format!(
+                    "{}={}",
+                    urlencoding::encode(key),
+                    urlencoding::encode(value)
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("&");
+        format!("{path}?{query}")
+    }
+}
+
+/// Appends the page number to the query of an endpoint
+pub(crate) fn paged_endpoint(endpoint: &str, page: u32) -> String {
+    let separator = if endpoint.contains('?') { '&' } else { '?' };
+    format!("{endpoint}{separator}page={page}")
+}
+
+/// Fetches every page of a list using the given function, which fetches a single page
+pub(crate) async fn collect_pages<T, F, Fut>(fetch: F) -> crate::Result<Vec<T>>
+where
+    F: Fn(u32) -> Fut,
+    Fut: Future<Output = crate::Result<Page<T>>>,
+{
+    let mut items = Vec::new();
+    let mut page = Some(1);
+    while let Some(number) = page {
+        let result = fetch(number).await?;
+        page = result.next_page();
+        items.extend(result.data);
+    }
+    Ok(items)
+}
+
+/// Lazily fetches every page of a list using the given function, which fetches a single page, and
+/// streams the items of each page
+#[cfg(feature = "stream")]
+pub(crate) fn stream_pages<T, F, Fut>(
+    fetch: F,
+) -> impl futures_core::Stream<Item = crate::Result<T>>
+where
+    F: Fn(u32) -> Fut,
+    Fut: Future<Output = crate::Result<Page<T>>>,
+{
+    use futures_util::{stream, TryStreamExt};
+    stream::try_unfold((fetch, Some(1)), |(fetch, page)| async move {
+        let Some(number) = page else {
+            return crate::Result::Ok(None);
+        };
+        let result = fetch(number).await?;
+        let page = result.next_page();
+        Ok(Some((
+            stream::iter(result.data.into_iter().map(Ok)),
+            (fetch, page),
+        )))
+    })
+    .try_flatten()
+}
+
+/// Traces a single request in a span when the `tracing` feature is enabled, and does nothing
+/// otherwise
+pub(crate) struct RequestTrace {
+    #[cfg(feature = "tracing")]
+    span: tracing::Span,
+    #[cfg(feature = "tracing")]
+    start: std::time::Instant,
+}
+
+#[cfg_attr(not(feature = "tracing"), allow(clippy::unused_self))]
+impl RequestTrace {
+    #[cfg_attr(not(feature = "tracing"), allow(unused_variables))]
+    pub(crate) fn new(method: &Method, endpoint: &str) -> Self {
+        #[cfg(feature = "tracing")]
+        {
+            let (template, server) = endpoint_template(endpoint);
+            RequestTrace {
+                span: tracing::info_span!(
+                    "pterodactyl_request",
+                    method = %method,
+                    endpoint = %template,
+                    server = server,
+                    status = tracing::field::Empty,
+                    latency_ms = tracing::field::Empty,
+                    ratelimit_limit = tracing::field::Empty,
+                    ratelimit_remaining = tracing::field::Empty,
+                ),
+                start: std::time::Instant::now(),
+            }
+        }
+        #[cfg(not(feature = "tracing"))]
+        RequestTrace {}
+    }
+
+    /// Runs the given future inside the span of this request
+    pub(crate) fn instrument<F: Future>(&self, future: F) -> impl Future<Output = F::Output> {
+        #[cfg(feature = "tracing")]
+        return tracing::Instrument::instrument(future, self.span.clone());
+        #[cfg(not(feature = "tracing"))]
+        future
+    }
+
+    /// Records the status, latency and rate limits of a response
+    #[cfg_attr(not(feature = "tracing"), allow(unused_variables))]
+    pub(crate) fn record_response(&self, response: &Response) {
+        #[cfg(feature = "tracing")]
+        {
+            let header = |name: &str| {
+                response
+                    .headers()
+                    .get(name)
+                    .and_then(|header| header.to_str().ok())
+                    .and_then(|header| header.parse::<u32>().ok())
+            };
+            self.span.record("status", response.status().as_u16());
+            self.span
+                .record("latency_ms", self.start.elapsed().as_millis() as u64);
+            if let Some(limit) = header("x-ratelimit-limit") {
+                self.span.record("ratelimit_limit", limit);
+            }
+            if let Some(remaining) = header("x-ratelimit-remaining") {
+                self.span.record("ratelimit_remaining", remaining);
+            }
+        }
+    }
+
+    /// Records that the request failed with the given error, and returns it
+    pub(crate) fn error(&self, error: crate::Error) -> crate::Error {
+        #[cfg(feature = "tracing")]
+        self.span
+            .in_scope(|| tracing::warn!(error = %error, "request failed"));
+        error
+    }
+}
+
+/// Replaces the IDs in an endpoint with placeholders, so that requests to the same endpoint can be
+/// grouped together. Also returns the server ID, if the endpoint refers to a server
+#[cfg(feature = "tracing")]
+fn endpoint_template(endpoint: &str) -> (String, Option<&str>) {
+    let path = endpoint.split_once('?').map_or(endpoint, |(path, _)| path);
+    let mut server = None;
+    let mut previous = "";
+    let template = path
+        .split('/')
+        .map(|segment| {
+            let placeholder = if previous == "servers" && segment != "external" {
+                server = Some(segment);
+                "{server}"
+            } else if previous == "external" {
+                "{external_id}"
+            } else if !segment.is_empty() && segment.bytes().all(|b| b.is_ascii_digit()) {
+                "{id}"
+            } else if uuid::Uuid::parse_str(segment).is_ok() {
+                "{uuid}"
+            } else if matches!(previous, "api-keys" | "databases") {
+                "{id}"
+            } else {
+                segment
+            };
+            previous = segment;
+            placeholder
+        })
+        .collect::<Vec<_>>()
+        .join("/");
+    (template, server)
+}
+
+#[cfg(test)]
+mod test {
+    use super::ErrorBody;
+    use crate::transport::{BoxFuture, Request, Response, Transport};
+    use crate::{RateLimiter, RetryPolicy};
+    use reqwest::StatusCode;
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    fn error_body(status: StatusCode, body: &str) -> ErrorBody {
+        ErrorBody {
+            status,
+            request_id: Some("abc".to_owned()),
+            response: serde_json::from_str(body).unwrap_or_default(),
+        }
+    }
+
+    #[test]
+    fn test_api_error() {
+        let error = error_body(
+            StatusCode::CONFLICT,
+            r#"{"errors":[{"code":"DisplayException","status":"409","detail":"server is currently installing"}]}"#,
+        )
+        .into_error();
+        assert_eq!(
+            error.to_string(),
+            "API Error (409 Conflict): DisplayException: server is currently installing"
+        );
+        match error {
+            crate::Error::Api {
+                status,
+                errors,
+                request_id,
+            } => {
+                assert_eq!(status, StatusCode::CONFLICT);
+                assert_eq!(errors.len(), 1);
+                assert_eq!(errors[0].status.as_deref(), Some("409"));
+                assert_eq!(request_id.as_deref(), Some("abc"));
+            }
+            error => panic!("Unexpected error {error:?}"),
+        }
+    }
+
+    #[test]
+    fn test_status_errors() {
+        let body = |code: &str| format!(r#"{{"errors":[{{"code":"{code}"}}]}}"#);
+        let error = error_body(StatusCode::FORBIDDEN, &body("AccessDeniedHttpException"));
+        let crate::Error::PermissionError { errors, request_id } = error.into_error() else {
+            panic!("403 responses should be permission errors");
+        };
+        assert_eq!(errors[0].code, "AccessDeniedHttpException");
+        assert_eq!(request_id.as_deref(), Some("abc"));
+
+        let error = error_body(StatusCode::NOT_FOUND, &body("NotFoundHttpException"));
+        let crate::Error::ResourceNotFound { errors, request_id } = error.into_error() else {
+            panic!("404 responses should be not found errors");
+        };
+        assert_eq!(errors[0].code, "NotFoundHttpException");
+        assert_eq!(request_id.as_deref(), Some("abc"));
+
+        let error = error_body(
+            StatusCode::TOO_MANY_REQUESTS,
+            &body("TooManyRequestsHttpException"),
+        );
+        let crate::Error::RateLimit { errors, request_id } = error.into_error() else {
+            panic!("429 responses should be rate limit errors");
+        };
+        assert_eq!(errors[0].code, "TooManyRequestsHttpException");
+        assert_eq!(request_id.as_deref(), Some("abc"));
+    }
+
+    #[test]
+    fn test_non_json_error() {
+        assert!(matches!(
+            error_body(StatusCode::BAD_GATEWAY, "<html></html>").into_error(),
+            crate::Error::Http(StatusCode::BAD_GATEWAY)
+        ));
+        assert!(matches!(
+            error_body(
+                StatusCode::NOT_FOUND,
+                r#"{"errors":[{"code":"NotFoundHttpException"}]}"#
+            )
+            .into_error(),
+            crate::Error::ResourceNotFound { .. }
+        ));
+    }
+
+    #[test]
+    fn test_validation_errors() {
+        let error = error_body(
+            StatusCode::UNPROCESSABLE_ENTITY,
+            r#"{"errors":[
+                {"code":"ValidationException","status":"422","detail":"The limits.memory field is required.","meta":{"source_field":"limits.memory","rule":"required"}},
+                {"code":"ValidationException","status":"422","detail":"The Server Jar File variable field is required.","meta":{"source_field":"environment.SERVER_JARFILE","rule":"required"}},
+                {"code":"ValidationException","status":"422","detail":"The limits.memory must be at least 0.","meta":{"source_field":"limits.memory","rule":"min"}}
+            ]}"#,
+        )
+        .into_error();
+        let crate::Error::Validation(errors) = error else {
+            panic!("Unexpected error {error:?}");
+        };
+        assert_eq!(
+            errors.fields().collect::<Vec<_>>(),
+            ["environment.SERVER_JARFILE", "limits.memory"]
+        );
+        let memory = errors.field("limits.memory");
+        assert_eq!(memory.len(), 2);
+        assert_eq!(memory[0].rule.as_deref(), Some("required"));
+        assert_eq!(memory[1].rule.as_deref(), Some("min"));
+        assert!(errors.field("limits.disk").is_empty());
+        assert!(errors.general().is_empty());
+    }
+
+    #[test]
+    fn test_mixed_validation_errors() {
+        let error = error_body(
+            StatusCode::UNPROCESSABLE_ENTITY,
+            r#"{"errors":[
+                {"code":"ValidationException","status":"422","detail":"The name field is required.","meta":{"source_field":"name","rule":"required"}},
+                {"code":"DisplayException","status":"422","detail":"No allocation could be found."}
+            ]}"#,
+        )
+        .into_error();
+        let crate::Error::Validation(errors) = error else {
+            panic!("Unexpected error {error:?}");
+        };
+        assert_eq!(errors.fields().collect::<Vec<_>>(), ["name"]);
+        assert_eq!(errors.general().len(), 1);
+        assert_eq!(errors.general()[0].detail, "No allocation could be found.");
+        assert_eq!(errors.general()[0].rule, None);
+        assert_eq!(
+            errors.to_string(),
+            "name: The name field is required., No allocation could be found."
+        );
+    }
+
+    #[cfg(feature = "tracing")]
+    #[test]
+    fn test_endpoint_template() {
+        use super::endpoint_template;
+        assert_eq!(
+            endpoint_template("servers/1a7ce997/files/list?directory=%2F"),
+            ("servers/{server}/files/list".to_owned(), Some("1a7ce997"))
+        );
+        assert_eq!(
+            endpoint_template("servers/1a7ce997/databases/bEY4yAD5/rotate-password"),
+            (
+                "servers/{server}/databases/{id}/rotate-password".to_owned(),
+                Some("1a7ce997")
+            )
+        );
+        assert_eq!(
+            endpoint_template("nodes/3/allocations"),
+            ("nodes/{id}/allocations".to_owned(), None)
+        );
+        assert_eq!(
+            endpoint_template("servers/1a7ce997/backups/904df120-a66f-4375-a0b2-bd82f6a5b4cf"),
+            (
+                "servers/{server}/backups/{uuid}".to_owned(),
+                Some("1a7ce997")
+            )
+        );
+    }
+
+    fn page(current_page: u32, total_pages: u32) -> crate::Page<u32> {
+        serde_json::from_value(serde_json::json!({
+            "object": "list",
+            "data": [{"object": "number", "attributes": current_page}],
+            "meta": {"pagination": {
+                "total": total_pages, "count": 1, "per_page": 1,
+                "current_page": current_page, "total_pages": total_pages, "links": {}
+            }}
+        }))
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_collect_pages() {
+        assert_eq!(super::paged_endpoint("servers", 2), "servers?page=2");
+        assert_eq!(
+            super::paged_endpoint("nests/1/eggs?include=nest", 2),
+            "nests/1/eggs?include=nest&page=2"
+        );
+        assert_eq!(page(1, 3).next_page(), Some(2));
+        assert_eq!(page(3, 3).next_page(), None);
+        let items = super::collect_pages(|number| async move { Ok(page(number, 3)) })
+            .await
+            .unwrap();
+        assert_eq!(items, [1, 2, 3]);
+    }
+
+    /// Answers each request with `503 Service Unavailable`, counting the requests
+    #[derive(Debug)]
+    struct UnavailableTransport(Arc<AtomicU32>);
+
+    impl Transport for UnavailableTransport {
+        fn send(&self, _request: Request) -> BoxFuture<'_, crate::Result<Response>> {
+            self.0.fetch_add(1, Ordering::Relaxed);
+            Box::pin(async { Ok(Response::new(StatusCode::SERVICE_UNAVAILABLE, "")) })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit_retries() {
+        let requests = Arc::new(AtomicU32::new(0));
+        let limiter = RateLimiter::with_limit(10);
+        let client = crate::client::ClientBuilder::new("https://panel.example.com", "key")
+            .with_transport(UnavailableTransport(Arc::clone(&requests)))
+            .with_retry_policy(
+                RetryPolicy::new()
+                    .with_base_delay(Duration::ZERO)
+                    .with_max_attempts(3),
+            )
+            .with_rate_limiter(limiter.clone())
+            .build();
+        assert!(matches!(
+            client.list_servers().await,
+            Err(crate::Error::Http(StatusCode::SERVICE_UNAVAILABLE))
+        ));
+        assert_eq!(requests.load(Ordering::Relaxed), 3);
+        assert_eq!(limiter.remaining(), 7);
+    }
+}
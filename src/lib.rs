@@ -84,11 +84,34 @@
 #![deny(missing_docs)]
 
 pub mod application;
+#[cfg(feature = "blocking")]
+pub mod blocking;
 pub mod client;
+pub mod credentials;
+mod diagnostics;
+mod dry_run;
 mod http;
+pub mod middleware;
+#[cfg(feature = "mock-server")]
+pub mod mock;
+mod rate_limit;
+mod retry;
+mod secret;
 mod structs;
-
-pub use structs::PteroObject;
+#[cfg(feature = "testing")]
+pub mod testing;
+pub mod transport;
+
+pub use diagnostics::{Diagnosis, KeyType, PanelFeatures};
+pub use dry_run::{DryRunPlan, PlannedRequest};
+pub use http::{ClientBuilder, RateLimits};
+pub use rate_limit::RateLimiter;
+pub use retry::RetryPolicy;
+pub use secret::Secret;
+pub use structs::{
+    ApiError, ApiErrorMeta, ApiErrorSource, FieldError, Page, Pagination, PteroObject,
+    ValidationErrors,
+};
 
 use reqwest::StatusCode;
 
@@ -107,10 +130,43 @@ pub enum Error {
     #[error("Json Error: {0}")]
     Json(#[from] serde_json::Error),
 
+    /// A header of the request, such as the API key, contains invalid characters
+    #[error("Invalid Header: {0}")]
+    InvalidHeader(#[from] reqwest::header::InvalidHeaderValue),
+
+    /// Errors produced by a [`credentials::CredentialProvider`] while reading the API key
+    #[error("Credential Error: {0}")]
+    Credentials(Box<dyn std::error::Error + Send + Sync>),
+
+    /// Errors produced by a custom [`transport::Transport`]
+    #[error("Transport Error: {0}")]
+    Transport(Box<dyn std::error::Error + Send + Sync>),
+
     /// Miscellaneous HTTP status codes
     #[error("Http Status Code: {0}")]
     Http(StatusCode),
 
+    /// An error reported by the panel in the body of a failed request
+    #[error("API Error ({status}): {}", display_api_errors(.errors))]
+    Api {
+        /// The HTTP status code of the response
+        status: StatusCode,
+        /// The errors reported by the panel
+        errors: Vec<ApiError>,
+        /// The ID of the request, if the panel returned one
+        request_id: Option<String>,
+    },
+
+    /// The request failed validation. Contains the errors for each invalid field
+    #[error("Validation Error: {0}")]
+    Validation(ValidationErrors),
+
+    /// IO errors while loading or saving a cassette, starting a mock panel, or creating the
+    /// runtime of a blocking client
+    #[cfg(any(feature = "blocking", feature = "testing", feature = "mock-server"))]
+    #[error("IO Error: {0}")]
+    Io(#[from] std::io::Error),
+
     /// Websocket errors
     #[cfg(feature = "websocket")]
     #[error("WebSocket Error: {0}")]
@@ -128,11 +184,21 @@ pub enum Error {
 
     /// Unable to perform operation due to lack of permissions
     #[error("Permission Error")]
-    PermissionError,
+    PermissionError {
+        /// The errors reported by the panel
+        errors: Vec<ApiError>,
+        /// The ID of the request, if the panel returned one
+        request_id: Option<String>,
+    },
 
     /// Rate limit reached
     #[error("Rate Limit")]
-    RateLimit,
+    RateLimit {
+        /// The errors reported by the panel
+        errors: Vec<ApiError>,
+        /// The ID of the request, if the panel returned one
+        request_id: Option<String>,
+    },
 
     /// 2fa token invalid
     #[error("Invalid 2fa Token")]
@@ -148,9 +214,27 @@ pub enum Error {
 
     /// The requested resource was not found
     #[error("Resource Not Found")]
-    ResourceNotFound,
+    ResourceNotFound {
+        /// The errors reported by the panel
+        errors: Vec<ApiError>,
+        /// The ID of the request, if the panel returned one
+        request_id: Option<String>,
+    },
 
     /// Unable to delete the primary network allocation
     #[error("Primary Allocation")]
     PrimaryAllocation,
+
+    /// The request was recorded in a [`DryRunPlan`] instead of being sent, so the panel didn't
+    /// return the result of the request
+    #[error("Dry Run: {} {}", .0.method, .0.endpoint)]
+    DryRun(PlannedRequest),
+}
+
+fn display_api_errors(errors: &[ApiError]) -> String {
+    errors
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(", ")
 }
@@ -0,0 +1,177 @@
+//! Hooks which see every request before it is sent and every response after it is received, for
+//! example to add headers, record metrics or answer requests without sending them. Middlewares
+//! are added to a client builder, and run in the order they were added:
+//!
+//! ```
+//! use pterodactyl_api::client::ClientBuilder;
+//! use pterodactyl_api::middleware::{Middleware, ResponseInfo};
+//! use pterodactyl_api::transport::{Request, Response};
+//! use reqwest::header::HeaderValue;
+//!
+//! #[derive(Debug)]
+//! struct TraceId(&'static str);
+//!
+//! impl Middleware for TraceId {
+//!     fn on_request(&self, request: &mut Request) -> Option<Response> {
+//!         let trace_id = HeaderValue::from_static(self.0);
+//!         request.headers_mut().insert("x-trace-id", trace_id);
+//!         None
+//!     }
+//! }
+//!
+//! #[derive(Debug)]
+//! struct Metrics;
+//!
+//! impl Middleware for Metrics {
+//!     fn on_response(&self, info: &ResponseInfo, response: &mut Response) {
+//!         println!(
+//!             "{} {} -> {} in {:?}",
+//!             info.method,
+//!             info.endpoint,
+//!             response.status(),
+//!             info.elapsed
+//!         );
+//!     }
+//! }
+//!
+//! let client = ClientBuilder::new("https://panel.example.com", "key")
+//!     .with_middleware(TraceId("4bf92f3577b34da6"))
+//!     .with_middleware(Metrics)
+//!     .build();
+//! ```
+
+use crate::transport::{Request, Response};
+use reqwest::Method;
+use std::fmt;
+use std::time::Duration;
+
+/// A hook around the requests a client sends to the panel.
+///
+/// Like layers of an onion, the first middleware added to a client sees each request first and
+/// its response last. When a middleware answers a request itself, the middlewares after it and
+/// the transport never see the request, and only the middlewares which saw the request see the
+/// response.
+pub trait Middleware: Send + Sync + fmt::Debug {
+    /// Called before a request is sent, after the authentication and content headers were set.
+    /// Returning a response answers the request with it instead of sending the request to the
+    /// panel. Does nothing by default
+    fn on_request(&self, _request: &mut Request) -> Option<Response> {
+        None
+    }
+
+    /// Called after a response was received, including unsuccessful responses, before its status
+    /// is translated into an error. Does nothing by default
+    fn on_response(&self, _info: &ResponseInfo, _response: &mut Response) {}
+}
+
+/// Information about the request a response answers
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct ResponseInfo {
+    /// The method of the request
+    pub method: Method,
+    /// The full URL of the request
+    pub url: String,
+    /// The endpoint of the request, relative to the API path of the panel
+    pub endpoint: String,
+    /// The time between sending the request and receiving the response, including retries and
+    /// delays by the rate limiter
+    pub elapsed: Duration,
+    /// Whether the response was returned by a middleware, rather than by the transport
+    pub short_circuited: bool,
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Middleware, ResponseInfo};
+    use crate::client::ClientBuilder;
+    use crate::transport::{BoxFuture, Request, Response, Transport};
+    use reqwest::header::{HeaderName, HeaderValue};
+    use reqwest::StatusCode;
+    use std::sync::{Arc, Mutex};
+
+    /// Responds to every request with an empty list, recording the headers it received
+    #[derive(Debug, Default)]
+    struct ListTransport(Arc<Mutex<Vec<String>>>);
+
+    impl Transport for ListTransport {
+        fn send(&self, request: Request) -> BoxFuture<'_, crate::Result<Response>> {
+            let headers = request.headers().keys().map(ToString::to_string);
+            let headers = headers.filter(|name| name.starts_with("x-"));
+            self.0.lock().unwrap().extend(headers);
+            Box::pin(async { Ok(Response::new(StatusCode::OK, r#"{"data":[]}"#)) })
+        }
+    }
+
+    /// Records the hooks it was called with, and adds a header named after itself
+    #[derive(Debug)]
+    struct Recorder(&'static str, Arc<Mutex<Vec<String>>>, bool);
+
+    impl Middleware for Recorder {
+        fn on_request(&self, request: &mut Request) -> Option<Response> {
+            self.1.lock().unwrap().push(format!("request {}", self.0));
+            let name = HeaderName::try_from(format!("x-{}", self.0)).unwrap();
+            request
+                .headers_mut()
+                .insert(name, HeaderValue::from_static("1"));
+            self.2
+                .then(|| Response::new(StatusCode::UNPROCESSABLE_ENTITY, "{}"))
+        }
+
+        fn on_response(&self, info: &ResponseInfo, response: &mut Response) {
+            self.1.lock().unwrap().push(format!(
+                "response {} {} {} {}",
+                self.0,
+                info.endpoint,
+                response.status().as_u16(),
+                info.short_circuited
+            ));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_middleware_order() {
+        let sent = Arc::default();
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let client = ClientBuilder::new("https://panel.example.com", "key")
+            .with_transport(ListTransport(Arc::clone(&sent)))
+            .with_middleware(Recorder("a", Arc::clone(&events), false))
+            .with_middleware(Recorder("b", Arc::clone(&events), false))
+            .build();
+        assert!(client.list_servers().await.unwrap().is_empty());
+        assert_eq!(*sent.lock().unwrap(), ["x-a", "x-b"]);
+        assert_eq!(
+            *events.lock().unwrap(),
+            [
+                "request a",
+                "request b",
+                "response b  200 false",
+                "response a  200 false"
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_short_circuit() {
+        let sent = Arc::default();
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let client = ClientBuilder::new("https://panel.example.com", "key")
+            .with_transport(ListTransport(Arc::clone(&sent)))
+            .with_middleware(Recorder("a", Arc::clone(&events), false))
+            .with_middleware(Recorder("b", Arc::clone(&events), true))
+            .with_middleware(Recorder("c", Arc::clone(&events), false))
+            .build();
+        let result = client.get_server("1a7ce997").get_details().await;
+        assert!(matches!(result, Err(crate::Error::Http(_))));
+        assert!(sent.lock().unwrap().is_empty());
+        assert_eq!(
+            *events.lock().unwrap(),
+            [
+                "request a",
+                "request b",
+                "response b servers/1a7ce997 422 true",
+                "response a servers/1a7ce997 422 true"
+            ]
+        );
+    }
+}
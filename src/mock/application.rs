@@ -0,0 +1,847 @@
+//! The routes of the application API, under `api/application`
+
+use super::client::database_name;
+use super::state::{
+    self, list, object, Limits, Location, Node, PanelState, Server, User, DATABASE_HOST,
+};
+use super::{display, error, no_content, not_found, ok, MockRequest, Outcome, Reply, Validator};
+use crate::client::ServerState;
+use reqwest::StatusCode;
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+pub(super) fn route(state: &mut PanelState, request: &MockRequest) -> Outcome {
+    match (request.method.as_str(), &request.segments()[..]) {
+        (_, ["servers", path @ ..]) => servers(state, request, path),
+        (_, ["locations", path @ ..]) => locations(state, request, path),
+        (_, ["nodes", path @ ..]) => nodes(state, request, path),
+        ("DELETE", ["allocations", id]) => delete_allocation(state, None, id),
+        (_, ["nests", path @ ..]) => nests(state, request, path),
+        (_, ["users", path @ ..]) => users(state, request, path),
+        _ => Err(not_found()),
+    }
+}
+
+/// Finds a server by its ID, as the application API doesn't accept identifiers
+fn server_index(state: &PanelState, id: &str) -> Result<usize, Reply> {
+    state
+        .servers
+        .iter()
+        .position(|server| server.id.to_string() == id)
+        .ok_or_else(not_found)
+}
+
+fn servers(state: &mut PanelState, request: &MockRequest, path: &[&str]) -> Outcome {
+    match (request.method.as_str(), path) {
+        ("GET", []) => request.list(
+            "server",
+            state
+                .servers
+                .iter()
+                .map(|server| state.application_server(server))
+                .collect(),
+            &[
+                ("name", "name"),
+                ("uuid", "uuid"),
+                ("uuidShort", "identifier"),
+                ("external_id", "external_id"),
+                ("image", "container.image"),
+                ("description", "description"),
+            ],
+            &["id", "uuid"],
+        ),
+        ("POST", []) => create_server(state, request),
+        ("GET", ["external", external_id]) => {
+            let server = state
+                .servers
+                .iter()
+                .find(|server| server.external_id.as_deref() == Some(*external_id))
+                .ok_or_else(not_found)?;
+            ok(object("server", state.application_server(server)))
+        }
+        ("GET", [id]) => {
+            let index = server_index(state, id)?;
+            ok(object(
+                "server",
+                state.application_server(&state.servers[index]),
+            ))
+        }
+        ("DELETE", [id] | [id, "force"]) => {
+            let index = server_index(state, id)?;
+            state.remove_server(index);
+            no_content()
+        }
+        ("PATCH", [id, "details"]) => {
+            let index = server_index(state, id)?;
+            let body = request.json()?;
+            let mut validator = Validator::new(&body);
+            let name = validator.string("name");
+            let user = validator.integer::<u32>("user");
+            let external_id = validator.optional_string("external_id");
+            let description = validator.optional_string("description");
+            if validator.has("user") && !state.users.iter().any(|u| u.id == user) {
+                validator.fail("user", "exists", "The selected user is invalid.");
+            }
+            let id = state.servers[index].id;
+            if external_id.as_ref().is_some_and(|external_id| {
+                state.servers.iter().any(|server| {
+                    server.id != id && server.external_id.as_ref() == Some(external_id)
+                })
+            }) {
+                validator.fail(
+                    "external_id",
+                    "unique",
+                    "The external id has already been taken.",
+                );
+            }
+            validator.finish()?;
+            let server = &mut state.servers[index];
+            server.name = name;
+            server.user = user;
+            server.external_id = external_id;
+            server.description = description;
+            ok(object(
+                "server",
+                state.application_server(&state.servers[index]),
+            ))
+        }
+        ("PATCH", [id, "build"]) => {
+            let index = server_index(state, id)?;
+            let body = request.json()?;
+            let mut validator = Validator::new(&body);
+            let allocation = validator.integer::<u32>("allocation");
+            let limits = limits(&mut validator, "");
+            let feature_limits = [
+                validator.optional_integer::<u32>("feature_limits.databases"),
+                validator.optional_integer::<u32>("feature_limits.allocations"),
+                validator.optional_integer::<u32>("feature_limits.backups"),
+            ];
+            // like the panel, allocations which can't be added are ignored
+            let (server, node) = (state.servers[index].id, state.servers[index].node);
+            let added: Vec<u32> = validator
+                .integers::<u32>("add_allocations")
+                .into_iter()
+                .filter(|&id| {
+                    state
+                        .allocation(id)
+                        .is_some_and(|a| a.node == node && a.server.is_none())
+                })
+                .collect();
+            let removed = validator.integers::<u32>("remove_allocations");
+            let owned = added.contains(&allocation)
+                || state
+                    .allocation(allocation)
+                    .is_some_and(|a| a.server == Some(server));
+            if validator.has("allocation") && !owned {
+                validator.fail(
+                    "allocation",
+                    "exists",
+                    "The selected allocation is invalid.",
+                );
+            }
+            validator.finish()?;
+            if removed.contains(&allocation) {
+                return Err(display(
+                    "You are attempting to delete the default allocation for this server but \
+                     there is no fallback allocation to use.",
+                ));
+            }
+            for id in added {
+                if let Some(allocation) = state.allocation_mut(id) {
+                    allocation.server = Some(server);
+                }
+            }
+            for id in removed {
+                if let Some(allocation) = state.allocation_mut(id) {
+                    if allocation.server == Some(server) {
+                        allocation.server = None;
+                    }
+                }
+            }
+            let server = &mut state.servers[index];
+            server.allocation = allocation;
+            server.limits = limits;
+            let [databases, allocations, backups] = feature_limits;
+            let current = &mut server.feature_limits;
+            current.databases = databases.unwrap_or(current.databases);
+            current.allocations = allocations.unwrap_or(current.allocations);
+            current.backups = backups.unwrap_or(current.backups);
+            ok(object(
+                "server",
+                state.application_server(&state.servers[index]),
+            ))
+        }
+        ("PATCH", [id, "startup"]) => {
+            let index = server_index(state, id)?;
+            let body = request.json()?;
+            let mut validator = Validator::new(&body);
+            let startup = validator.string("startup");
+            let egg = validator.integer::<u32>("egg");
+            let image = validator.optional_string("image");
+            let environment = environment(&validator);
+            if validator.has("egg") && state.egg(egg).is_none() {
+                validator.fail("egg", "exists", "The selected egg is invalid.");
+            }
+            validator.finish()?;
+            let server = &mut state.servers[index];
+            server.startup = startup;
+            server.egg = egg;
+            if let Some(image) = image {
+                server.image = image;
+            }
+            server.environment.extend(environment);
+            ok(object(
+                "server",
+                state.application_server(&state.servers[index]),
+            ))
+        }
+        ("POST", [id, "reinstall"]) => {
+            let index = server_index(state, id)?;
+            state.servers[index].state = ServerState::Offline;
+            no_content()
+        }
+        (_, [id, "databases", path @ ..]) => {
+            let index = server_index(state, id)?;
+            databases(state, index, request, path)
+        }
+        ("POST", [id, "suspend"]) => {
+            let index = server_index(state, id)?;
+            state.servers[index].suspended = true;
+            no_content()
+        }
+        ("POST", [id, "unsuspend" | "resume"]) => {
+            let index = server_index(state, id)?;
+            state.servers[index].suspended = false;
+            no_content()
+        }
+        _ => Err(not_found()),
+    }
+}
+
+/// Reads the resource limits of a server, from fields with the given prefix
+fn limits(validator: &mut Validator<'_>, prefix: &str) -> Limits {
+    Limits {
+        memory: validator.integer(&format!("{prefix}memory")),
+        swap: validator.integer(&format!("{prefix}swap")),
+        disk: validator.integer(&format!("{prefix}disk")),
+        io: validator.integer(&format!("{prefix}io")),
+        cpu: validator.integer(&format!("{prefix}cpu")),
+        threads: validator.optional_string(&format!("{prefix}threads")),
+        oom_disabled: validator
+            .boolean(&format!("{prefix}oom_disabled"))
+            .unwrap_or(true),
+    }
+}
+
+/// Reads the environment variables of a server, where values may be given as any scalar
+fn environment(validator: &Validator<'_>) -> BTreeMap<String, String> {
+    let Some(Value::Object(environment)) = validator.value("environment") else {
+        return BTreeMap::new();
+    };
+    environment
+        .iter()
+        .map(|(key, value)| {
+            let value = match value {
+                Value::String(value) => value.clone(),
+                Value::Null => String::new(),
+                value => value.to_string(),
+            };
+            (key.clone(), value)
+        })
+        .collect()
+}
+
+fn create_server(state: &mut PanelState, request: &MockRequest) -> Outcome {
+    let body = request.json()?;
+    let mut validator = Validator::new(&body);
+    let name = validator.string("name");
+    let user = validator.integer::<u32>("user");
+    let egg = validator.integer::<u32>("egg");
+    let image = validator.string("docker_image");
+    let startup = validator.string("startup");
+    let description = validator.optional_string("description");
+    let external_id = validator.optional_string("external_id");
+    let mut environment = environment(&validator);
+    let limits = limits(&mut validator, "limits.");
+    let databases = validator.integer::<u32>("feature_limits.databases");
+    let allocations = validator.optional_integer::<u32>("feature_limits.allocations");
+    let backups = validator.integer::<u32>("feature_limits.backups");
+    let allocation = validator.integer::<u32>("allocation.default");
+
+    match state.egg(egg) {
+        Some(egg) => {
+            for variable in &egg.variables {
+                let value = environment
+                    .entry(variable.env_variable.clone())
+                    .or_default();
+                if value.is_empty() && variable.rules.split('|').any(|rule| rule == "required") {
+                    validator.fail(
+                        &format!("environment.{}", variable.env_variable),
+                        "required",
+                        format!("The {} variable field is required.", variable.name),
+                    );
+                }
+            }
+        }
+        None if validator.has("egg") => {
+            validator.fail("egg", "exists", "The selected egg is invalid.");
+        }
+        None => {}
+    }
+    let free = state
+        .allocation(allocation)
+        .is_some_and(|allocation| allocation.server.is_none());
+    if validator.has("allocation.default") && !free {
+        validator.fail(
+            "allocation.default",
+            "exists",
+            "The selected allocation.default is invalid.",
+        );
+    }
+    if external_id.as_ref().is_some_and(|external_id| {
+        state
+            .servers
+            .iter()
+            .any(|server| server.external_id.as_ref() == Some(external_id))
+    }) {
+        validator.fail(
+            "external_id",
+            "unique",
+            "The external id has already been taken.",
+        );
+    }
+    validator.finish()?;
+
+    let id = state.next_id();
+    let Some(egg) = state.egg(egg) else {
+        return Err(not_found());
+    };
+    let mut server = Server::new(id, &name, egg);
+    server.user = user;
+    server.image = image;
+    server.startup = startup;
+    server.description = description;
+    server.external_id = external_id;
+    server.environment = environment;
+    server.limits = limits;
+    server.feature_limits.databases = databases;
+    server.feature_limits.allocations = allocations.unwrap_or_default();
+    server.feature_limits.backups = backups;
+    state.assign_allocation(&mut server, allocation);
+    let json = state.application_server(&server);
+    state.servers.push(server);
+    Ok(Reply::Json(StatusCode::CREATED, object("server", json)))
+}
+
+fn locations(state: &mut PanelState, request: &MockRequest, path: &[&str]) -> Outcome {
+    let location = |state: &PanelState, id: &str| {
+        state
+            .locations
+            .iter()
+            .position(|location| location.id.to_string() == id)
+            .ok_or_else(not_found)
+    };
+    let short_taken = |state: &PanelState, short: &str, id: u32| {
+        state
+            .locations
+            .iter()
+            .any(|location| location.short == short && location.id != id)
+    };
+    let includes = request.includes();
+    match (request.method.as_str(), path) {
+        ("GET", []) => request.list(
+            "location",
+            state
+                .locations
+                .iter()
+                .map(|location| state.location(location, &includes))
+                .collect(),
+            &[("short", "short"), ("long", "long")],
+            &["id"],
+        ),
+        ("POST", []) => {
+            let body = request.json()?;
+            let mut validator = Validator::new(&body);
+            let mut location = Location {
+                id: 0,
+                short: validator.string("short"),
+                long: validator.optional_string("long"),
+            };
+            if short_taken(state, &location.short, 0) {
+                validator.fail("short", "unique", "The short has already been taken.");
+            }
+            validator.finish()?;
+            location.id = state.next_id();
+            let json = state.location(&location, &[]);
+            state.locations.push(location);
+            Ok(Reply::Json(StatusCode::CREATED, object("location", json)))
+        }
+        ("GET", [id]) => {
+            let location = &state.locations[location(state, id)?];
+            ok(object("location", state.location(location, &includes)))
+        }
+        ("PATCH", [id]) => {
+            let index = location(state, id)?;
+            let body = request.json()?;
+            let mut validator = Validator::new(&body);
+            let short = validator.optional_string("short");
+            let long = validator.optional_string("long");
+            let id = state.locations[index].id;
+            if short
+                .as_ref()
+                .is_some_and(|short| short_taken(state, short, id))
+            {
+                validator.fail("short", "unique", "The short has already been taken.");
+            }
+            validator.finish()?;
+            let location = &mut state.locations[index];
+            if let Some(short) = short {
+                location.short = short;
+            }
+            if let Some(long) = long {
+                location.long = Some(long);
+            }
+            ok(object(
+                "location",
+                state.location(&state.locations[index], &[]),
+            ))
+        }
+        ("DELETE", [id]) => {
+            let index = location(state, id)?;
+            let id = state.locations[index].id;
+            if state.nodes.iter().any(|node| node.location_id == id) {
+                return Err(error(
+                    StatusCode::BAD_REQUEST,
+                    "HasActiveNodesException",
+                    "Cannot delete a location that has active nodes attached to it.",
+                ));
+            }
+            state.locations.remove(index);
+            no_content()
+        }
+        _ => Err(not_found()),
+    }
+}
+
+fn databases(
+    state: &mut PanelState,
+    index: usize,
+    request: &MockRequest,
+    path: &[&str],
+) -> Outcome {
+    let database = |state: &PanelState, id: &str| {
+        state.servers[index]
+            .databases
+            .iter()
+            .position(|database| database.internal_id.to_string() == id)
+            .ok_or_else(not_found)
+    };
+    let includes = request.includes();
+    let server = state.servers[index].id;
+    match (request.method.as_str(), path) {
+        ("GET", []) => ok(list(
+            state.servers[index]
+                .databases
+                .iter()
+                .map(|database| {
+                    object(
+                        "server_database",
+                        database.application_json(server, &includes),
+                    )
+                })
+                .collect(),
+        )),
+        ("POST", []) => {
+            let body = request.json()?;
+            let mut validator = Validator::new(&body);
+            let name = database_name(&mut validator);
+            let remote = validator.string("remote");
+            let host = validator.integer::<u32>("host");
+            if validator.has("host") && host != DATABASE_HOST {
+                validator.fail("host", "exists", "The selected host is invalid.");
+            }
+            validator.finish()?;
+            let database = state.add_database(index, &name, remote)?;
+            let database = &state.servers[index].databases[database];
+            Ok(Reply::Json(
+                StatusCode::CREATED,
+                object("server_database", database.application_json(server, &[])),
+            ))
+        }
+        ("GET", [database_id]) => {
+            let database = &state.servers[index].databases[database(state, database_id)?];
+            ok(object(
+                "server_database",
+                database.application_json(server, &includes),
+            ))
+        }
+        ("POST", [database_id, "reset-password"]) => {
+            let position = database(state, database_id)?;
+            let id = state.next_id();
+            let password = state::uuid(id).simple().to_string();
+            state.servers[index].databases[position].password = password;
+            no_content()
+        }
+        ("DELETE", [database_id]) => {
+            let position = database(state, database_id)?;
+            state.servers[index].databases.remove(position);
+            no_content()
+        }
+        _ => Err(not_found()),
+    }
+}
+
+fn nodes(state: &mut PanelState, request: &MockRequest, path: &[&str]) -> Outcome {
+    let node = |state: &PanelState, id: &str| {
+        state
+            .nodes
+            .iter()
+            .position(|node| node.id.to_string() == id)
+            .ok_or_else(not_found)
+    };
+    match (request.method.as_str(), path) {
+        ("GET", []) => request.list(
+            "node",
+            state.nodes.iter().map(Node::json).collect(),
+            &[
+                ("uuid", "uuid"),
+                ("name", "name"),
+                ("fqdn", "fqdn"),
+                ("daemon_token_id", "daemon_token_id"),
+            ],
+            &["id", "uuid", "memory", "disk"],
+        ),
+        ("POST", []) => {
+            let body = request.json()?;
+            let mut validator = Validator::new(&body);
+            let mut node = Node {
+                id: 0,
+                name: validator.string("name"),
+                description: validator.optional_string("description"),
+                location_id: validator.integer("location_id"),
+                fqdn: validator.string("fqdn"),
+                scheme: validator.string("scheme"),
+                public: validator.boolean("public").unwrap_or(true),
+                behind_proxy: validator.boolean("behind_proxy").unwrap_or_default(),
+                maintenance_mode: validator.boolean("maintenance_mode").unwrap_or_default(),
+                memory: validator.integer("memory"),
+                memory_overallocate: validator.integer("memory_overallocate"),
+                disk: validator.integer("disk"),
+                disk_overallocate: validator.integer("disk_overallocate"),
+                upload_size: validator.optional_integer("upload_size").unwrap_or(100),
+                daemon_listen: validator.integer("daemon_listen"),
+                daemon_sftp: validator.integer("daemon_sftp"),
+                daemon_base: validator
+                    .optional_string("daemon_base")
+                    .unwrap_or_else(|| "/var/lib/pterodactyl/volumes".to_owned()),
+            };
+            if !matches!(&node.scheme[..], "" | "http" | "https") {
+                validator.fail("scheme", "in", "The selected scheme is invalid.");
+            }
+            validator.finish()?;
+            node.id = state.next_id();
+            let json = node.json();
+            state.nodes.push(node);
+            Ok(Reply::Json(StatusCode::CREATED, object("node", json)))
+        }
+        ("GET", [id]) => ok(object("node", state.nodes[node(state, id)?].json())),
+        ("PATCH", [id]) => {
+            let index = node(state, id)?;
+            let body = request.json()?;
+            let mut validator = Validator::new(&body);
+            let node = &mut state.nodes[index];
+            macro_rules! update {
+                ($($field:ident: $read:ident),* $(,)?) => {
+                    $(if let Some(value) = validator.$read(stringify!($field)) {
+                        node.$field = value;
+                    })*
+                };
+            }
+            update!(
+                name: optional_string,
+                location_id: optional_integer,
+                fqdn: optional_string,
+                scheme: optional_string,
+                public: boolean,
+                behind_proxy: boolean,
+                maintenance_mode: boolean,
+                memory: optional_integer,
+                memory_overallocate: optional_integer,
+                disk: optional_integer,
+                disk_overallocate: optional_integer,
+                upload_size: optional_integer,
+                daemon_listen: optional_integer,
+                daemon_sftp: optional_integer,
+                daemon_base: optional_string,
+            );
+            if let Some(description) = validator.optional_string("description") {
+                node.description = Some(description);
+            }
+            validator.finish()?;
+            ok(object("node", node.json()))
+        }
+        ("DELETE", [id]) => {
+            let index = node(state, id)?;
+            let id = state.nodes[index].id;
+            if state.servers.iter().any(|server| server.node == id) {
+                return Err(error(
+                    StatusCode::BAD_REQUEST,
+                    "HasActiveServersException",
+                    "Cannot delete a node that has active servers attached to it.",
+                ));
+            }
+            state.nodes.remove(index);
+            state.allocations.retain(|allocation| allocation.node != id);
+            no_content()
+        }
+        ("GET", [id, "allocations"]) => {
+            let id = state.nodes[node(state, id)?].id;
+            request.list(
+                "allocation",
+                state
+                    .allocations
+                    .iter()
+                    .filter(|allocation| allocation.node == id)
+                    .map(|allocation| allocation.application_json())
+                    .collect(),
+                &[
+                    ("ip", "ip"),
+                    ("port", "port"),
+                    ("ip_alias", "ip_alias"),
+                    ("server_id", "server_id"),
+                ],
+                &[],
+            )
+        }
+        ("POST", [id, "allocations"]) => {
+            let id = state.nodes[node(state, id)?].id;
+            let body = request.json()?;
+            let mut validator = Validator::new(&body);
+            let ip = validator.string("ip");
+            let alias = validator.optional_string("alias");
+            let ports = validator.strings("ports");
+            if ports.is_empty() && !validator.has("ports") {
+                validator.fail("ports", "required", "The ports field is required.");
+            }
+            let mut parsed = Vec::new();
+            for port in &ports {
+                let range = match port.split_once('-') {
+                    Some((start, end)) => start
+                        .trim()
+                        .parse::<u16>()
+                        .ok()
+                        .zip(end.trim().parse().ok()),
+                    None => port.trim().parse().ok().map(|port| (port, port)),
+                };
+                match range {
+                    Some((start, end)) if start <= end && start > 1024 => {
+                        parsed.extend(start..=end);
+                    }
+                    _ => {
+                        return Err(display(format!(
+                            "The mapping provided for {port} was invalid and could not be \
+                             processed."
+                        )));
+                    }
+                }
+            }
+            validator.finish()?;
+            for port in parsed {
+                let exists = state.allocations.iter().any(|allocation| {
+                    allocation.node == id && allocation.ip == ip && allocation.port == port
+                });
+                if !exists {
+                    state.add_allocation(id, &ip, port, alias.clone());
+                }
+            }
+            no_content()
+        }
+        ("DELETE", [id, "allocations", allocation]) => {
+            let id = state.nodes[node(state, id)?].id;
+            delete_allocation(state, Some(id), allocation)
+        }
+        _ => Err(not_found()),
+    }
+}
+
+/// Deletes an allocation, optionally checking that it belongs to the given node
+fn delete_allocation(state: &mut PanelState, node: Option<u32>, id: &str) -> Outcome {
+    let index = state
+        .allocations
+        .iter()
+        .position(|allocation| {
+            allocation.id.to_string() == id && node.is_none_or(|node| allocation.node == node)
+        })
+        .ok_or_else(not_found)?;
+    if state.allocations[index].server.is_some() {
+        return Err(error(
+            StatusCode::BAD_REQUEST,
+            "ServerUsingAllocationException",
+            "Cannot delete an allocation that is currently assigned to a server.",
+        ));
+    }
+    state.allocations.remove(index);
+    no_content()
+}
+
+fn nests(state: &mut PanelState, request: &MockRequest, path: &[&str]) -> Outcome {
+    let nest = |state: &PanelState, id: &str| {
+        state
+            .nests
+            .iter()
+            .position(|nest| nest.id.to_string() == id)
+            .ok_or_else(not_found)
+    };
+    let includes = request.includes();
+    match (request.method.as_str(), path) {
+        ("GET", []) => request.list(
+            "nest",
+            state.nests.iter().map(|nest| nest.json()).collect(),
+            &[],
+            &[],
+        ),
+        ("GET", [id]) => ok(object("nest", state.nests[nest(state, id)?].json())),
+        ("GET", [id, "eggs"]) => {
+            let id = state.nests[nest(state, id)?].id;
+            request.list(
+                "egg",
+                state
+                    .eggs
+                    .iter()
+                    .filter(|egg| egg.nest == id)
+                    .map(|egg| egg.json(&includes))
+                    .collect(),
+                &[],
+                &[],
+            )
+        }
+        ("GET", [id, "eggs", egg]) => {
+            let id = state.nests[nest(state, id)?].id;
+            let egg = state
+                .eggs
+                .iter()
+                .find(|e| e.nest == id && e.id.to_string() == *egg)
+                .ok_or_else(not_found)?;
+            ok(object("egg", egg.json(&includes)))
+        }
+        _ => Err(not_found()),
+    }
+}
+
+fn users(state: &mut PanelState, request: &MockRequest, path: &[&str]) -> Outcome {
+    let user = |state: &PanelState, id: &str| {
+        state
+            .users
+            .iter()
+            .position(|user| user.id.to_string() == id)
+            .ok_or_else(not_found)
+    };
+    let includes = request.includes();
+    match (request.method.as_str(), path) {
+        ("GET", []) => request.list(
+            "user",
+            state
+                .users
+                .iter()
+                .map(|user| state.application_user(user, &includes))
+                .collect(),
+            &[
+                ("email", "email"),
+                ("uuid", "uuid"),
+                ("username", "username"),
+                ("external_id", "external_id"),
+            ],
+            &["id", "uuid"],
+        ),
+        ("POST", []) => {
+            let body = request.json()?;
+            let mut validator = Validator::new(&body);
+            let mut user = read_user(&mut validator, state, None);
+            validator.finish()?;
+            user.id = state.next_id();
+            let json = state.application_user(&user, &[]);
+            state.users.push(user);
+            Ok(Reply::Json(StatusCode::CREATED, object("user", json)))
+        }
+        ("GET", ["external", external_id]) => {
+            let user = state
+                .users
+                .iter()
+                .find(|user| user.external_id.as_deref() == Some(*external_id))
+                .ok_or_else(not_found)?;
+            ok(object("user", state.application_user(user, &includes)))
+        }
+        ("GET", [id]) => {
+            let user = &state.users[user(state, id)?];
+            ok(object("user", state.application_user(user, &includes)))
+        }
+        ("PATCH", [id]) => {
+            let index = user(state, id)?;
+            let body = request.json()?;
+            let mut validator = Validator::new(&body);
+            let mut user = read_user(&mut validator, state, Some(state.users[index].id));
+            validator.finish()?;
+            user.id = state.users[index].id;
+            state.users[index] = user;
+            ok(object(
+                "user",
+                state.application_user(&state.users[index], &[]),
+            ))
+        }
+        ("DELETE", [id]) => {
+            let index = user(state, id)?;
+            let id = state.users[index].id;
+            if state.servers.iter().any(|server| server.user == id) {
+                return Err(display(
+                    "Cannot delete a user with active servers attached to their account.",
+                ));
+            }
+            state.users.remove(index);
+            no_content()
+        }
+        _ => Err(not_found()),
+    }
+}
+
+/// Reads the fields of a user being created, or updated if the ID of the user is given
+fn read_user(validator: &mut Validator<'_>, state: &PanelState, id: Option<u32>) -> User {
+    let user = User {
+        id: 0,
+        external_id: validator.optional_string("external_id"),
+        username: validator.string("username"),
+        email: validator.string("email"),
+        first_name: validator.string("first_name"),
+        last_name: validator.string("last_name"),
+        language: validator
+            .optional_string("language")
+            .unwrap_or_else(|| "en".to_owned()),
+        root_admin: validator.boolean("root_admin").unwrap_or_default(),
+    };
+    validator.optional_string("password");
+    if !user.email.is_empty() && !user.email.contains('@') {
+        validator.fail("email", "email", "The email must be a valid email address.");
+    }
+    let others = || state.users.iter().filter(|other| Some(other.id) != id);
+    for (field, taken) in [
+        ("email", others().any(|other| other.email == user.email)),
+        (
+            "username",
+            others().any(|other| other.username == user.username),
+        ),
+        (
+            "external_id",
+            user.external_id.is_some()
+                && others().any(|other| other.external_id == user.external_id),
+        ),
+    ] {
+        if taken {
+            let name = field.replace('_', " ");
+            validator.fail(
+                field,
+                "unique",
+                format!("The {name} has already been taken."),
+            );
+        }
+    }
+    user
+}
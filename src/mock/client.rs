@@ -0,0 +1,1014 @@
+//! The routes of the client API, under `api/client`
+
+use super::state::{
+    self, file_json, list, object, resolve, ApiKey, Backup, PanelState, Schedule, Server, Subuser,
+    Task, TIMESTAMP,
+};
+use super::{display, error, no_content, not_found, ok, MockRequest, Outcome, Reply, Validator};
+use crate::client::{PowerSignal, ServerState};
+use bytes::Bytes;
+use reqwest::StatusCode;
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+
+pub(super) fn route(state: &mut PanelState, request: &MockRequest) -> Outcome {
+    match (request.method.as_str(), &request.segments()[..]) {
+        ("GET", []) => list_servers(state, request),
+        ("GET", ["permissions"]) => ok(object(
+            "system_permissions",
+            json!({"permissions": permissions()}),
+        )),
+        (_, ["account", path @ ..]) => account(state, request, path),
+        (_, ["servers", id, path @ ..]) => {
+            let index = state.server_index(id).ok_or_else(not_found)?;
+            let suspended = state.servers[index].suspended;
+            if suspended && !(request.method == "GET" && path.is_empty()) {
+                return Err(error(
+                    StatusCode::CONFLICT,
+                    "ServerStateConflictException",
+                    "This server is currently suspended and the functionality requested is \
+                     unavailable.",
+                ));
+            }
+            server(state, index, request, path)
+        }
+        _ => Err(not_found()),
+    }
+}
+
+fn list_servers(state: &mut PanelState, request: &MockRequest) -> Outcome {
+    // every server is owned by the account, so none is only accessible as an admin
+    let servers = match request.query("type") {
+        Some("admin") => Vec::new(),
+        _ => state
+            .servers
+            .iter()
+            .map(|server| state.client_server(server))
+            .collect(),
+    };
+    request.list(
+        "server",
+        servers,
+        &[
+            ("*", "name"),
+            ("*", "uuid"),
+            ("*", "identifier"),
+            ("*", "description"),
+            ("uuid", "uuid"),
+            ("name", "name"),
+            ("description", "description"),
+            ("external_id", "external_id"),
+        ],
+        &[],
+    )
+}
+
+fn permissions() -> Value {
+    let group = |description: &str, keys: &[(&str, &str)]| {
+        let keys: BTreeMap<&str, &str> = keys.iter().copied().collect();
+        json!({"description": description, "keys": keys})
+    };
+    json!({
+        "control": group(
+            "Permissions that control a user's ability to control the power state of a server, \
+             or send commands.",
+            &[
+                ("console", "Allows a user to send commands to the server instance via the console."),
+                ("start", "Allows a user to start the server if it is stopped."),
+                ("stop", "Allows a user to stop a server if it is running."),
+                ("restart", "Allows a user to perform a server restart."),
+            ],
+        ),
+        "user": group(
+            "Permissions that allow a user to manage other subusers on a server.",
+            &[
+                ("create", "Allows a user to create new subusers for the server."),
+                ("read", "Allows the user to view subusers and their permissions for the server."),
+                ("update", "Allows a user to modify other subusers."),
+                ("delete", "Allows a user to delete a subuser from the server."),
+            ],
+        ),
+        "file": group(
+            "Permissions that control a user's ability to modify the filesystem for this server.",
+            &[
+                ("create", "Allows a user to create additional files and folders."),
+                ("read", "Allows a user to view the contents of a directory."),
+                ("read-content", "Allows a user to view the contents of a given file."),
+                ("update", "Allows a user to update the contents of an existing file or directory."),
+                ("delete", "Allows a user to delete files or directories."),
+                ("archive", "Allows a user to archive the contents of a directory."),
+            ],
+        ),
+        "backup": group(
+            "Permissions that control a user's ability to generate and manage server backups.",
+            &[
+                ("create", "Allows a user to create new backups for this server."),
+                ("read", "Allows a user to view all backups that exist for this server."),
+                ("delete", "Allows a user to remove backups from the system."),
+                ("download", "Allows a user to download a backup for the server."),
+            ],
+        ),
+    })
+}
+
+fn account(state: &mut PanelState, request: &MockRequest, path: &[&str]) -> Outcome {
+    let account = &mut state.account;
+    match (request.method.as_str(), path) {
+        ("GET", []) => ok(object("user", account.json())),
+        ("GET", ["activity"]) => request.list("activity_log", Vec::new(), &[], &[]),
+        ("GET", ["two-factor"]) => {
+            if account.two_factor {
+                return Err(error(
+                    StatusCode::BAD_REQUEST,
+                    "BadRequestHttpException",
+                    "Two-factor authentication is already enabled on this account.",
+                ));
+            }
+            ok(json!({
+                "data": {
+                    "image_url_data": "otpauth://totp/Pterodactyl:admin%40example.com?secret=MOCKSECRET",
+                    "secret": "MOCKSECRET",
+                },
+            }))
+        }
+        ("POST", ["two-factor"]) => {
+            let body = request.json()?;
+            let mut validator = Validator::new(&body);
+            let code = validator.string("code");
+            validator.finish()?;
+            // any six digits are accepted, as the mock has no clock to derive codes from
+            if code.len() != 6 || !code.bytes().all(|byte| byte.is_ascii_digit()) {
+                return Err(error(
+                    StatusCode::BAD_REQUEST,
+                    "TwoFactorAuthenticationTokenInvalid",
+                    "The token provided is not valid.",
+                ));
+            }
+            account.two_factor = true;
+            let tokens: Vec<String> = (1..=10).map(|i| format!("recovery-{i:02}")).collect();
+            ok(object("recovery_tokens", json!({"tokens": tokens})))
+        }
+        ("DELETE", ["two-factor"]) => {
+            let body = request.json()?;
+            if body["password"] != *account.password {
+                return Err(error(
+                    StatusCode::BAD_REQUEST,
+                    "BadRequestHttpException",
+                    "The password provided was not valid.",
+                ));
+            }
+            account.two_factor = false;
+            no_content()
+        }
+        ("PUT", ["email"]) => {
+            let body = request.json()?;
+            let mut validator = Validator::new(&body);
+            let email = validator.string("email");
+            let password = validator.string("password");
+            if !email.is_empty() && !email.contains('@') {
+                validator.fail("email", "email", "The email must be a valid email address.");
+            }
+            validator.finish()?;
+            if password != account.password {
+                return Err(invalid_password());
+            }
+            account.email = email;
+            no_content()
+        }
+        ("PUT", ["password"]) => {
+            let body = request.json()?;
+            let mut validator = Validator::new(&body);
+            let current = validator.string("current_password");
+            let password = validator.string("password");
+            if body["password_confirmation"] != *password {
+                validator.fail(
+                    "password",
+                    "confirmed",
+                    "The password confirmation does not match.",
+                );
+            } else if password.len() < 8 {
+                validator.fail(
+                    "password",
+                    "min",
+                    "The password must be at least 8 characters.",
+                );
+            }
+            validator.finish()?;
+            if current != account.password {
+                return Err(invalid_password());
+            }
+            account.password = password;
+            no_content()
+        }
+        ("GET", ["api-keys"]) => ok(list(
+            account
+                .api_keys
+                .iter()
+                .map(|key| object("api_key", key.json()))
+                .collect(),
+        )),
+        ("POST", ["api-keys"]) => {
+            let body = request.json()?;
+            let mut validator = Validator::new(&body);
+            let description = validator.string("description");
+            let allowed_ips = validator.strings("allowed_ips");
+            validator.finish()?;
+            if account.api_keys.len() >= 25 {
+                return Err(display(
+                    "You have reached the account limit for number of API keys.",
+                ));
+            }
+            let id = state.next_id();
+            let secret = state::uuid(id).simple().to_string();
+            let key = ApiKey {
+                identifier: format!("ptlc_{}", &secret[..11]),
+                description,
+                allowed_ips,
+            };
+            let created = json!({
+                "object": "api_key",
+                "attributes": key.json(),
+                "meta": {"secret_token": &secret[11..]},
+            });
+            state.account.api_keys.push(key);
+            Ok(Reply::Json(StatusCode::CREATED, created))
+        }
+        ("DELETE", ["api-keys", identifier]) => {
+            let index = account
+                .api_keys
+                .iter()
+                .position(|key| key.identifier == *identifier)
+                .ok_or_else(not_found)?;
+            account.api_keys.remove(index);
+            no_content()
+        }
+        _ => Err(not_found()),
+    }
+}
+
+fn invalid_password() -> Reply {
+    error(
+        StatusCode::BAD_REQUEST,
+        "InvalidPasswordProvidedException",
+        "The password provided was invalid for this account.",
+    )
+}
+
+fn server(state: &mut PanelState, index: usize, request: &MockRequest, path: &[&str]) -> Outcome {
+    match (request.method.as_str(), path) {
+        ("GET", []) => ok(object("server", state.client_server(&state.servers[index]))),
+        ("GET", ["resources"]) => ok(object("stats", state.servers[index].resources())),
+        ("POST", ["command"]) => {
+            let body = request.json()?;
+            let mut validator = Validator::new(&body);
+            let command = validator.string("command");
+            validator.finish()?;
+            let server = &mut state.servers[index];
+            if server.state == ServerState::Offline {
+                return Err(error(
+                    StatusCode::BAD_GATEWAY,
+                    "HttpException",
+                    "Server must be online in order to send commands.",
+                ));
+            }
+            server.commands.push(command);
+            no_content()
+        }
+        ("POST", ["power"]) => {
+            let body = request.json()?;
+            let mut validator = Validator::new(&body);
+            let signal = validator.string("signal");
+            let signal = match &signal[..] {
+                "start" => PowerSignal::Start,
+                "stop" => PowerSignal::Stop,
+                "restart" => PowerSignal::Restart,
+                "kill" => PowerSignal::Kill,
+                _ => {
+                    validator.fail("signal", "in", "The selected signal is invalid.");
+                    PowerSignal::Kill
+                }
+            };
+            validator.finish()?;
+            state.servers[index].apply_signal(signal);
+            no_content()
+        }
+        ("GET", ["websocket"]) => {
+            // the panel also serves the websocket of its node, in place of Wings
+            let id = state.next_id();
+            let node = node_url(state, index).replacen("http", "ws", 1);
+            let server = &mut state.servers[index];
+            let socket = format!("{node}/api/servers/{}/ws", server.uuid);
+            let token = format!("{}.{id}", server.uuid.simple());
+            server.tokens.push(token.clone());
+            ok(json!({"data": {"token": token, "socket": socket}}))
+        }
+        (_, ["files", action]) => {
+            let node = node_url(state, index);
+            files(&mut state.servers[index], &node, request, action)
+        }
+        (_, ["backups", path @ ..]) => backups(state, index, request, path),
+        (_, ["schedules", path @ ..]) => schedules(state, index, request, path),
+        (_, ["users", path @ ..]) => users(state, index, request, path),
+        (_, ["databases", path @ ..]) => databases(state, index, request, path),
+        (_, ["network", "allocations", path @ ..]) => network(state, index, request, path),
+        (_, ["startup", path @ ..]) => startup(state, index, request, path),
+        (_, ["settings", "docker-image"]) => startup(state, index, request, &["docker-image"]),
+        ("POST", ["settings", "rename"]) => {
+            let body = request.json()?;
+            let mut validator = Validator::new(&body);
+            let name = validator.string("name");
+            let description = validator.optional_string("description");
+            validator.finish()?;
+            let server = &mut state.servers[index];
+            server.name = name;
+            if description.is_some() {
+                server.description = description;
+            }
+            no_content()
+        }
+        ("POST", ["settings", "reinstall"]) => {
+            state.servers[index].state = ServerState::Offline;
+            Ok(Reply::Empty(StatusCode::ACCEPTED))
+        }
+        _ => Err(not_found()),
+    }
+}
+
+/// The URL of the Wings daemon of the node of a server, which signed URLs point to
+fn node_url(state: &PanelState, index: usize) -> String {
+    state
+        .node(state.servers[index].node)
+        .map(|node| format!("{}://{}:{}", node.scheme, node.fqdn, node.daemon_listen))
+        .unwrap_or_default()
+}
+
+fn signed_url(url: String) -> Outcome {
+    ok(object("signed_url", json!({"url": url})))
+}
+
+/// The contents of an archive created by the mock panel: the paths of the archived files,
+/// relative to the directory they were archived from, with the contents of the files
+type Archive = BTreeMap<String, Option<Vec<u8>>>;
+
+fn files(server: &mut Server, node: &str, request: &MockRequest, action: &str) -> Outcome {
+    let file = || resolve("/", request.query("file").unwrap_or_default());
+    match (request.method.as_str(), action) {
+        ("GET", "list") => {
+            let dir = resolve("/", request.query("directory").unwrap_or_default());
+            if !server.is_dir(&dir) {
+                return Err(not_found());
+            }
+            let files = server
+                .children(&dir)
+                .into_iter()
+                .map(|(name, contents)| object("file_object", file_json(name, contents)))
+                .collect();
+            ok(list(files))
+        }
+        ("GET", "contents") => match server.files.get(&file()) {
+            Some(Some(contents)) => Ok(Reply::Bytes(contents.clone())),
+            Some(None) => Err(display("Cannot perform that action: file is a directory.")),
+            None => Err(not_found()),
+        },
+        ("GET", "download") => {
+            let file = file();
+            if !server.files.contains_key(&file) {
+                return Err(not_found());
+            }
+            signed_url(format!(
+                "{node}/download/file?token={}",
+                urlencoding::encode(&format!("{}:{file}", server.uuid))
+            ))
+        }
+        ("GET", "upload") => signed_url(format!("{node}/upload/file?token={}", server.uuid)),
+        ("POST", "write") => {
+            let file = file();
+            if server.is_dir(&file) {
+                return Err(display("Cannot perform that action: file is a directory."));
+            }
+            server.write_file(&file, request.body().clone());
+            no_content()
+        }
+        ("POST", "create-folder") => {
+            let body = request.json()?;
+            let mut validator = Validator::new(&body);
+            let root = validator.optional_string("root").unwrap_or_default();
+            let name = validator.string("name");
+            validator.finish()?;
+            server.create_dir(&resolve(&root, &name));
+            no_content()
+        }
+        ("PUT", "rename") => {
+            let body = request.json()?;
+            let root = body["root"].as_str().unwrap_or("/");
+            let renames = body["files"].as_array().cloned().unwrap_or_default();
+            for rename in renames {
+                let from = resolve(root, rename["from"].as_str().unwrap_or_default());
+                let to = resolve(root, rename["to"].as_str().unwrap_or_default());
+                if !server.files.contains_key(&from) {
+                    return Err(not_found());
+                }
+                if server.files.contains_key(&to) {
+                    return Err(display(
+                        "Cannot move or rename file, destination already exists.",
+                    ));
+                }
+                server.create_dir(state::parent(&to));
+                for path in server.subtree(&from) {
+                    let contents = server.files.remove(&path).flatten();
+                    let path = format!("{to}{}", &path[from.len()..]);
+                    server.files.insert(path, contents);
+                }
+            }
+            no_content()
+        }
+        ("POST", "copy") => {
+            let body = request.json()?;
+            let mut validator = Validator::new(&body);
+            let location = resolve("/", &validator.string("location"));
+            validator.finish()?;
+            let contents = match server.files.get(&location) {
+                Some(Some(contents)) => contents.clone(),
+                Some(None) => return Err(display("Cannot copy a directory.")),
+                None => return Err(not_found()),
+            };
+            let (name, extension) = match location.rfind('.') {
+                Some(dot) if dot > location.rfind('/').unwrap_or(0) + 1 => location.split_at(dot),
+                _ => (&location[..], ""),
+            };
+            let mut copy = format!("{name} copy{extension}");
+            let mut i = 0;
+            while server.files.contains_key(&copy) {
+                i += 1;
+                copy = format!("{name} copy {i}{extension}");
+            }
+            server.files.insert(copy, Some(contents));
+            no_content()
+        }
+        ("POST", "delete") => {
+            let body = request.json()?;
+            let root = body["root"].as_str().unwrap_or("/");
+            for file in body["files"].as_array().into_iter().flatten() {
+                let file = resolve(root, file.as_str().unwrap_or_default());
+                for path in server.subtree(&file) {
+                    server.files.remove(&path);
+                }
+            }
+            no_content()
+        }
+        ("POST", "compress") => {
+            let body = request.json()?;
+            let root = resolve("/", body["root"].as_str().unwrap_or("/"));
+            let mut archive = Archive::new();
+            for file in body["files"].as_array().into_iter().flatten() {
+                let file = resolve(&root, file.as_str().unwrap_or_default());
+                if !server.files.contains_key(&file) {
+                    return Err(not_found());
+                }
+                for path in server.subtree(&file) {
+                    let relative = path[root.len()..].trim_start_matches('/').to_owned();
+                    let contents = server.files[&path].as_ref().map(|bytes| bytes.to_vec());
+                    archive.insert(relative, contents);
+                }
+            }
+            let mut name = format!("archive-{}.tar.gz", TIMESTAMP.replace(':', ""));
+            let mut i = 0;
+            while server.files.contains_key(&resolve(&root, &name)) {
+                i += 1;
+                name = format!("archive-{} ({i}).tar.gz", TIMESTAMP.replace(':', ""));
+            }
+            let contents = Bytes::from(serde_json::to_vec(&archive).unwrap_or_default());
+            server.write_file(&resolve(&root, &name), contents.clone());
+            ok(object("file_object", file_json(&name, Some(&contents))))
+        }
+        ("POST", "decompress") => {
+            let body = request.json()?;
+            let root = resolve("/", body["root"].as_str().unwrap_or("/"));
+            let file = resolve(&root, body["file"].as_str().unwrap_or_default());
+            let contents = match server.files.get(&file) {
+                Some(Some(contents)) => contents,
+                Some(None) => return Err(display("Cannot decompress a directory.")),
+                None => return Err(not_found()),
+            };
+            let archive: Archive = serde_json::from_slice(contents)
+                .map_err(|_err| display("The file is not an archive created by the mock panel."))?;
+            for (path, contents) in archive {
+                let path = resolve(&root, &path);
+                match contents {
+                    Some(contents) => server.write_file(&path, contents.into()),
+                    None => server.create_dir(&path),
+                }
+            }
+            no_content()
+        }
+        _ => Err(not_found()),
+    }
+}
+
+fn backups(state: &mut PanelState, index: usize, request: &MockRequest, path: &[&str]) -> Outcome {
+    let node = node_url(state, index);
+    if let ("POST", []) = (request.method.as_str(), path) {
+        let body = request.json()?;
+        let mut validator = Validator::new(&body);
+        let name = validator.optional_string("name");
+        let is_locked = validator.boolean("is_locked").unwrap_or_default();
+        let ignored = validator.optional_string("ignored").unwrap_or_default();
+        validator.finish()?;
+        let id = state.next_id();
+        let server = &mut state.servers[index];
+        let limit = server.feature_limits.backups as usize;
+        if server.backups.len() >= limit {
+            return Err(error(
+                StatusCode::BAD_REQUEST,
+                "TooManyBackupsException",
+                format!("Only {limit} backups may be created for this server."),
+            ));
+        }
+        let backup = Backup {
+            uuid: state::uuid(id),
+            name: name.unwrap_or_else(|| format!("Backup at {TIMESTAMP}")),
+            ignored_files: ignored.lines().map(str::to_owned).collect(),
+            bytes: server.disk_bytes(),
+            is_locked,
+        };
+        let json = backup.json();
+        server.backups.push(backup);
+        return ok(object("backup", json));
+    }
+
+    let server = &mut state.servers[index];
+    let backup = |uuid: &str| {
+        server
+            .backups
+            .iter()
+            .position(|backup| backup.uuid.to_string() == uuid)
+            .ok_or_else(not_found)
+    };
+    match (request.method.as_str(), path) {
+        ("GET", []) => request.list(
+            "backup",
+            server.backups.iter().map(Backup::json).collect(),
+            &[],
+            &[],
+        ),
+        ("GET", [uuid]) => ok(object("backup", server.backups[backup(uuid)?].json())),
+        ("GET", [uuid, "download"]) => {
+            let backup = &server.backups[backup(uuid)?];
+            signed_url(format!("{node}/download/backup?token={}", backup.uuid))
+        }
+        ("DELETE", [uuid]) => {
+            let index = backup(uuid)?;
+            if server.backups[index].is_locked {
+                return Err(error(
+                    StatusCode::BAD_REQUEST,
+                    "BackupLockedException",
+                    "Cannot delete a backup that is marked as locked.",
+                ));
+            }
+            server.backups.remove(index);
+            no_content()
+        }
+        _ => Err(not_found()),
+    }
+}
+
+/// Reads the name and cron fields of a schedule
+fn schedule_params(body: &Value) -> Result<(String, BTreeMap<&'static str, String>, bool), Reply> {
+    let mut validator = Validator::new(body);
+    let name = validator.string("name");
+    let mut cron = BTreeMap::new();
+    for field in ["minute", "hour", "day_of_month", "day_of_week"] {
+        cron.insert(field, validator.string(field));
+    }
+    let month = validator.optional_string("month");
+    cron.insert("month", month.unwrap_or_else(|| "*".to_owned()));
+    let is_active = validator.boolean("is_active").unwrap_or_default();
+    validator.finish()?;
+    Ok((name, cron, is_active))
+}
+
+/// Reads the action, payload and time offset of a task
+fn task_params(body: &Value) -> Result<(String, String, i64), Reply> {
+    let mut validator = Validator::new(body);
+    let action = validator.string("action");
+    let payload = match &action[..] {
+        "command" | "power" => validator.string("payload"),
+        "backup" => validator.optional_string("payload").unwrap_or_default(),
+        "" => String::new(),
+        _ => {
+            validator.fail("action", "in", "The selected action is invalid.");
+            String::new()
+        }
+    };
+    let time_offset = validator.integer::<i64>("time_offset");
+    if !(0..=900).contains(&time_offset) {
+        validator.fail(
+            "time_offset",
+            "between",
+            "The time offset must be between 0 and 900.",
+        );
+    }
+    validator.finish()?;
+    Ok((action, payload, time_offset))
+}
+
+fn schedules(
+    state: &mut PanelState,
+    index: usize,
+    request: &MockRequest,
+    path: &[&str],
+) -> Outcome {
+    let id = state.next_id();
+    let server = &mut state.servers[index];
+    let schedule = |server: &Server, id: &str| {
+        server
+            .schedules
+            .iter()
+            .position(|schedule| schedule.id.to_string() == id)
+            .ok_or_else(not_found)
+    };
+    match (request.method.as_str(), path) {
+        ("GET", []) => ok(list(
+            server
+                .schedules
+                .iter()
+                .map(|schedule| object("server_schedule", schedule.json()))
+                .collect(),
+        )),
+        ("POST", []) => {
+            let (name, cron, is_active) = schedule_params(&request.json()?)?;
+            let schedule = Schedule {
+                id,
+                name,
+                cron,
+                is_active,
+                tasks: Vec::new(),
+            };
+            let json = schedule.json();
+            server.schedules.push(schedule);
+            ok(object("server_schedule", json))
+        }
+        ("GET", [schedule_id]) => {
+            let schedule = &server.schedules[schedule(server, schedule_id)?];
+            ok(object("server_schedule", schedule.json()))
+        }
+        ("POST", [schedule_id]) => {
+            let index = schedule(server, schedule_id)?;
+            let (name, cron, is_active) = schedule_params(&request.json()?)?;
+            let schedule = &mut server.schedules[index];
+            schedule.name = name;
+            schedule.cron = cron;
+            schedule.is_active = is_active;
+            ok(object("server_schedule", schedule.json()))
+        }
+        ("DELETE", [schedule_id]) => {
+            let index = schedule(server, schedule_id)?;
+            server.schedules.remove(index);
+            no_content()
+        }
+        ("POST", [schedule_id, "tasks"]) => {
+            let index = schedule(server, schedule_id)?;
+            let (action, payload, time_offset) = task_params(&request.json()?)?;
+            let schedule = &mut server.schedules[index];
+            if schedule.tasks.len() >= 10 {
+                return Err(error(
+                    StatusCode::BAD_REQUEST,
+                    "ServiceLimitExceededException",
+                    "Schedules may not have more than 10 tasks associated with them.",
+                ));
+            }
+            let task = Task {
+                id,
+                sequence_id: schedule.tasks.len() as u32 + 1,
+                action,
+                payload,
+                time_offset,
+            };
+            let json = task.json();
+            schedule.tasks.push(task);
+            ok(object("schedule_task", json))
+        }
+        ("POST", [schedule_id, "tasks", task_id]) => {
+            let index = schedule(server, schedule_id)?;
+            let (action, payload, time_offset) = task_params(&request.json()?)?;
+            let task = server.schedules[index]
+                .tasks
+                .iter_mut()
+                .find(|task| task.id.to_string() == *task_id)
+                .ok_or_else(not_found)?;
+            task.action = action;
+            task.payload = payload;
+            task.time_offset = time_offset;
+            ok(object("schedule_task", task.json()))
+        }
+        ("DELETE", [schedule_id, "tasks", task_id]) => {
+            let index = schedule(server, schedule_id)?;
+            let tasks = &mut server.schedules[index].tasks;
+            let task = tasks
+                .iter()
+                .position(|task| task.id.to_string() == *task_id)
+                .ok_or_else(not_found)?;
+            tasks.remove(task);
+            for (task, sequence_id) in tasks.iter_mut().zip(1..) {
+                task.sequence_id = sequence_id;
+            }
+            no_content()
+        }
+        _ => Err(not_found()),
+    }
+}
+
+fn users(state: &mut PanelState, index: usize, request: &MockRequest, path: &[&str]) -> Outcome {
+    let id = state.next_id();
+    let server = &mut state.servers[index];
+    let user = |server: &Server, uuid: &str| {
+        server
+            .subusers
+            .iter()
+            .position(|user| user.uuid.to_string() == uuid)
+            .ok_or_else(not_found)
+    };
+    match (request.method.as_str(), path) {
+        ("GET", []) => ok(list(
+            server
+                .subusers
+                .iter()
+                .map(|user| object("server_subuser", user.json()))
+                .collect(),
+        )),
+        ("POST", []) => {
+            let body = request.json()?;
+            let mut validator = Validator::new(&body);
+            let email = validator.string("email");
+            let permissions = validator.strings("permissions");
+            if !email.is_empty() && !email.contains('@') {
+                validator.fail("email", "email", "The email must be a valid email address.");
+            }
+            validator.finish()?;
+            if server.subusers.iter().any(|user| user.email == email) {
+                return Err(error(
+                    StatusCode::BAD_REQUEST,
+                    "ServerSubuserExistsException",
+                    "A user with that email address is already assigned as a subuser for this \
+                     server.",
+                ));
+            }
+            let user = Subuser {
+                uuid: state::uuid(id),
+                email,
+                permissions,
+            };
+            let json = user.json();
+            server.subusers.push(user);
+            ok(object("server_subuser", json))
+        }
+        ("GET", [uuid]) => ok(object(
+            "server_subuser",
+            server.subusers[user(server, uuid)?].json(),
+        )),
+        ("POST", [uuid]) => {
+            let index = user(server, uuid)?;
+            let body = request.json()?;
+            let mut validator = Validator::new(&body);
+            let permissions = validator.strings("permissions");
+            validator.finish()?;
+            let user = &mut server.subusers[index];
+            user.permissions = permissions;
+            ok(object("server_subuser", user.json()))
+        }
+        ("DELETE", [uuid]) => {
+            let index = user(server, uuid)?;
+            server.subusers.remove(index);
+            no_content()
+        }
+        _ => Err(not_found()),
+    }
+}
+
+/// Reads the name of a database being created, which the panel prefixes with the server ID
+pub(super) fn database_name(validator: &mut Validator<'_>) -> String {
+    let name = validator.string("database");
+    if !name
+        .bytes()
+        .all(|byte| byte.is_ascii_alphanumeric() || byte == b'_')
+    {
+        validator.fail(
+            "database",
+            "regex",
+            "The database may only contain letters, numbers and underscores.",
+        );
+    }
+    name
+}
+
+fn databases(
+    state: &mut PanelState,
+    index: usize,
+    request: &MockRequest,
+    path: &[&str],
+) -> Outcome {
+    let id = state.next_id();
+    let password = state::uuid(id).simple().to_string();
+    let server = &mut state.servers[index];
+    let database = |server: &Server, id: &str| {
+        server
+            .databases
+            .iter()
+            .position(|database| database.id == id)
+            .ok_or_else(not_found)
+    };
+    match (request.method.as_str(), path) {
+        ("GET", []) => {
+            let with_password = request.includes().contains(&"password");
+            ok(list(
+                server
+                    .databases
+                    .iter()
+                    .map(|database| object("server_database", database.json(with_password)))
+                    .collect(),
+            ))
+        }
+        ("POST", []) => {
+            let body = request.json()?;
+            let mut validator = Validator::new(&body);
+            let name = database_name(&mut validator);
+            let remote = validator.string("remote");
+            validator.finish()?;
+            let database = state.add_database(index, &name, remote)?;
+            let json = state.servers[index].databases[database].json(true);
+            ok(object("server_database", json))
+        }
+        ("POST", [database_id, "rotate-password"]) => {
+            let index = database(server, database_id)?;
+            let database = &mut server.databases[index];
+            database.password = password;
+            ok(object("server_database", database.json(true)))
+        }
+        ("DELETE", [database_id]) => {
+            let index = database(server, database_id)?;
+            server.databases.remove(index);
+            no_content()
+        }
+        _ => Err(not_found()),
+    }
+}
+
+fn network(state: &mut PanelState, index: usize, request: &MockRequest, path: &[&str]) -> Outcome {
+    let (server, primary, node) = {
+        let server = &state.servers[index];
+        (server.id, server.allocation, server.node)
+    };
+    let allocation = |state: &PanelState, id: &str| {
+        state
+            .allocations
+            .iter()
+            .position(|allocation| {
+                allocation.server == Some(server) && allocation.id.to_string() == id
+            })
+            .ok_or_else(not_found)
+    };
+    match (request.method.as_str(), path) {
+        ("GET", []) => ok(list(
+            state
+                .allocations
+                .iter()
+                .filter(|allocation| allocation.server == Some(server))
+                .map(|allocation| object("allocation", allocation.client_json(primary)))
+                .collect(),
+        )),
+        ("POST", []) => {
+            let assigned = state
+                .allocations
+                .iter()
+                .filter(|allocation| allocation.server == Some(server))
+                .count();
+            if assigned >= state.servers[index].feature_limits.allocations as usize {
+                return Err(display(
+                    "Cannot assign additional allocations to this server: limit has been reached.",
+                ));
+            }
+            let id = state.free_allocation(Some(node)).ok_or_else(|| {
+                error(
+                    StatusCode::BAD_REQUEST,
+                    "NoAutoAllocationSpaceAvailableException",
+                    "Cannot assign additional allocation: no more space available on node.",
+                )
+            })?;
+            let allocation = state.allocation_mut(id).ok_or_else(not_found)?;
+            allocation.server = Some(server);
+            ok(object("allocation", allocation.client_json(primary)))
+        }
+        ("POST", [id]) => {
+            let index = allocation(state, id)?;
+            let body = request.json()?;
+            let mut validator = Validator::new(&body);
+            let notes = validator.optional_string("notes");
+            validator.finish()?;
+            let allocation = &mut state.allocations[index];
+            allocation.notes = notes.filter(|notes| !notes.is_empty());
+            ok(object("allocation", allocation.client_json(primary)))
+        }
+        ("POST", [id, "primary"]) => {
+            let allocation = allocation(state, id)?;
+            let allocation = &state.allocations[allocation];
+            let json = allocation.client_json(allocation.id);
+            state.servers[index].allocation = allocation.id;
+            ok(object("allocation", json))
+        }
+        ("DELETE", [id]) => {
+            let index = allocation(state, id)?;
+            let allocation = &mut state.allocations[index];
+            if allocation.id == primary {
+                return Err(display(
+                    "You cannot delete the primary allocation for this server.",
+                ));
+            }
+            allocation.server = None;
+            allocation.notes = None;
+            no_content()
+        }
+        _ => Err(not_found()),
+    }
+}
+
+fn startup(state: &mut PanelState, index: usize, request: &MockRequest, path: &[&str]) -> Outcome {
+    let server = &state.servers[index];
+    let egg = state.egg(server.egg).ok_or_else(not_found)?;
+    match (request.method.as_str(), path) {
+        ("GET", []) => ok(json!({
+            "object": "list",
+            "data": state.variables(server),
+            "meta": {
+                "startup_command": server.invocation(),
+                "raw_startup_command": server.startup,
+                "docker_images": egg.docker_images(),
+            },
+        })),
+        ("PUT", ["variable"]) => {
+            let body = request.json()?;
+            let mut validator = Validator::new(&body);
+            let key = validator.string("key");
+            let value = validator.optional_string("value").unwrap_or_default();
+            validator.finish()?;
+            let position = egg
+                .variables
+                .iter()
+                .position(|variable| variable.env_variable == key)
+                .ok_or_else(|| {
+                    error(
+                        StatusCode::BAD_REQUEST,
+                        "BadRequestHttpException",
+                        "The environment variable you are trying to edit does not exist.",
+                    )
+                })?;
+            let variable = &egg.variables[position];
+            if !variable.user_editable {
+                return Err(error(
+                    StatusCode::BAD_REQUEST,
+                    "BadRequestHttpException",
+                    "The environment variable you are trying to edit is read-only.",
+                ));
+            }
+            if value.is_empty() && variable.rules.split('|').any(|rule| rule == "required") {
+                let mut validator = Validator::new(&body);
+                validator.fail(
+                    "value",
+                    "required",
+                    format!("The {} variable field is required.", variable.name),
+                );
+                validator.finish()?;
+            }
+            state.servers[index].environment.insert(key, value);
+            let server = &state.servers[index];
+            let json = state
+                .egg(server.egg)
+                .map(|egg| egg.variables[position].client_json(server))
+                .unwrap_or_default();
+            ok(object("egg_variable", json))
+        }
+        ("PUT", ["docker-image"]) => {
+            let body = request.json()?;
+            let mut validator = Validator::new(&body);
+            let image = validator.string("docker_image");
+            if !image.is_empty() && !egg.docker_images.iter().any(|(_, i)| *i == image) {
+                validator.fail(
+                    "docker_image",
+                    "in",
+                    "The selected docker image is invalid.",
+                );
+            }
+            validator.finish()?;
+            if !egg.docker_images.iter().any(|(_, i)| *i == server.image) {
+                return Err(error(
+                    StatusCode::BAD_REQUEST,
+                    "BadRequestHttpException",
+                    "This server's Docker image has been manually set by an administrator and \
+                     cannot be updated.",
+                ));
+            }
+            state.servers[index].image = image;
+            no_content()
+        }
+        _ => Err(not_found()),
+    }
+}
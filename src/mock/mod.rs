@@ -0,0 +1,990 @@
+//! An in-process fake panel, which keeps its servers, files, backups, schedules, subusers,
+//! databases, allocations, nodes, nests and eggs in memory and serves the client and application
+//! APIs over HTTP. Any non-empty API key is accepted, so clients can be pointed at it to run full
+//! workflows without a real panel:
+//!
+//! ```
+//! use pterodactyl_api::client::ClientBuilder;
+//! use pterodactyl_api::mock::MockPanel;
+//!
+//! # #[tokio::main(flavor = "current_thread")]
+//! # async fn main() -> pterodactyl_api::Result<()> {
+//! let panel = MockPanel::start().await?;
+//! let client = ClientBuilder::new(panel.url(), "key").build();
+//!
+//! let servers = client.list_servers().await?;
+//! let server = client.get_server(&servers[0].identifier);
+//! server.write_file("eula.txt", "eula=true").await?;
+//!
+//! let contents = panel.file_contents(&servers[0].identifier, "eula.txt");
+//! assert_eq!(contents.as_deref(), Some(&b"eula=true"[..]));
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Failed requests are answered with the same error bodies as the panel, such as a
+//! `ValidationException` for each invalid field.
+//!
+//! The panel also acts as the Wings daemon of its node. With the `websocket` feature, it serves
+//! the console websocket of its servers, which sends the events scripted with
+//! [`MockPanel::script_websocket`].
+
+mod application;
+mod client;
+mod state;
+#[cfg(feature = "websocket")]
+mod wings;
+
+#[cfg(feature = "websocket")]
+pub use wings::WingsEvent;
+
+use crate::client::{PowerSignal, ServerState};
+use bytes::Bytes;
+use http_body_util::{BodyExt, Full};
+use hyper::body::Incoming;
+use hyper::server::conn::http1;
+use hyper::service::service_fn;
+use hyper_util::rt::TokioIo;
+use reqwest::header::{HeaderValue, AUTHORIZATION, CONTENT_TYPE};
+use reqwest::{Method, StatusCode, Url};
+use serde_json::{json, Map, Value};
+use state::PanelState;
+use std::cmp::Ordering;
+use std::convert::Infallible;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use tokio::net::TcpListener;
+use tokio::task::JoinHandle;
+
+/// A fake panel listening on a local port. The panel stops when this is dropped
+#[derive(Debug)]
+pub struct MockPanel {
+    addr: SocketAddr,
+    state: Arc<Mutex<PanelState>>,
+    task: JoinHandle<()>,
+}
+
+impl MockPanel {
+    /// Starts a panel on a free local port, with a node, a nest with an egg, and a server named
+    /// `Test Server`. Must be called within a tokio runtime
+    pub async fn start() -> crate::Result<Self> {
+        let listener = TcpListener::bind(("127.0.0.1", 0)).await?;
+        let addr = listener.local_addr()?;
+        let state = Arc::new(Mutex::new(PanelState::seeded(addr)));
+        let task = tokio::spawn(serve(listener, Arc::clone(&state)));
+        Ok(MockPanel { addr, state, task })
+    }
+
+    /// Gets the URL of this panel, to pass to a client builder
+    pub fn url(&self) -> String {
+        format!("http://{}", self.addr)
+    }
+
+    /// Adds a server on the first free allocation, and returns its identifier
+    pub fn add_server(&self, name: impl AsRef<str>) -> String {
+        self.state().add_server(name.as_ref())
+    }
+
+    /// Gets the identifiers of the servers on this panel
+    pub fn servers(&self) -> Vec<String> {
+        self.state()
+            .servers
+            .iter()
+            .map(|server| server.identifier.clone())
+            .collect()
+    }
+
+    /// Writes a file on a server, creating its parent directories. Does nothing if the server
+    /// doesn't exist
+    pub fn write_file(&self, server: &str, file: &str, contents: impl Into<Bytes>) {
+        if let Some(server) = self.state().server_mut(server) {
+            server.write_file(&state::resolve("/", file), contents.into());
+        }
+    }
+
+    /// Gets the contents of a file on a server, or [`None`] if the file doesn't exist or is a
+    /// directory
+    pub fn file_contents(&self, server: &str, file: &str) -> Option<Bytes> {
+        self.state()
+            .server(server)?
+            .files
+            .get(&state::resolve("/", file))
+            .cloned()
+            .flatten()
+    }
+
+    /// Gets the commands sent to a server, in the order they were sent
+    pub fn commands(&self, server: &str) -> Vec<String> {
+        self.state()
+            .server(server)
+            .map(|server| server.commands.clone())
+            .unwrap_or_default()
+    }
+
+    /// Gets the power signals sent to a server, in the order they were sent
+    pub fn power_signals(&self, server: &str) -> Vec<PowerSignal> {
+        self.state()
+            .server(server)
+            .map(|server| server.signals.clone())
+            .unwrap_or_default()
+    }
+
+    /// Gets the current state of a server, or [`None`] if the server doesn't exist
+    pub fn server_state(&self, server: &str) -> Option<ServerState> {
+        self.state().server(server).map(|server| server.state)
+    }
+
+    /// Makes the next request fail with the given status and error, without handling it.
+    /// Failures queue up, so calling this twice fails the next two requests
+    pub fn fail_next(&self, status: StatusCode, code: impl AsRef<str>, detail: impl Into<String>) {
+        self.state()
+            .failures
+            .push_back(error(status, code.as_ref(), detail));
+    }
+
+    /// Gets the method and path of every request this panel received, such as
+    /// `GET /api/client/servers/1a7ce997`
+    pub fn requests(&self) -> Vec<String> {
+        self.state().requests.clone()
+    }
+
+    /// Scripts the events to send on the next websocket connection to a server, once it
+    /// authenticates. Does nothing if the server doesn't exist
+    #[cfg(feature = "websocket")]
+    pub fn script_websocket(&self, server: &str, events: impl IntoIterator<Item = WingsEvent>) {
+        let mut state = self.state();
+        if let Some(server) = state.server(server) {
+            let uuid = server.uuid.to_string();
+            state.wings.script(uuid, events);
+        }
+    }
+
+    /// Waits until every websocket connection to this panel is closed, after which everything
+    /// the clients sent is reflected in [`MockPanel::commands`] and [`MockPanel::power_signals`]
+    #[cfg(feature = "websocket")]
+    pub async fn websockets_closed(&self) {
+        loop {
+            let closed = Arc::clone(&self.state().wings.closed);
+            let notified = closed.notified();
+            if self.state().wings.open == 0 {
+                return;
+            }
+            notified.await;
+        }
+    }
+
+    fn state(&self) -> MutexGuard<'_, PanelState> {
+        lock(&self.state)
+    }
+}
+
+impl Drop for MockPanel {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+fn lock(state: &Mutex<PanelState>) -> MutexGuard<'_, PanelState> {
+    state.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+async fn serve(listener: TcpListener, state: Arc<Mutex<PanelState>>) {
+    loop {
+        let Ok((stream, _)) = listener.accept().await else {
+            continue;
+        };
+        let state = Arc::clone(&state);
+        tokio::spawn(async move {
+            let service = service_fn(move |request| handle(Arc::clone(&state), request));
+            // the connection only fails if the client goes away, which isn't the panel's concern
+            let _ = http1::Builder::new()
+                .serve_connection(TokioIo::new(stream), service)
+                .with_upgrades()
+                .await;
+        });
+    }
+}
+
+async fn handle(
+    state: Arc<Mutex<PanelState>>,
+    request: hyper::Request<Incoming>,
+) -> Result<hyper::Response<Full<Bytes>>, Infallible> {
+    #[cfg(feature = "websocket")]
+    let mut request = request;
+    #[cfg(feature = "websocket")]
+    if let Some(response) = wings::upgrade(&state, &mut request) {
+        return Ok(response);
+    }
+    let (parts, body) = request.into_parts();
+    let body = match body.collect().await {
+        Ok(body) => body.to_bytes(),
+        Err(_) => Bytes::new(),
+    };
+    let mut state = lock(&state);
+    state
+        .requests
+        .push(format!("{} {}", parts.method, parts.uri.path()));
+    let reply = match state.failures.pop_front() {
+        Some(reply) => reply,
+        None => dispatch(&mut state, &parts, body).unwrap_or_else(|reply| reply),
+    };
+    Ok(reply.into_response())
+}
+
+fn dispatch(state: &mut PanelState, parts: &hyper::http::request::Parts, body: Bytes) -> Outcome {
+    let authorized = parts
+        .headers
+        .get(AUTHORIZATION)
+        .and_then(|header| header.to_str().ok())
+        .and_then(|header| header.strip_prefix("Bearer "))
+        .is_some_and(|key| !key.trim().is_empty());
+    if !authorized {
+        return Err(error(
+            StatusCode::UNAUTHORIZED,
+            "AuthenticationException",
+            "Unauthenticated.",
+        ));
+    }
+    let path = parts.uri.path();
+    let (route, path): (fn(&mut PanelState, &MockRequest) -> Outcome, _) =
+        if let Some(path) = path.strip_prefix("/api/client") {
+            (client::route, path)
+        } else if let Some(path) = path.strip_prefix("/api/application") {
+            (application::route, path)
+        } else {
+            return Err(not_found());
+        };
+    let query = Url::parse(&format!("http://localhost{}", parts.uri))
+        .map(|url| url.query_pairs().into_owned().collect())
+        .unwrap_or_default();
+    let request = MockRequest {
+        method: parts.method.clone(),
+        path: path
+            .split('/')
+            .filter(|segment| !segment.is_empty())
+            .map(|segment| {
+                urlencoding::decode(segment).map_or_else(|_| segment.to_owned(), |s| s.into_owned())
+            })
+            .collect(),
+        query,
+        body,
+    };
+    route(state, &request)
+}
+
+/// A reply of the mock panel
+#[derive(Debug)]
+pub(super) enum Reply {
+    Json(StatusCode, Value),
+    Bytes(Bytes),
+    Empty(StatusCode),
+}
+
+impl Reply {
+    fn into_response(self) -> hyper::Response<Full<Bytes>> {
+        let (status, content_type, body) = match self {
+            Reply::Json(status, value) => (status, "application/json", value.to_string().into()),
+            Reply::Bytes(bytes) => (StatusCode::OK, "text/plain", bytes),
+            Reply::Empty(status) => (status, "text/plain", Bytes::new()),
+        };
+        let mut response = hyper::Response::new(Full::new(body));
+        *response.status_mut() = status;
+        response
+            .headers_mut()
+            .insert(CONTENT_TYPE, HeaderValue::from_static(content_type));
+        response
+    }
+}
+
+/// The result of handling a request, where both sides are sent as the reply
+pub(super) type Outcome = Result<Reply, Reply>;
+
+pub(super) fn ok(value: Value) -> Outcome {
+    Ok(Reply::Json(StatusCode::OK, value))
+}
+
+pub(super) fn no_content() -> Outcome {
+    Ok(Reply::Empty(StatusCode::NO_CONTENT))
+}
+
+/// An error body with a single error
+pub(super) fn error(status: StatusCode, code: &str, detail: impl Into<String>) -> Reply {
+    Reply::Json(
+        status,
+        json!({
+            "errors": [{
+                "code": code,
+                "status": status.as_str(),
+                "detail": detail.into(),
+            }],
+        }),
+    )
+}
+
+pub(super) fn not_found() -> Reply {
+    error(
+        StatusCode::NOT_FOUND,
+        "NotFoundHttpException",
+        "The requested resource could not be found on the server.",
+    )
+}
+
+/// A `DisplayException`, which the panel uses for requests which are valid but can't be done
+pub(super) fn display(detail: impl Into<String>) -> Reply {
+    error(StatusCode::BAD_REQUEST, "DisplayException", detail)
+}
+
+/// A request to the mock panel
+#[derive(Debug)]
+pub(super) struct MockRequest {
+    pub(super) method: Method,
+    /// The decoded segments of the path, after the API prefix
+    pub(super) path: Vec<String>,
+    query: Vec<(String, String)>,
+    body: Bytes,
+}
+
+impl MockRequest {
+    /// Gets the segments of the path, to match routes against
+    pub(super) fn segments(&self) -> Vec<&str> {
+        self.path.iter().map(String::as_str).collect()
+    }
+
+    pub(super) fn query(&self, key: &str) -> Option<&str> {
+        self.query
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Gets the relationships to include, from the `include` query parameter
+    pub(super) fn includes(&self) -> Vec<&str> {
+        self.query("include")
+            .map(|include| include.split(',').collect())
+            .unwrap_or_default()
+    }
+
+    pub(super) fn body(&self) -> &Bytes {
+        &self.body
+    }
+
+    /// Parses the JSON body of this request, where a missing body is an empty object
+    pub(super) fn json(&self) -> Result<Value, Reply> {
+        if self.body.is_empty() {
+            return Ok(Value::Object(Map::new()));
+        }
+        serde_json::from_slice(&self.body).map_err(|_err| {
+            error(
+                StatusCode::BAD_REQUEST,
+                "BadRequestHttpException",
+                "The JSON data passed in the request appears to be malformed.",
+            )
+        })
+    }
+
+    /// Lists the given attributes as a page of objects of the given kind, after applying the
+    /// filters and sort of this request. `filters` maps each allowed filter to the attribute it
+    /// searches, and `sorts` lists the attributes the list can be sorted by
+    pub(super) fn list(
+        &self,
+        kind: &str,
+        mut items: Vec<Value>,
+        filters: &[(&str, &str)],
+        sorts: &[&str],
+    ) -> Outcome {
+        for (key, value) in &self.query {
+            let Some(filter) = key
+                .strip_prefix("filter[")
+                .and_then(|key| key.strip_suffix(']'))
+            else {
+                continue;
+            };
+            let attributes: Vec<&str> = filters
+                .iter()
+                .filter(|(name, _)| *name == filter)
+                .map(|(_, attribute)| *attribute)
+                .collect();
+            if attributes.is_empty() {
+                return Err(error(
+                    StatusCode::BAD_REQUEST,
+                    "InvalidFilterQuery",
+                    format!("Requested filter(s) `{filter}` are not allowed."),
+                ));
+            }
+            items.retain(|item| {
+                attributes
+                    .iter()
+                    .any(|attribute| matches(attribute_value(item, attribute), value))
+            });
+        }
+        if let Some(sort) = self.query("sort") {
+            let (key, descending) = match sort.strip_prefix('-') {
+                Some(key) => (key, true),
+                None => (sort, false),
+            };
+            if !sorts.contains(&key) {
+                return Err(error(
+                    StatusCode::BAD_REQUEST,
+                    "InvalidSortQuery",
+                    format!("Requested sort(s) `{key}` is not allowed."),
+                ));
+            }
+            items.sort_by(|a, b| compare(&a[key], &b[key]));
+            if descending {
+                items.reverse();
+            }
+        }
+
+        let per_page = self
+            .query("per_page")
+            .and_then(|per_page| per_page.parse().ok())
+            .filter(|&per_page| per_page > 0)
+            .unwrap_or(50usize);
+        let current_page = self
+            .query("page")
+            .and_then(|page| page.parse().ok())
+            .filter(|&page| page > 0)
+            .unwrap_or(1usize);
+        let total = items.len();
+        let data: Vec<Value> = items
+            .into_iter()
+            .skip((current_page - 1) * per_page)
+            .take(per_page)
+            .map(|item| state::object(kind, item))
+            .collect();
+        ok(json!({
+            "object": "list",
+            "meta": {
+                "pagination": {
+                    "total": total,
+                    "count": data.len(),
+                    "per_page": per_page,
+                    "current_page": current_page,
+                    "total_pages": total.div_ceil(per_page).max(1),
+                    "links": {},
+                },
+            },
+            "data": data,
+        }))
+    }
+}
+
+fn attribute_value<'a>(item: &'a Value, attribute: &str) -> &'a Value {
+    item.pointer(&format!("/{}", attribute.replace('.', "/")))
+        .unwrap_or(&Value::Null)
+}
+
+fn matches(value: &Value, filter: &str) -> bool {
+    match value {
+        Value::String(value) => value.to_lowercase().contains(&filter.to_lowercase()),
+        Value::Number(value) => value.to_string() == filter,
+        _ => false,
+    }
+}
+
+fn compare(a: &Value, b: &Value) -> Ordering {
+    match (a, b) {
+        (Value::Number(a), Value::Number(b)) => a
+            .as_f64()
+            .partial_cmp(&b.as_f64())
+            .unwrap_or(Ordering::Equal),
+        (Value::String(a), Value::String(b)) => a.cmp(b),
+        _ => Ordering::Equal,
+    }
+}
+
+/// Validates the fields of a JSON body, collecting a `ValidationException` for each invalid
+/// field like the panel does. Invalid fields are read as their default value, and
+/// [`Validator::finish`] must be called before using them
+#[derive(Debug)]
+pub(super) struct Validator<'a> {
+    body: &'a Value,
+    errors: Vec<Value>,
+}
+
+impl<'a> Validator<'a> {
+    pub(super) fn new(body: &'a Value) -> Self {
+        Validator {
+            body,
+            errors: Vec::new(),
+        }
+    }
+
+    fn get(&self, field: &str) -> Option<&'a Value> {
+        Some(attribute_value(self.body, field)).filter(|value| !value.is_null())
+    }
+
+    /// Gets the value of a field without validating it
+    pub(super) fn value(&self, field: &str) -> Option<&'a Value> {
+        self.get(field)
+    }
+
+    /// Whether the body has a value for the given field
+    pub(super) fn has(&self, field: &str) -> bool {
+        self.get(field).is_some()
+    }
+
+    /// Records that a field is invalid
+    pub(super) fn fail(&mut self, field: &str, rule: &str, detail: impl Into<String>) {
+        self.errors.push(json!({
+            "code": "ValidationException",
+            "status": "422",
+            "detail": detail.into(),
+            "meta": {"source_field": field, "rule": rule},
+        }));
+    }
+
+    fn required(&mut self, field: &str) -> Option<&'a Value> {
+        let value = self.get(field);
+        if value.is_none() {
+            let name = field.replace('_', " ");
+            self.fail(field, "required", format!("The {name} field is required."));
+        }
+        value
+    }
+
+    /// Reads a required string field
+    pub(super) fn string(&mut self, field: &str) -> String {
+        self.required(field)
+            .map(|value| self.as_string(field, value))
+            .unwrap_or_default()
+    }
+
+    /// Reads an optional string field
+    pub(super) fn optional_string(&mut self, field: &str) -> Option<String> {
+        self.get(field).map(|value| self.as_string(field, value))
+    }
+
+    fn as_string(&mut self, field: &str, value: &Value) -> String {
+        if let Value::String(value) = value {
+            value.clone()
+        } else {
+            let name = field.replace('_', " ");
+            self.fail(field, "string", format!("The {name} must be a string."));
+            String::new()
+        }
+    }
+
+    /// Reads a required integer field, which may also be given as a string
+    pub(super) fn integer<T: TryFrom<i64> + Default>(&mut self, field: &str) -> T {
+        self.required(field)
+            .map(|value| self.as_integer(field, value))
+            .unwrap_or_default()
+    }
+
+    /// Reads an optional integer field, which may also be given as a string
+    pub(super) fn optional_integer<T: TryFrom<i64> + Default>(&mut self, field: &str) -> Option<T> {
+        self.get(field).map(|value| self.as_integer(field, value))
+    }
+
+    fn as_integer<T: TryFrom<i64> + Default>(&mut self, field: &str, value: &Value) -> T {
+        let integer = match value {
+            Value::Number(value) => value.as_i64(),
+            Value::String(value) => value.parse().ok(),
+            _ => None,
+        };
+        if let Some(integer) = integer.and_then(|integer| T::try_from(integer).ok()) {
+            integer
+        } else {
+            let name = field.replace('_', " ");
+            self.fail(field, "integer", format!("The {name} must be an integer."));
+            T::default()
+        }
+    }
+
+    /// Reads an optional boolean field
+    pub(super) fn boolean(&mut self, field: &str) -> Option<bool> {
+        match self.get(field)? {
+            Value::Bool(value) => Some(*value),
+            Value::Number(value) => Some(value.as_i64() != Some(0)),
+            _ => {
+                let name = field.replace('_', " ");
+                self.fail(
+                    field,
+                    "boolean",
+                    format!("The {name} field must be true or false."),
+                );
+                None
+            }
+        }
+    }
+
+    /// Reads an optional field holding a list of strings
+    pub(super) fn strings(&mut self, field: &str) -> Vec<String> {
+        match self.get(field) {
+            None => Vec::new(),
+            Some(Value::Array(values)) => values
+                .iter()
+                .map(|value| self.as_string(field, value))
+                .collect(),
+            Some(_) => {
+                let name = field.replace('_', " ");
+                self.fail(field, "array", format!("The {name} must be an array."));
+                Vec::new()
+            }
+        }
+    }
+
+    /// Reads an optional field holding a list of integers
+    pub(super) fn integers<T: TryFrom<i64> + Default>(&mut self, field: &str) -> Vec<T> {
+        match self.get(field) {
+            None => Vec::new(),
+            Some(Value::Array(values)) => values
+                .iter()
+                .map(|value| self.as_integer(field, value))
+                .collect(),
+            Some(_) => {
+                let name = field.replace('_', " ");
+                self.fail(field, "array", format!("The {name} must be an array."));
+                Vec::new()
+            }
+        }
+    }
+
+    /// Replies with the collected errors, if any field is invalid
+    pub(super) fn finish(self) -> Result<(), Reply> {
+        if self.errors.is_empty() {
+            Ok(())
+        } else {
+            Err(Reply::Json(
+                StatusCode::UNPROCESSABLE_ENTITY,
+                json!({"errors": self.errors}),
+            ))
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::MockPanel;
+    use crate::application::structs::{
+        AllocationSettings, BuildUpdate, CreateServerDatabaseRequest, CreateServerRequest,
+        CreateUserRequest, DatabaseInclude, ServerFeatureLimits, ServerFilter, ServerLimits,
+        ServerQuery, ServerSort, SortDirection, UpdateServerDetailsRequest,
+    };
+    use crate::client::{ClientBuilder, PowerSignal, ServerState};
+    use reqwest::StatusCode;
+
+    #[tokio::test]
+    async fn test_mock_client_workflow() {
+        let panel = MockPanel::start().await.unwrap();
+        let client = ClientBuilder::new(panel.url(), "key").build();
+        let id = panel.servers().remove(0);
+        let server = client.get_server(&id);
+
+        server
+            .write_file("config/server.properties", "motd=hi")
+            .await
+            .unwrap();
+        server
+            .create_file_copy("config/server.properties")
+            .await
+            .unwrap();
+        let files = server.list_files("config").await.unwrap();
+        let names: Vec<_> = files.iter().map(|file| file.name.as_str()).collect();
+        assert_eq!(names, ["server copy.properties", "server.properties"]);
+        assert_eq!(
+            server
+                .file_contents_text("config/server.properties")
+                .await
+                .unwrap(),
+            "motd=hi"
+        );
+
+        // commands are only accepted while the server is running
+        assert!(matches!(
+            server.send_command("say hi").await,
+            Err(crate::Error::Api {
+                status: StatusCode::BAD_GATEWAY,
+                ..
+            })
+        ));
+        server.send_power_signal(PowerSignal::Start).await.unwrap();
+        server.send_command("say hi").await.unwrap();
+        assert_eq!(panel.commands(&id), ["say hi"]);
+        assert_eq!(panel.power_signals(&id), [PowerSignal::Start]);
+        assert_eq!(panel.server_state(&id), Some(ServerState::Running));
+
+        assert!(matches!(
+            client.get_server("missing").get_details().await,
+            Err(crate::Error::ResourceNotFound { .. })
+        ));
+        panel.fail_next(
+            StatusCode::TOO_MANY_REQUESTS,
+            "TooManyRequestsHttpException",
+            "",
+        );
+        assert!(matches!(
+            server.get_details().await,
+            Err(crate::Error::RateLimit { .. })
+        ));
+        assert_eq!(server.get_details().await.unwrap().name, "Test Server");
+    }
+
+    #[tokio::test]
+    async fn test_mock_application_workflow() {
+        let panel = MockPanel::start().await.unwrap();
+        let client = crate::application::ClientBuilder::new(panel.url(), "key").build();
+        let nest = client.list_nests().await.unwrap().remove(0);
+        let egg = client.list_eggs(nest.id, None).await.unwrap().remove(0);
+        let node = client.list_nodes().await.unwrap().remove(0);
+        let allocations = client.list_node_allocations(node.id).await.unwrap();
+        let free = allocations.iter().find(|a| !a.assigned).unwrap().id;
+        let request = |allocation, environment: &[(&str, &str)]| CreateServerRequest {
+            name: "Lobby".to_owned(),
+            user: 1,
+            egg: egg.id,
+            docker_image: "ghcr.io/pterodactyl/yolks:java_21".to_owned(),
+            startup: "java -jar {{SERVER_JARFILE}}".to_owned(),
+            environment: environment
+                .iter()
+                .map(|(key, value)| ((*key).to_owned(), (*value).to_owned()))
+                .collect(),
+            limits: ServerLimits {
+                memory: 2048,
+                swap: 0,
+                disk: 10240,
+                io: 500,
+                cpu: 200,
+                threads: None,
+                oom_disabled: None,
+            },
+            feature_limits: ServerFeatureLimits {
+                databases: 1,
+                allocations: 1,
+                backups: 1,
+            },
+            allocation: AllocationSettings {
+                default: allocation,
+            },
+        };
+
+        let Err(crate::Error::Validation(errors)) = client
+            .create_server(request(999, &[("VANILLA_VERSION", "1.21")]))
+            .await
+        else {
+            panic!("creating a server with invalid fields should fail validation");
+        };
+        let fields: Vec<_> = errors.fields().collect();
+        assert_eq!(fields, ["allocation.default", "environment.SERVER_JARFILE"]);
+
+        let created = client
+            .create_server(request(
+                free,
+                &[("SERVER_JARFILE", "paper.jar"), ("VANILLA_VERSION", "1.21")],
+            ))
+            .await
+            .unwrap()
+            .attributes;
+        assert_eq!(created.allocation, free);
+        assert_eq!(panel.servers().len(), 2);
+
+        let query = ServerQuery::new()
+            .with_sort(ServerSort::Id, SortDirection::Descending)
+            .with_per_page(1);
+        let page = client.list_servers_filtered(&query).await.unwrap();
+        assert_eq!(page.data[0].name, "Lobby");
+        assert_eq!(page.next_page(), Some(2));
+        let servers = client.list_servers_filtered_all(&query).await.unwrap();
+        let names: Vec<_> = servers.iter().map(|server| server.name.as_str()).collect();
+        assert_eq!(names, ["Lobby", "Test Server"]);
+
+        let query = ServerQuery::new().with_filter(ServerFilter::Name, "test");
+        let page = client.list_servers_filtered(&query).await.unwrap();
+        assert_eq!(page.data.len(), 1);
+        assert_eq!(page.data[0].name, "Test Server");
+
+        client.suspend_server(created.id).await.unwrap();
+        let server = ClientBuilder::new(panel.url(), "key").build();
+        assert!(matches!(
+            server.get_server(&created.identifier).get_resources().await,
+            Err(crate::Error::Api {
+                status: StatusCode::CONFLICT,
+                ..
+            })
+        ));
+        assert!(matches!(
+            client.delete_allocation(free).await,
+            Err(crate::Error::Api {
+                status: StatusCode::BAD_REQUEST,
+                ..
+            })
+        ));
+        client.delete_server(created.id).await.unwrap();
+        client.delete_allocation(free).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_mock_server_details() {
+        let panel = MockPanel::start().await.unwrap();
+        let client = crate::application::ClientBuilder::new(panel.url(), "key").build();
+        let server = client.list_servers().await.unwrap().remove(0);
+        let details = client
+            .update_server_details(
+                server.id,
+                UpdateServerDetailsRequest {
+                    name: "Survival".to_owned(),
+                    user: server.user,
+                    external_id: Some("order/1234".to_owned()),
+                    description: Some("Billed monthly".to_owned()),
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(details.name, "Survival");
+        let found = client
+            .get_server_by_external_id("order/1234")
+            .await
+            .unwrap();
+        assert_eq!(found.id, server.id);
+
+        let Err(crate::Error::Validation(errors)) =
+            client.transfer_server_ownership(server.id, 999).await
+        else {
+            panic!("transferring a server to an unknown user should fail validation");
+        };
+        assert_eq!(errors.fields().collect::<Vec<_>>(), ["user"]);
+        let user = client
+            .create_user(CreateUserRequest {
+                email: "player@example.com".to_owned(),
+                username: "player".to_owned(),
+                first_name: "Example".to_owned(),
+                last_name: "Player".to_owned(),
+                external_id: None,
+                password: None,
+                root_admin: None,
+                language: None,
+            })
+            .await
+            .unwrap();
+        let transferred = client
+            .transfer_server_ownership(server.id, user.id)
+            .await
+            .unwrap();
+        assert_eq!(transferred.user, user.id);
+        assert_eq!(transferred.name, "Survival");
+        assert_eq!(transferred.external_id.as_deref(), Some("order/1234"));
+        assert_eq!(transferred.description, "Billed monthly");
+    }
+
+    #[tokio::test]
+    async fn test_mock_server_databases() {
+        let panel = MockPanel::start().await.unwrap();
+        let client = crate::application::ClientBuilder::new(panel.url(), "key").build();
+        let server = client.list_servers().await.unwrap().remove(0);
+        let request = |host| CreateServerDatabaseRequest {
+            database: "players".to_owned(),
+            remote: "%".to_owned(),
+            host,
+        };
+
+        let Err(crate::Error::Validation(errors)) =
+            client.create_server_database(server.id, request(999)).await
+        else {
+            panic!("creating a database on an unknown host should fail validation");
+        };
+        assert_eq!(errors.fields().collect::<Vec<_>>(), ["host"]);
+        let created = client
+            .create_server_database(server.id, request(1))
+            .await
+            .unwrap();
+        assert_eq!(created.database, format!("s{}_players", server.id));
+        assert!(created.relationships.is_none());
+
+        let include = [DatabaseInclude::Password, DatabaseInclude::Host];
+        let database = client
+            .get_server_database(server.id, created.id, &include)
+            .await
+            .unwrap();
+        let relationships = database.relationships.unwrap();
+        let password = relationships.password.unwrap();
+        assert_eq!(relationships.host.unwrap().port, 3306);
+        client
+            .reset_server_database_password(server.id, created.id)
+            .await
+            .unwrap();
+        let databases = client
+            .list_server_databases(server.id, &include)
+            .await
+            .unwrap();
+        assert_eq!(databases.len(), 1);
+        let relationships = databases[0].relationships.as_ref().unwrap();
+        assert_ne!(relationships.password.as_ref(), Some(&password));
+
+        let client_api = ClientBuilder::new(panel.url(), "key").build();
+        let identifier = &panel.servers()[0];
+        let listed = client_api
+            .get_server(identifier)
+            .list_databases()
+            .await
+            .unwrap();
+        assert_eq!(listed[0].name, created.database);
+
+        client
+            .delete_server_database(server.id, created.id)
+            .await
+            .unwrap();
+        assert!(client
+            .list_server_databases(server.id, &[])
+            .await
+            .unwrap()
+            .is_empty());
+        client.reinstall_server(server.id).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_mock_build_update() {
+        let panel = MockPanel::start().await.unwrap();
+        let client = crate::application::ClientBuilder::new(panel.url(), "key").build();
+        let server = client.list_servers().await.unwrap().remove(0);
+        let allocations = client.list_node_allocations(server.node).await.unwrap();
+        let free: Vec<u32> = allocations
+            .iter()
+            .filter(|allocation| !allocation.assigned)
+            .map(|allocation| allocation.id)
+            .collect();
+
+        let upgrade = BuildUpdate::new()
+            .with_memory(4096)
+            .with_backups(5)
+            .with_threads("0-3,5")
+            .with_added_allocation(free[0])
+            .with_added_allocation(free[1]);
+        let upgraded = client.apply_build_update(server.id, upgrade).await.unwrap();
+        assert_eq!(upgraded.limits.memory, 4096);
+        assert_eq!(upgraded.limits.disk, server.limits.disk);
+        assert_eq!(upgraded.limits.threads.as_deref(), Some("0-3,5"));
+        assert_eq!(upgraded.feature_limits.backups, 5);
+        assert_eq!(
+            upgraded.feature_limits.databases,
+            server.feature_limits.databases
+        );
+        assert_eq!(upgraded.allocation, server.allocation);
+
+        let remove_primary = BuildUpdate::new().with_removed_allocation(server.allocation);
+        assert!(matches!(
+            client.apply_build_update(server.id, remove_primary).await,
+            Err(crate::Error::Api {
+                status: StatusCode::BAD_REQUEST,
+                ..
+            })
+        ));
+        let moved = BuildUpdate::new()
+            .with_allocation(free[0])
+            .with_removed_allocation(server.allocation)
+            .with_removed_allocation(free[1]);
+        let moved = client.apply_build_update(server.id, moved).await.unwrap();
+        assert_eq!(moved.allocation, free[0]);
+        assert_eq!(moved.limits.memory, 4096);
+        assert_eq!(moved.limits.threads.as_deref(), Some("0-3,5"));
+        let assigned: Vec<u32> = client
+            .list_node_allocations(server.node)
+            .await
+            .unwrap()
+            .iter()
+            .filter(|allocation| allocation.assigned)
+            .map(|allocation| allocation.id)
+            .collect();
+        assert_eq!(assigned, [free[0]]);
+    }
+}
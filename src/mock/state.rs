@@ -0,0 +1,1198 @@
+//! The resources kept in memory by the mock panel, and their JSON representations
+
+use super::{error, Reply};
+use crate::client::{PowerSignal, ServerState};
+use bytes::Bytes;
+use reqwest::StatusCode;
+use serde_json::{json, Value};
+use std::collections::{BTreeMap, VecDeque};
+use std::net::SocketAddr;
+use uuid::Uuid;
+
+/// The time reported for every timestamp of the mock panel
+pub(super) const TIMESTAMP: &str = "2024-01-01T00:00:00+00:00";
+
+/// The password of the account of the mock panel
+pub(super) const PASSWORD: &str = "password";
+
+/// The ID of the only database host of the mock panel, which every database is on
+pub(super) const DATABASE_HOST: u32 = 1;
+
+/// Wraps attributes in a Pterodactyl object
+pub(super) fn object(kind: &str, attributes: Value) -> Value {
+    json!({"object": kind, "attributes": attributes})
+}
+
+/// Wraps objects in a Pterodactyl list, without pagination metadata
+pub(super) fn list(items: Vec<Value>) -> Value {
+    json!({"object": "list", "data": items})
+}
+
+/// Generates the UUID of the resource with the given ID. The IDs are spread over the UUID, so
+/// that the short identifiers of servers differ
+pub(super) fn uuid(id: u32) -> Uuid {
+    let bits = u128::from(id).wrapping_mul(0x9e37_79b9_7f4a_7c15_f39c_c060_5ced_c835);
+    Uuid::from_u128(bits & !(0xf << 76) | (0x4 << 76))
+}
+
+#[derive(Debug)]
+pub(super) struct PanelState {
+    next_id: u32,
+    pub(super) account: Account,
+    pub(super) locations: Vec<Location>,
+    pub(super) nodes: Vec<Node>,
+    pub(super) allocations: Vec<Allocation>,
+    pub(super) nests: Vec<Nest>,
+    pub(super) eggs: Vec<Egg>,
+    pub(super) servers: Vec<Server>,
+    pub(super) users: Vec<User>,
+    /// Errors to reply with instead of handling the next requests
+    pub(super) failures: VecDeque<Reply>,
+    /// The method and path of every request received
+    pub(super) requests: Vec<String>,
+    #[cfg(feature = "websocket")]
+    pub(super) wings: super::wings::Wings,
+}
+
+impl PanelState {
+    /// Creates a panel with a node, a nest with an egg, and a server named `Test Server`. The node
+    /// is served at the given address, like the panel itself
+    pub(super) fn seeded(addr: SocketAddr) -> Self {
+        let mut state = PanelState {
+            next_id: 1,
+            account: Account {
+                email: "admin@example.com".to_owned(),
+                password: PASSWORD.to_owned(),
+                two_factor: false,
+                api_keys: Vec::new(),
+            },
+            locations: vec![Location {
+                id: 1,
+                short: "local".to_owned(),
+                long: Some("Mock Location".to_owned()),
+            }],
+            nodes: Vec::new(),
+            allocations: Vec::new(),
+            nests: Vec::new(),
+            eggs: Vec::new(),
+            servers: Vec::new(),
+            users: vec![User {
+                id: 1,
+                external_id: None,
+                username: "admin".to_owned(),
+                email: "admin@example.com".to_owned(),
+                first_name: "Mock".to_owned(),
+                last_name: "Admin".to_owned(),
+                language: "en".to_owned(),
+                root_admin: true,
+            }],
+            failures: VecDeque::new(),
+            requests: Vec::new(),
+            #[cfg(feature = "websocket")]
+            wings: super::wings::Wings::default(),
+        };
+        let node = state.next_id();
+        state.nodes.push(Node {
+            id: node,
+            name: "Mock Node".to_owned(),
+            description: None,
+            location_id: 1,
+            fqdn: addr.ip().to_string(),
+            scheme: "http".to_owned(),
+            public: true,
+            behind_proxy: false,
+            maintenance_mode: false,
+            memory: 16384,
+            memory_overallocate: 0,
+            disk: 102_400,
+            disk_overallocate: 0,
+            upload_size: 100,
+            daemon_listen: addr.port(),
+            daemon_sftp: 2022,
+            daemon_base: "/var/lib/pterodactyl/volumes".to_owned(),
+        });
+        for port in 25565..25575 {
+            state.add_allocation(node, "127.0.0.1", port, None);
+        }
+        let nest = state.next_id();
+        state.nests.push(Nest {
+            id: nest,
+            name: "Minecraft".to_owned(),
+            description: "Minecraft - the classic game from Mojang.".to_owned(),
+        });
+        let egg = state.next_id();
+        state.eggs.push(Egg {
+            id: egg,
+            nest,
+            name: "Vanilla Minecraft".to_owned(),
+            description: "Minecraft is a game about placing blocks and going on adventures."
+                .to_owned(),
+            docker_images: vec![
+                (
+                    "Java 17".to_owned(),
+                    "ghcr.io/pterodactyl/yolks:java_17".to_owned(),
+                ),
+                (
+                    "Java 21".to_owned(),
+                    "ghcr.io/pterodactyl/yolks:java_21".to_owned(),
+                ),
+            ],
+            startup: "java -Xms128M -Xmx{{SERVER_MEMORY}}M -jar {{SERVER_JARFILE}}".to_owned(),
+            variables: vec![
+                EggVariable {
+                    name: "Server Jar File".to_owned(),
+                    description: "The name of the server jarfile to run the server with."
+                        .to_owned(),
+                    env_variable: "SERVER_JARFILE".to_owned(),
+                    default_value: "server.jar".to_owned(),
+                    user_editable: true,
+                    rules: "required|regex:/^([\\w\\d._-]+)(\\.jar)$/".to_owned(),
+                },
+                EggVariable {
+                    name: "Server Version".to_owned(),
+                    description: "The version of Minecraft to install.".to_owned(),
+                    env_variable: "VANILLA_VERSION".to_owned(),
+                    default_value: "latest".to_owned(),
+                    user_editable: false,
+                    rules: "required|string|between:3,15".to_owned(),
+                },
+            ],
+        });
+        state.add_server("Test Server");
+        state
+    }
+
+    pub(super) fn next_id(&mut self) -> u32 {
+        let id = self.next_id;
+        self.next_id += 1;
+        id
+    }
+
+    pub(super) fn add_allocation(
+        &mut self,
+        node: u32,
+        ip: &str,
+        port: u16,
+        alias: Option<String>,
+    ) -> u32 {
+        let id = self.next_id();
+        self.allocations.push(Allocation {
+            id,
+            node,
+            ip: ip.to_owned(),
+            alias,
+            port,
+            notes: None,
+            server: None,
+        });
+        id
+    }
+
+    /// Adds a server with the first egg on the first free allocation, and returns its identifier
+    pub(super) fn add_server(&mut self, name: &str) -> String {
+        let allocation = if let Some(allocation) = self.free_allocation(None) {
+            allocation
+        } else {
+            let node = self.nodes[0].id;
+            let port = self
+                .allocations
+                .iter()
+                .map(|a| a.port)
+                .max()
+                .unwrap_or(25564)
+                + 1;
+            self.add_allocation(node, "127.0.0.1", port, None)
+        };
+        let id = self.next_id();
+        let mut server = Server::new(id, name, &self.eggs[0]);
+        self.assign_allocation(&mut server, allocation);
+        let identifier = server.identifier.clone();
+        self.servers.push(server);
+        identifier
+    }
+
+    /// Finds an allocation which isn't assigned to a server, optionally on the given node
+    /// Creates a database with the given name on the server at the given index, and returns its
+    /// index. Fails if the server reached its database limit, or already has the database
+    pub(super) fn add_database(
+        &mut self,
+        index: usize,
+        name: &str,
+        remote: String,
+    ) -> Result<usize, Reply> {
+        let id = self.next_id();
+        let password = uuid(id).simple().to_string();
+        let server = &mut self.servers[index];
+        if server.databases.len() >= server.feature_limits.databases as usize {
+            return Err(error(
+                StatusCode::BAD_REQUEST,
+                "TooManyDatabasesException",
+                "Cannot create additional databases on this server: limit has been reached.",
+            ));
+        }
+        let name = format!("s{}_{name}", server.id);
+        if server
+            .databases
+            .iter()
+            .any(|database| database.name == name)
+        {
+            return Err(error(
+                StatusCode::BAD_REQUEST,
+                "DuplicateDatabaseNameException",
+                "A database with that name already exists for this server.",
+            ));
+        }
+        server.databases.push(Database {
+            id: password[..8].to_owned(),
+            internal_id: id,
+            name,
+            username: format!("u{}_{}", server.id, &password[8..18]),
+            remote,
+            password: password[18..].to_owned(),
+        });
+        Ok(server.databases.len() - 1)
+    }
+
+    pub(super) fn free_allocation(&self, node: Option<u32>) -> Option<u32> {
+        self.allocations
+            .iter()
+            .find(|allocation| {
+                allocation.server.is_none() && node.is_none_or(|node| allocation.node == node)
+            })
+            .map(|allocation| allocation.id)
+    }
+
+    /// Makes the given allocation the primary allocation of a server, on the node of the
+    /// allocation
+    pub(super) fn assign_allocation(&mut self, server: &mut Server, allocation: u32) {
+        if let Some(allocation) = self.allocation_mut(allocation) {
+            allocation.server = Some(server.id);
+            server.node = allocation.node;
+            server.allocation = allocation.id;
+        }
+    }
+
+    /// Removes the server at the given index, and frees its allocations
+    pub(super) fn remove_server(&mut self, index: usize) {
+        let server = self.servers.remove(index);
+        for allocation in &mut self.allocations {
+            if allocation.server == Some(server.id) {
+                allocation.server = None;
+                allocation.notes = None;
+            }
+        }
+    }
+
+    pub(super) fn node(&self, id: u32) -> Option<&Node> {
+        self.nodes.iter().find(|node| node.id == id)
+    }
+
+    pub(super) fn allocation(&self, id: u32) -> Option<&Allocation> {
+        self.allocations
+            .iter()
+            .find(|allocation| allocation.id == id)
+    }
+
+    pub(super) fn allocation_mut(&mut self, id: u32) -> Option<&mut Allocation> {
+        self.allocations
+            .iter_mut()
+            .find(|allocation| allocation.id == id)
+    }
+
+    pub(super) fn egg(&self, id: u32) -> Option<&Egg> {
+        self.eggs.iter().find(|egg| egg.id == id)
+    }
+
+    /// Finds a server by its identifier, UUID or ID
+    pub(super) fn server_index(&self, id: &str) -> Option<usize> {
+        self.servers.iter().position(|server| {
+            server.identifier == id || server.uuid.to_string() == id || server.id.to_string() == id
+        })
+    }
+
+    pub(super) fn server(&self, id: &str) -> Option<&Server> {
+        self.server_index(id).map(|index| &self.servers[index])
+    }
+
+    pub(super) fn server_mut(&mut self, id: &str) -> Option<&mut Server> {
+        self.server_index(id).map(|index| &mut self.servers[index])
+    }
+
+    /// Renders a server as returned by the client API
+    pub(super) fn client_server(&self, server: &Server) -> Value {
+        let node = self.node(server.node);
+        let allocations = self
+            .allocations
+            .iter()
+            .filter(|allocation| allocation.server == Some(server.id))
+            .map(|allocation| object("allocation", allocation.client_json(server.allocation)))
+            .collect();
+        json!({
+            "server_owner": true,
+            "identifier": server.identifier,
+            "internal_id": server.id,
+            "uuid": server.uuid,
+            "name": server.name,
+            "node": node.map_or("", |node| &node.name),
+            "is_node_under_maintenance": node.is_some_and(|node| node.maintenance_mode),
+            "sftp_details": {
+                "ip": node.map_or("", |node| &node.fqdn),
+                "port": node.map_or(2022, |node| node.daemon_sftp),
+            },
+            "description": server.description,
+            "limits": {
+                "memory": server.limits.memory,
+                "swap": server.limits.swap,
+                "disk": server.limits.disk,
+                "io": server.limits.io,
+                "cpu": server.limits.cpu,
+                "threads": server.limits.threads,
+                "oom_disabled": server.limits.oom_disabled,
+                "oom_killer": !server.limits.oom_disabled,
+            },
+            "invocation": server.invocation(),
+            "docker_image": server.image,
+            "egg_features": ["eula", "java_version"],
+            "feature_limits": server.feature_limits.json(),
+            "status": server.status(),
+            "is_suspended": server.suspended,
+            "is_installing": !server.installed,
+            "is_transferring": false,
+            "relationships": {
+                "allocations": list(allocations),
+                "variables": list(self.variables(server)),
+            },
+        })
+    }
+
+    /// Renders the startup variables of a server as returned by the client API
+    pub(super) fn variables(&self, server: &Server) -> Vec<Value> {
+        let Some(egg) = self.egg(server.egg) else {
+            return Vec::new();
+        };
+        egg.variables
+            .iter()
+            .map(|variable| object("egg_variable", variable.client_json(server)))
+            .collect()
+    }
+
+    /// Renders a server as returned by the application API
+    pub(super) fn application_server(&self, server: &Server) -> Value {
+        let nest = self.egg(server.egg).map_or(0, |egg| egg.nest);
+        json!({
+            "id": server.id,
+            "external_id": server.external_id,
+            "uuid": server.uuid,
+            "identifier": server.identifier,
+            "name": server.name,
+            "description": server.description.clone().unwrap_or_default(),
+            "status": server.status(),
+            "suspended": server.suspended,
+            "limits": {
+                "memory": server.limits.memory,
+                "swap": server.limits.swap,
+                "disk": server.limits.disk,
+                "io": server.limits.io,
+                "cpu": server.limits.cpu,
+                "threads": server.limits.threads,
+                "oom_disabled": server.limits.oom_disabled,
+            },
+            "feature_limits": server.feature_limits.json(),
+            "user": server.user,
+            "node": server.node,
+            "allocation": server.allocation,
+            "nest": nest,
+            "egg": server.egg,
+            "container": {
+                "startup_command": server.startup,
+                "image": server.image,
+                "installed": u8::from(server.installed),
+                "environment": server.environment,
+            },
+            "updated_at": TIMESTAMP,
+            "created_at": TIMESTAMP,
+        })
+    }
+
+    /// Renders a location, including its nodes and their servers if requested
+    pub(super) fn location(&self, location: &Location, include: &[&str]) -> Value {
+        let mut json = json!({
+            "id": location.id,
+            "short": location.short,
+            "long": location.long,
+            "created_at": TIMESTAMP,
+            "updated_at": TIMESTAMP,
+        });
+        let nodes = || {
+            self.nodes
+                .iter()
+                .filter(|node| node.location_id == location.id)
+        };
+        let mut relationships = serde_json::Map::new();
+        if include.contains(&"nodes") {
+            let nodes = nodes().map(|node| object("node", node.json())).collect();
+            relationships.insert("nodes".to_owned(), list(nodes));
+        }
+        if include.contains(&"servers") {
+            let servers = self
+                .servers
+                .iter()
+                .filter(|server| nodes().any(|node| node.id == server.node))
+                .map(|server| object("server", self.application_server(server)))
+                .collect();
+            relationships.insert("servers".to_owned(), list(servers));
+        }
+        if !relationships.is_empty() {
+            json["relationships"] = Value::Object(relationships);
+        }
+        json
+    }
+
+    /// Renders a user of the application API, including its servers if requested
+    pub(super) fn application_user(&self, user: &User, include: &[&str]) -> Value {
+        let mut json = json!({
+            "id": user.id,
+            "external_id": user.external_id,
+            "uuid": uuid(user.id),
+            "username": user.username,
+            "email": user.email,
+            "first_name": user.first_name,
+            "last_name": user.last_name,
+            "language": user.language,
+            "root_admin": user.root_admin,
+            "2fa": false,
+            "created_at": TIMESTAMP,
+            "updated_at": TIMESTAMP,
+        });
+        if include.contains(&"servers") {
+            let servers = self
+                .servers
+                .iter()
+                .filter(|server| server.user == user.id)
+                .map(|server| object("server", self.application_server(server)))
+                .collect();
+            json["relationships"] = json!({"servers": list(servers)});
+        }
+        json
+    }
+}
+
+#[derive(Debug)]
+pub(super) struct User {
+    pub(super) id: u32,
+    pub(super) external_id: Option<String>,
+    pub(super) username: String,
+    pub(super) email: String,
+    pub(super) first_name: String,
+    pub(super) last_name: String,
+    pub(super) language: String,
+    pub(super) root_admin: bool,
+}
+
+#[derive(Debug)]
+pub(super) struct Account {
+    pub(super) email: String,
+    pub(super) password: String,
+    pub(super) two_factor: bool,
+    pub(super) api_keys: Vec<ApiKey>,
+}
+
+impl Account {
+    pub(super) fn json(&self) -> Value {
+        json!({
+            "id": 1,
+            "admin": true,
+            "username": "admin",
+            "email": self.email,
+            "first_name": "Mock",
+            "last_name": "Admin",
+            "language": "en",
+        })
+    }
+}
+
+#[derive(Debug)]
+pub(super) struct ApiKey {
+    pub(super) identifier: String,
+    pub(super) description: String,
+    pub(super) allowed_ips: Vec<String>,
+}
+
+impl ApiKey {
+    pub(super) fn json(&self) -> Value {
+        json!({
+            "identifier": self.identifier,
+            "description": self.description,
+            "allowed_ips": self.allowed_ips,
+            "last_used_at": null,
+            "created_at": TIMESTAMP,
+        })
+    }
+}
+
+#[derive(Debug)]
+pub(super) struct Location {
+    pub(super) id: u32,
+    pub(super) short: String,
+    pub(super) long: Option<String>,
+}
+
+#[derive(Debug)]
+pub(super) struct Node {
+    pub(super) id: u32,
+    pub(super) name: String,
+    pub(super) description: Option<String>,
+    pub(super) location_id: u32,
+    pub(super) fqdn: String,
+    pub(super) scheme: String,
+    pub(super) public: bool,
+    pub(super) behind_proxy: bool,
+    pub(super) maintenance_mode: bool,
+    pub(super) memory: u32,
+    pub(super) memory_overallocate: i32,
+    pub(super) disk: u32,
+    pub(super) disk_overallocate: i32,
+    pub(super) upload_size: u32,
+    pub(super) daemon_listen: u16,
+    pub(super) daemon_sftp: u16,
+    pub(super) daemon_base: String,
+}
+
+impl Node {
+    pub(super) fn json(&self) -> Value {
+        json!({
+            "id": self.id,
+            "uuid": uuid(self.id),
+            "public": self.public,
+            "name": self.name,
+            "description": self.description,
+            "location_id": self.location_id,
+            "fqdn": self.fqdn,
+            "scheme": self.scheme,
+            "behind_proxy": self.behind_proxy,
+            "maintenance_mode": self.maintenance_mode,
+            "memory": self.memory,
+            "memory_overallocate": self.memory_overallocate,
+            "disk": self.disk,
+            "disk_overallocate": self.disk_overallocate,
+            "upload_size": self.upload_size,
+            "daemon_listen": self.daemon_listen,
+            "daemon_sftp": self.daemon_sftp,
+            "daemon_base": self.daemon_base,
+            "created_at": TIMESTAMP,
+            "updated_at": TIMESTAMP,
+        })
+    }
+}
+
+#[derive(Debug)]
+pub(super) struct Allocation {
+    pub(super) id: u32,
+    pub(super) node: u32,
+    pub(super) ip: String,
+    pub(super) alias: Option<String>,
+    pub(super) port: u16,
+    pub(super) notes: Option<String>,
+    pub(super) server: Option<u32>,
+}
+
+impl Allocation {
+    /// Renders this allocation as returned by the client API, for a server with the given
+    /// primary allocation
+    pub(super) fn client_json(&self, primary: u32) -> Value {
+        json!({
+            "id": self.id,
+            "ip": self.ip,
+            "ip_alias": self.alias,
+            "port": self.port,
+            "notes": self.notes,
+            "is_default": self.id == primary,
+        })
+    }
+
+    /// Renders this allocation as returned by the application API
+    pub(super) fn application_json(&self) -> Value {
+        json!({
+            "id": self.id,
+            "node": self.node,
+            "ip": self.ip,
+            "ip_alias": self.alias,
+            "alias": self.alias,
+            "port": self.port,
+            "notes": self.notes,
+            "server_id": self.server,
+            "assigned": self.server.is_some(),
+        })
+    }
+}
+
+#[derive(Debug)]
+pub(super) struct Nest {
+    pub(super) id: u32,
+    pub(super) name: String,
+    pub(super) description: String,
+}
+
+impl Nest {
+    pub(super) fn json(&self) -> Value {
+        json!({
+            "id": self.id,
+            "uuid": uuid(self.id),
+            "author": "support@pterodactyl.io",
+            "name": self.name,
+            "description": self.description,
+            "created_at": TIMESTAMP,
+            "updated_at": TIMESTAMP,
+        })
+    }
+}
+
+#[derive(Debug)]
+pub(super) struct Egg {
+    pub(super) id: u32,
+    pub(super) nest: u32,
+    pub(super) name: String,
+    pub(super) description: String,
+    /// The docker images of this egg by their display name, where the first one is the default
+    pub(super) docker_images: Vec<(String, String)>,
+    pub(super) startup: String,
+    pub(super) variables: Vec<EggVariable>,
+}
+
+impl Egg {
+    pub(super) fn docker_image(&self) -> &str {
+        self.docker_images
+            .first()
+            .map_or("", |(_, image)| image.as_str())
+    }
+
+    pub(super) fn docker_images(&self) -> BTreeMap<&str, &str> {
+        self.docker_images
+            .iter()
+            .map(|(name, image)| (name.as_str(), image.as_str()))
+            .collect()
+    }
+
+    /// Renders this egg, including the given relationships
+    pub(super) fn json(&self, include: &[&str]) -> Value {
+        let mut egg = json!({
+            "id": self.id,
+            "uuid": uuid(self.id),
+            "name": self.name,
+            "nest": self.nest,
+            "author": "support@pterodactyl.io",
+            "description": self.description,
+            "docker_image": self.docker_image(),
+            "docker_images": self.docker_images(),
+            "config": {
+                "files": {},
+                "startup": {"done": ")! For help, type "},
+                "stop": "stop",
+                "logs": {},
+                "file_denylist": [],
+                "extends": null,
+            },
+            "startup": self.startup,
+            "script": {
+                "privileged": true,
+                "install": "#!/bin/ash\n",
+                "entry": "ash",
+                "container": "ghcr.io/pterodactyl/installers:alpine",
+                "extends": null,
+            },
+            "created_at": TIMESTAMP,
+            "updated_at": TIMESTAMP,
+        });
+        if include.contains(&"variables") {
+            let variables = self
+                .variables
+                .iter()
+                .zip(1..)
+                .map(|(variable, id)| object("egg_variable", variable.json(id, self.id)))
+                .collect();
+            egg["relationships"] = json!({"variables": list(variables)});
+        }
+        egg
+    }
+}
+
+#[derive(Debug)]
+pub(super) struct EggVariable {
+    pub(super) name: String,
+    pub(super) description: String,
+    pub(super) env_variable: String,
+    pub(super) default_value: String,
+    pub(super) user_editable: bool,
+    pub(super) rules: String,
+}
+
+impl EggVariable {
+    /// Renders this variable as returned by the application API
+    fn json(&self, id: u32, egg: u32) -> Value {
+        json!({
+            "id": id,
+            "egg_id": egg,
+            "name": self.name,
+            "description": self.description,
+            "env_variable": self.env_variable,
+            "default_value": self.default_value,
+            "user_viewable": true,
+            "user_editable": self.user_editable,
+            "rules": self.rules,
+            "created_at": TIMESTAMP,
+            "updated_at": TIMESTAMP,
+        })
+    }
+
+    /// Renders this variable as returned by the client API, with the value of the given server
+    pub(super) fn client_json(&self, server: &Server) -> Value {
+        json!({
+            "name": self.name,
+            "description": self.description,
+            "env_variable": self.env_variable,
+            "default_value": self.default_value,
+            "server_value": server
+                .environment
+                .get(&self.env_variable)
+                .unwrap_or(&self.default_value),
+            "is_editable": self.user_editable,
+            "rules": self.rules,
+        })
+    }
+}
+
+#[derive(Debug)]
+pub(super) struct Limits {
+    pub(super) memory: u32,
+    pub(super) swap: u32,
+    pub(super) disk: u32,
+    pub(super) io: u32,
+    pub(super) cpu: u32,
+    pub(super) threads: Option<String>,
+    pub(super) oom_disabled: bool,
+}
+
+#[derive(Debug)]
+pub(super) struct FeatureLimits {
+    pub(super) databases: u32,
+    pub(super) allocations: u32,
+    pub(super) backups: u32,
+}
+
+impl FeatureLimits {
+    fn json(&self) -> Value {
+        json!({
+            "databases": self.databases,
+            "allocations": self.allocations,
+            "backups": self.backups,
+        })
+    }
+}
+
+#[derive(Debug)]
+pub(super) struct Server {
+    pub(super) id: u32,
+    pub(super) uuid: Uuid,
+    pub(super) identifier: String,
+    pub(super) external_id: Option<String>,
+    pub(super) name: String,
+    pub(super) description: Option<String>,
+    pub(super) user: u32,
+    pub(super) node: u32,
+    pub(super) allocation: u32,
+    pub(super) egg: u32,
+    pub(super) suspended: bool,
+    pub(super) installed: bool,
+    pub(super) limits: Limits,
+    pub(super) feature_limits: FeatureLimits,
+    pub(super) startup: String,
+    pub(super) image: String,
+    pub(super) environment: BTreeMap<String, String>,
+    pub(super) state: ServerState,
+    pub(super) commands: Vec<String>,
+    pub(super) signals: Vec<PowerSignal>,
+    /// The websocket tokens issued for this server
+    pub(super) tokens: Vec<String>,
+    /// The files of this server by their absolute path, where directories have no contents
+    pub(super) files: BTreeMap<String, Option<Bytes>>,
+    pub(super) backups: Vec<Backup>,
+    pub(super) schedules: Vec<Schedule>,
+    pub(super) subusers: Vec<Subuser>,
+    pub(super) databases: Vec<Database>,
+}
+
+impl Server {
+    pub(super) fn new(id: u32, name: &str, egg: &Egg) -> Self {
+        let uuid = uuid(id);
+        Server {
+            id,
+            uuid,
+            identifier: uuid.to_string()[..8].to_owned(),
+            external_id: None,
+            name: name.to_owned(),
+            description: None,
+            user: 1,
+            node: 0,
+            allocation: 0,
+            egg: egg.id,
+            suspended: false,
+            installed: true,
+            limits: Limits {
+                memory: 1024,
+                swap: 0,
+                disk: 5120,
+                io: 500,
+                cpu: 100,
+                threads: None,
+                oom_disabled: true,
+            },
+            feature_limits: FeatureLimits {
+                databases: 2,
+                allocations: 2,
+                backups: 2,
+            },
+            startup: egg.startup.clone(),
+            image: egg.docker_image().to_owned(),
+            environment: egg
+                .variables
+                .iter()
+                .map(|variable| {
+                    (
+                        variable.env_variable.clone(),
+                        variable.default_value.clone(),
+                    )
+                })
+                .collect(),
+            state: ServerState::Offline,
+            commands: Vec::new(),
+            signals: Vec::new(),
+            tokens: Vec::new(),
+            files: BTreeMap::new(),
+            backups: Vec::new(),
+            schedules: Vec::new(),
+            subusers: Vec::new(),
+            databases: Vec::new(),
+        }
+    }
+
+    pub(super) fn status(&self) -> Option<&'static str> {
+        if self.suspended {
+            Some("suspended")
+        } else if !self.installed {
+            Some("installing")
+        } else {
+            None
+        }
+    }
+
+    /// Records a power signal, and moves the server to the state it leads to
+    pub(super) fn apply_signal(&mut self, signal: PowerSignal) {
+        self.state = match signal {
+            PowerSignal::Start | PowerSignal::Restart => ServerState::Running,
+            PowerSignal::Stop | PowerSignal::Kill => ServerState::Offline,
+        };
+        self.signals.push(signal);
+    }
+
+    /// The startup command with the variables substituted
+    pub(super) fn invocation(&self) -> String {
+        let mut invocation = self
+            .startup
+            .replace("{{SERVER_MEMORY}}", &self.limits.memory.to_string());
+        for (variable, value) in &self.environment {
+            invocation = invocation.replace(&format!("{{{{{variable}}}}}"), value);
+        }
+        invocation
+    }
+
+    /// Whether the given absolute path is a directory
+    pub(super) fn is_dir(&self, path: &str) -> bool {
+        path == "/" || matches!(self.files.get(path), Some(None))
+    }
+
+    /// Creates a directory and its parents
+    pub(super) fn create_dir(&mut self, path: &str) {
+        let mut dir = String::new();
+        for part in path.split('/').filter(|part| !part.is_empty()) {
+            dir.push('/');
+            dir.push_str(part);
+            self.files.entry(dir.clone()).or_insert(None);
+        }
+    }
+
+    /// Writes a file, creating its parent directories
+    pub(super) fn write_file(&mut self, path: &str, contents: Bytes) {
+        self.create_dir(parent(path));
+        self.files.insert(path.to_owned(), Some(contents));
+    }
+
+    /// Lists the paths of a file and everything inside it, if it is a directory
+    pub(super) fn subtree(&self, path: &str) -> Vec<String> {
+        let prefix = format!("{path}/");
+        self.files
+            .keys()
+            .filter(|file| *file == path || file.starts_with(&prefix))
+            .cloned()
+            .collect()
+    }
+
+    /// Lists the names and contents of the files directly inside a directory, with directories
+    /// first
+    pub(super) fn children(&self, dir: &str) -> Vec<(&str, Option<&Bytes>)> {
+        let mut children: Vec<_> = self
+            .files
+            .iter()
+            .filter(|(path, _)| parent(path) == dir)
+            .map(|(path, contents)| (file_name(path), contents.as_ref()))
+            .collect();
+        children.sort_by_key(|(_, contents)| contents.is_some());
+        children
+    }
+
+    /// The total size of the files of this server, in bytes
+    pub(super) fn disk_bytes(&self) -> u64 {
+        self.files
+            .values()
+            .flatten()
+            .map(|file| file.len() as u64)
+            .sum()
+    }
+
+    pub(super) fn resources(&self) -> Value {
+        let running = self.state != ServerState::Offline;
+        json!({
+            "current_state": self.state,
+            "is_suspended": self.suspended,
+            "resources": {
+                "memory_bytes": if running { 512 * 1024 * 1024 } else { 0 },
+                "cpu_absolute": if running { 12.5 } else { 0.0 },
+                "disk_bytes": self.disk_bytes(),
+                "network_rx_bytes": 0,
+                "network_tx_bytes": 0,
+                "uptime": 0,
+            },
+        })
+    }
+}
+
+/// Resolves a path relative to the given directory into an absolute path without a trailing
+/// slash
+pub(super) fn resolve(root: &str, path: &str) -> String {
+    let root = if path.starts_with('/') { "" } else { root };
+    let mut parts = Vec::new();
+    for part in root.split('/').chain(path.split('/')) {
+        match part {
+            "" | "." => {}
+            ".." => {
+                parts.pop();
+            }
+            part => parts.push(part),
+        }
+    }
+    format!("/{}", parts.join("/"))
+}
+
+/// Gets the directory containing the given absolute path
+pub(super) fn parent(path: &str) -> &str {
+    match path.rfind('/') {
+        Some(0) | None => "/",
+        Some(index) => &path[..index],
+    }
+}
+
+/// Gets the last component of the given absolute path
+pub(super) fn file_name(path: &str) -> &str {
+    path.rsplit('/').next().unwrap_or(path)
+}
+
+/// Renders a file with the given name, or a directory if it has no contents
+pub(super) fn file_json(name: &str, contents: Option<&Bytes>) -> Value {
+    let mimetype = match contents {
+        None => "inode/directory",
+        Some(_) if name.ends_with(".tar.gz") => "application/gzip",
+        Some(_) if name.ends_with(".json") => "application/json",
+        Some(_) => "text/plain",
+    };
+    json!({
+        "name": name,
+        "mode": if contents.is_some() { "-rw-r--r--" } else { "drwxr-xr-x" },
+        "mode_bits": if contents.is_some() { "644" } else { "755" },
+        "size": contents.map_or(4096, |contents| contents.len()),
+        "is_file": contents.is_some(),
+        "is_symlink": false,
+        "mimetype": mimetype,
+        "created_at": TIMESTAMP,
+        "modified_at": TIMESTAMP,
+    })
+}
+
+#[derive(Debug)]
+pub(super) struct Backup {
+    pub(super) uuid: Uuid,
+    pub(super) name: String,
+    pub(super) ignored_files: Vec<String>,
+    pub(super) bytes: u64,
+    pub(super) is_locked: bool,
+}
+
+impl Backup {
+    pub(super) fn json(&self) -> Value {
+        json!({
+            "uuid": self.uuid,
+            "is_successful": true,
+            "is_locked": self.is_locked,
+            "name": self.name,
+            "ignored_files": self.ignored_files,
+            "checksum": format!("sha1:{}", self.uuid.simple()),
+            "bytes": self.bytes,
+            "created_at": TIMESTAMP,
+            "completed_at": TIMESTAMP,
+        })
+    }
+}
+
+#[derive(Debug)]
+pub(super) struct Schedule {
+    pub(super) id: u32,
+    pub(super) name: String,
+    /// The cron fields of this schedule by their name, such as `minute`
+    pub(super) cron: BTreeMap<&'static str, String>,
+    pub(super) is_active: bool,
+    pub(super) tasks: Vec<Task>,
+}
+
+impl Schedule {
+    pub(super) fn json(&self) -> Value {
+        let tasks = self
+            .tasks
+            .iter()
+            .map(|task| object("schedule_task", task.json()))
+            .collect();
+        json!({
+            "id": self.id,
+            "name": self.name,
+            "cron": self.cron,
+            "is_active": self.is_active,
+            "is_processing": false,
+            "only_when_online": false,
+            "last_run_at": null,
+            "next_run_at": TIMESTAMP,
+            "created_at": TIMESTAMP,
+            "updated_at": TIMESTAMP,
+            "relationships": {"tasks": list(tasks)},
+        })
+    }
+}
+
+#[derive(Debug)]
+pub(super) struct Task {
+    pub(super) id: u32,
+    pub(super) sequence_id: u32,
+    pub(super) action: String,
+    pub(super) payload: String,
+    pub(super) time_offset: i64,
+}
+
+impl Task {
+    pub(super) fn json(&self) -> Value {
+        json!({
+            "id": self.id,
+            "sequence_id": self.sequence_id,
+            "action": self.action,
+            "payload": self.payload,
+            "time_offset": self.time_offset,
+            "is_queued": false,
+            "continue_on_failure": false,
+            "created_at": TIMESTAMP,
+            "updated_at": TIMESTAMP,
+        })
+    }
+}
+
+#[derive(Debug)]
+pub(super) struct Subuser {
+    pub(super) uuid: Uuid,
+    pub(super) email: String,
+    pub(super) permissions: Vec<String>,
+}
+
+impl Subuser {
+    pub(super) fn json(&self) -> Value {
+        let username = self.email.split('@').next().unwrap_or_default();
+        json!({
+            "uuid": self.uuid,
+            "username": username,
+            "email": self.email,
+            "image": format!("https://gravatar.com/avatar/{}", self.uuid.simple()),
+            "2fa_enabled": false,
+            "created_at": TIMESTAMP,
+            "permissions": self.permissions,
+        })
+    }
+}
+
+#[derive(Debug)]
+pub(super) struct Database {
+    pub(super) id: String,
+    /// The numeric ID, which the application API uses instead of the hashed ID
+    pub(super) internal_id: u32,
+    pub(super) name: String,
+    pub(super) username: String,
+    pub(super) remote: String,
+    pub(super) password: String,
+}
+
+impl Database {
+    /// Renders this database, including its password if requested
+    pub(super) fn json(&self, with_password: bool) -> Value {
+        let mut database = json!({
+            "id": self.id,
+            "host": {"address": "127.0.0.1", "port": 3306},
+            "name": self.name,
+            "username": self.username,
+            "connections_from": self.remote,
+            "max_connections": 0,
+        });
+        if with_password {
+            database["relationships"] = json!({
+                "password": object("database_password", json!({"password": self.password})),
+            });
+        }
+        database
+    }
+
+    /// Renders this database for the application API, including the given relationships
+    pub(super) fn application_json(&self, server: u32, include: &[&str]) -> Value {
+        let mut database = json!({
+            "id": self.internal_id,
+            "server": server,
+            "host": DATABASE_HOST,
+            "database": self.name,
+            "username": self.username,
+            "remote": self.remote,
+            "max_connections": 0,
+            "created_at": TIMESTAMP,
+            "updated_at": TIMESTAMP,
+        });
+        if include.contains(&"password") {
+            database["relationships"]["password"] =
+                object("database_password", json!({"password": self.password}));
+        }
+        if include.contains(&"host") {
+            database["relationships"]["host"] = object(
+                "database_host",
+                json!({
+                    "id": DATABASE_HOST,
+                    "name": "Mock Database Host",
+                    "host": "127.0.0.1",
+                    "port": 3306,
+                    "username": "pterodactyl",
+                    "node": null,
+                    "created_at": TIMESTAMP,
+                    "updated_at": TIMESTAMP,
+                }),
+            );
+        }
+        database
+    }
+}
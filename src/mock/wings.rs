@@ -0,0 +1,321 @@
+//! A stand-in for the console websocket of Wings, served by the mock panel for the servers on its
+//! node
+
+use super::state::{PanelState, Server};
+use super::{lock, not_found};
+use crate::client::{PowerSignal, ServerState};
+use async_tungstenite::tokio::TokioAdapter;
+use async_tungstenite::tungstenite::handshake::derive_accept_key;
+use async_tungstenite::tungstenite::protocol::Role;
+use async_tungstenite::tungstenite::Message;
+use async_tungstenite::WebSocketStream;
+use bytes::Bytes;
+use futures_util::StreamExt;
+use http_body_util::Full;
+use hyper::body::Incoming;
+use hyper_util::rt::TokioIo;
+use reqwest::header::{HeaderValue, CONNECTION, SEC_WEBSOCKET_ACCEPT, SEC_WEBSOCKET_KEY, UPGRADE};
+use reqwest::StatusCode;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+use tokio::sync::Notify;
+
+/// An event sent by the websocket stand-in
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum WingsEvent {
+    /// A line of console output, which is also sent again when the client requests the logs
+    ConsoleOutput(String),
+    /// A line of output of the install script
+    InstallOutput(String),
+    /// A change of the state of the server, which is applied to the server
+    Status(ServerState),
+    /// The stats of the server, from its current state
+    Stats,
+    /// A warning that the token is about to expire, after which the client fetches a new token
+    /// and authenticates again
+    TokenExpiring,
+    /// A notice that the token expired
+    TokenExpired,
+    /// Closes the connection
+    Close,
+}
+
+/// The websocket connections of the mock panel
+#[derive(Debug, Default)]
+pub(super) struct Wings {
+    /// The events to send on the next connection to each server, by the UUID of the server
+    scripts: BTreeMap<String, Vec<WingsEvent>>,
+    /// The console output sent to each server, by the UUID of the server
+    logs: BTreeMap<String, Vec<String>>,
+    /// The number of open connections
+    pub(super) open: usize,
+    /// Notified whenever a connection closes
+    pub(super) closed: Arc<Notify>,
+}
+
+impl Wings {
+    pub(super) fn script(&mut self, uuid: String, events: impl IntoIterator<Item = WingsEvent>) {
+        self.scripts.entry(uuid).or_default().extend(events);
+    }
+}
+
+/// Accepts a request for the websocket of a server, or returns [`None`] if it isn't one
+pub(super) fn upgrade(
+    state: &Arc<Mutex<PanelState>>,
+    request: &mut hyper::Request<Incoming>,
+) -> Option<hyper::Response<Full<Bytes>>> {
+    let path = request.uri().path();
+    let uuid = path.strip_prefix("/api/servers/")?.strip_suffix("/ws")?;
+    let accept = derive_accept_key(request.headers().get(SEC_WEBSOCKET_KEY)?.as_bytes());
+
+    {
+        let mut state = lock(state);
+        state.requests.push(format!("GET {path}"));
+        if state.server(uuid).is_none() {
+            return Some(not_found().into_response());
+        }
+        state.wings.open += 1;
+    }
+    let uuid = uuid.to_owned();
+    let upgrade = hyper::upgrade::on(request);
+    let state = Arc::clone(state);
+    tokio::spawn(async move {
+        if let Ok(upgraded) = upgrade.await {
+            let io = TokioAdapter::new(TokioIo::new(upgraded));
+            let socket = WebSocketStream::from_raw_socket(io, Role::Server, None).await;
+            session(&state, &uuid, socket).await;
+        }
+        let mut state = lock(&state);
+        state.wings.open -= 1;
+        state.wings.closed.notify_waiters();
+    });
+
+    let mut response = hyper::Response::new(Full::new(Bytes::new()));
+    *response.status_mut() = StatusCode::SWITCHING_PROTOCOLS;
+    let headers = response.headers_mut();
+    headers.insert(CONNECTION, HeaderValue::from_static("Upgrade"));
+    headers.insert(UPGRADE, HeaderValue::from_static("websocket"));
+    if let Ok(accept) = HeaderValue::from_str(&accept) {
+        headers.insert(SEC_WEBSOCKET_ACCEPT, accept);
+    }
+    Some(response)
+}
+
+/// Answers the messages of a client until it disconnects
+async fn session<S>(state: &Mutex<PanelState>, uuid: &str, mut socket: WebSocketStream<S>)
+where
+    S: futures_io::AsyncRead + futures_io::AsyncWrite + Unpin,
+{
+    let mut authenticated = false;
+    while let Some(Ok(message)) = socket.next().await {
+        let Message::Text(text) = message else {
+            continue;
+        };
+        let replies = respond(&mut lock(state), uuid, &mut authenticated, &text);
+        for reply in replies {
+            // sending fails once the connection is closing, but the client may still send
+            // messages that need to be recorded
+            let _ = socket.send(reply).await;
+        }
+    }
+}
+
+fn respond(
+    state: &mut PanelState,
+    uuid: &str,
+    authenticated: &mut bool,
+    message: &str,
+) -> Vec<Message> {
+    #[derive(Deserialize)]
+    struct Event {
+        event: String,
+        #[serde(default)]
+        args: Vec<Value>,
+    }
+    let Ok(Event { event, args }) = serde_json::from_str(message) else {
+        return Vec::new();
+    };
+    let Some(index) = state.server_index(uuid) else {
+        return vec![Message::Close(None)];
+    };
+    let arg = args.first().and_then(Value::as_str).unwrap_or_default();
+    let server = &mut state.servers[index];
+    match &event[..] {
+        "auth" => {
+            if !server.tokens.iter().any(|token| token == arg) {
+                return vec![event_message("jwt error", &["invalid token"])];
+            }
+            let mut replies = vec![event_message("auth success", &[])];
+            if !std::mem::replace(authenticated, true) {
+                replies.push(status(server.state));
+                let script = state.wings.scripts.remove(uuid).unwrap_or_default();
+                for event in script {
+                    replies.push(render(state, index, event));
+                }
+            }
+            replies
+        }
+        _ if !*authenticated => Vec::new(),
+        "send command" => {
+            server.commands.push(arg.to_owned());
+            Vec::new()
+        }
+        "set state" => match PowerSignal::deserialize(json!(arg)) {
+            Ok(signal) => {
+                server.apply_signal(signal);
+                vec![status(server.state)]
+            }
+            Err(_err) => Vec::new(),
+        },
+        "send stats" => vec![stats(server)],
+        "send logs" => state
+            .wings
+            .logs
+            .get(uuid)
+            .into_iter()
+            .flatten()
+            .map(|line| event_message("console output", &[line]))
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Renders a scripted event, applying its effects to the server at the given index
+fn render(state: &mut PanelState, index: usize, event: WingsEvent) -> Message {
+    let server = &mut state.servers[index];
+    match event {
+        WingsEvent::ConsoleOutput(line) => {
+            let message = event_message("console output", &[&line]);
+            let logs = state.wings.logs.entry(server.uuid.to_string());
+            logs.or_default().push(line);
+            message
+        }
+        WingsEvent::InstallOutput(line) => event_message("install output", &[&line]),
+        WingsEvent::Status(new) => {
+            server.state = new;
+            status(new)
+        }
+        WingsEvent::Stats => stats(server),
+        WingsEvent::TokenExpiring => event_message("token expiring", &[]),
+        WingsEvent::TokenExpired => event_message("token expired", &[]),
+        WingsEvent::Close => Message::Close(None),
+    }
+}
+
+fn event_message(event: &str, args: &[&str]) -> Message {
+    Message::text(json!({"event": event, "args": args}).to_string())
+}
+
+fn status(state: ServerState) -> Message {
+    let state = json!(state);
+    event_message("status", &[state.as_str().unwrap_or_default()])
+}
+
+fn stats(server: &Server) -> Message {
+    let resources = server.resources();
+    let resources = &resources["resources"];
+    let stats = json!({
+        "memory_bytes": resources["memory_bytes"],
+        "memory_limit_bytes": u64::from(server.limits.memory) * 1024 * 1024,
+        "cpu_absolute": resources["cpu_absolute"],
+        "network": {"rx_bytes": 0, "tx_bytes": 0},
+        "state": server.state,
+        "disk_bytes": resources["disk_bytes"],
+        "uptime": 0,
+    });
+    event_message("stats", &[&stats.to_string()])
+}
+
+#[cfg(test)]
+mod test {
+    use super::WingsEvent;
+    use crate::client::websocket::{PteroWebSocketHandle, PteroWebSocketListener, ServerStats};
+    use crate::client::{ClientBuilder, PowerSignal, ServerState};
+    use crate::mock::MockPanel;
+    use async_tungstenite::tokio::connect_async;
+    use std::sync::{Arc, Mutex};
+
+    #[derive(Default)]
+    struct Listener(Arc<Mutex<Vec<String>>>);
+
+    impl<H: PteroWebSocketHandle> PteroWebSocketListener<H> for Listener {
+        async fn on_ready(&mut self, handle: &mut H) -> crate::Result<()> {
+            handle.send_power_signal(PowerSignal::Start).await?;
+            handle.send_command("say hello").await
+        }
+
+        async fn on_status(&mut self, _handle: &mut H, status: ServerState) -> crate::Result<()> {
+            self.0.lock().unwrap().push(format!("{status:?}"));
+            Ok(())
+        }
+
+        async fn on_console_output(&mut self, _handle: &mut H, output: &str) -> crate::Result<()> {
+            self.0.lock().unwrap().push(output.to_owned());
+            Ok(())
+        }
+
+        async fn on_stats(&mut self, _handle: &mut H, stats: ServerStats) -> crate::Result<()> {
+            self.0
+                .lock()
+                .unwrap()
+                .push(format!("stats {:?}", stats.state));
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_scripted_websocket() {
+        let panel = MockPanel::start().await.unwrap();
+        let id = panel.servers().remove(0);
+        panel.script_websocket(
+            &id,
+            [
+                WingsEvent::ConsoleOutput("Done (1.2s)!".to_owned()),
+                WingsEvent::TokenExpiring,
+                WingsEvent::Stats,
+                WingsEvent::Close,
+            ],
+        );
+        let client = ClientBuilder::new(panel.url(), "key").build();
+        let events = Arc::default();
+        client
+            .get_server(&id)
+            .run_websocket_loop(
+                |url| async move { connect_async(url).await.map(|(socket, _)| socket) },
+                Listener(Arc::clone(&events)),
+            )
+            .await
+            .unwrap();
+        panel.websockets_closed().await;
+
+        assert_eq!(
+            *events.lock().unwrap(),
+            ["Offline", "Done (1.2s)!", "stats Offline"]
+        );
+        assert_eq!(panel.server_state(&id), Some(ServerState::Running));
+        assert_eq!(panel.commands(&id), ["say hello"]);
+        assert_eq!(panel.power_signals(&id), [PowerSignal::Start]);
+        let path = format!("GET /api/client/servers/{id}/websocket");
+        let links = panel.requests().iter().filter(|r| **r == path).count();
+        assert_eq!(links, 2);
+    }
+
+    #[tokio::test]
+    async fn test_expired_token() {
+        let panel = MockPanel::start().await.unwrap();
+        let id = panel.servers().remove(0);
+        panel.script_websocket(&id, [WingsEvent::TokenExpired]);
+        let client = ClientBuilder::new(panel.url(), "key").build();
+        let result = client
+            .get_server(&id)
+            .run_websocket_loop(
+                |url| async move { connect_async(url).await.map(|(socket, _)| socket) },
+                Listener::default(),
+            )
+            .await;
+        assert!(matches!(result, Err(crate::Error::WebsocketTokenExpired)));
+    }
+}
@@ -0,0 +1,134 @@
+use crate::transport::Response;
+use reqwest::StatusCode;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// The default number of requests per minute allowed by the panel
+const DEFAULT_LIMIT: u32 = 240;
+
+/// The length of a rate limit window
+const WINDOW: Duration = Duration::from_secs(60);
+
+/// A client-side rate limiter, which delays requests before the panel starts rejecting them.
+///
+/// The limiter starts from the panel default of 240 requests per minute, and adjusts to the
+/// `x-ratelimit-*` headers of each response. Rate limits are tracked per API key by the panel, so
+/// clones of a limiter share their budget and should be given to every client using the same key.
+///
+/// ```
+/// # use pterodactyl_api::{client, RateLimiter};
+/// let limiter = RateLimiter::new();
+/// let first = client::ClientBuilder::new("https://pterodactyl.example.com", "ptlc_key")
+///     .with_rate_limiter(limiter.clone())
+///     .build();
+/// let second = client::ClientBuilder::new("https://pterodactyl.example.com", "ptlc_key")
+///     .with_rate_limiter(limiter)
+///     .build();
+/// ```
+#[derive(Debug, Clone)]
+pub struct RateLimiter {
+    state: Arc<Mutex<RateLimiterState>>,
+}
+
+#[derive(Debug)]
+struct RateLimiterState {
+    limit: u32,
+    remaining: u32,
+    window_start: Instant,
+}
+
+impl Default for RateLimiter {
+    fn default() -> Self {
+        RateLimiter::with_limit(DEFAULT_LIMIT)
+    }
+}
+
+impl RateLimiter {
+    /// Creates a rate limiter which allows the panel default of 240 requests per minute until the
+    /// panel reports a different limit
+    pub fn new() -> Self {
+        RateLimiter::default()
+    }
+
+    /// Creates a rate limiter which allows the given number of requests per minute until the panel
+    /// reports a different limit
+    pub fn with_limit(limit: u32) -> Self {
+        RateLimiter {
+            state: Arc::new(Mutex::new(RateLimiterState {
+                limit,
+                remaining: limit,
+                window_start: Instant::now(),
+            })),
+        }
+    }
+
+    /// Gets the number of requests which can be made before requests are delayed
+    pub fn remaining(&self) -> u32 {
+        let mut state = self.state.lock().unwrap();
+        state.refill(Instant::now());
+        state.remaining
+    }
+
+    /// Waits until a request may be sent, and takes it out of the budget
+    pub(crate) async fn acquire(&self) {
+        loop {
+            let delay = {
+                let mut state = self.state.lock().unwrap();
+                let now = Instant::now();
+                state.refill(now);
+                if state.remaining > 0 {
+                    state.remaining -= 1;
+                    return;
+                }
+                WINDOW.saturating_sub(now.duration_since(state.window_start))
+            };
+            tokio::time::sleep(delay).await;
+        }
+    }
+
+    /// Updates the budget from the rate limit headers of a response
+    pub(crate) fn observe(&self, response: &Response) {
+        let header = |name: &str| {
+            response
+                .headers()
+                .get(name)
+                .and_then(|header| header.to_str().ok())
+                .and_then(|header| header.parse::<u32>().ok())
+        };
+        let mut state = self.state.lock().unwrap();
+        state.refill(Instant::now());
+        if let Some(limit) = header("x-ratelimit-limit") {
+            state.limit = limit;
+        }
+        if let Some(remaining) = header("x-ratelimit-remaining") {
+            state.remaining = remaining;
+        } else if response.status() == StatusCode::TOO_MANY_REQUESTS {
+            state.remaining = 0;
+        }
+    }
+}
+
+impl RateLimiterState {
+    /// Starts a new window with a full budget if the current window has passed
+    fn refill(&mut self, now: Instant) {
+        if now.duration_since(self.window_start) >= WINDOW {
+            self.window_start = now;
+            self.remaining = self.limit;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::RateLimiter;
+
+    #[tokio::test]
+    async fn test_budget() {
+        let limiter = RateLimiter::with_limit(3);
+        let shared = limiter.clone();
+        limiter.acquire().await;
+        shared.acquire().await;
+        assert_eq!(limiter.remaining(), 1);
+        assert_eq!(shared.remaining(), 1);
+    }
+}
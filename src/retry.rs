@@ -0,0 +1,251 @@
+use crate::transport::{Request, Response, Transport};
+use reqwest::{Method, StatusCode};
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// The policy for retrying requests which failed with a transient error, such as a rate limit
+/// (`429`), a bad gateway (`502`), an unavailable panel (`503`) or a connection failure.
+///
+/// Rate limited requests and requests which never reached the panel are always safe to retry.
+/// Other failures are only retried for idempotent methods, unless
+/// [`RetryPolicy::retry_non_idempotent`] is set.
+///
+/// ```
+/// # use pterodactyl_api::RetryPolicy;
+/// # use std::time::Duration;
+/// let policy = RetryPolicy::new()
+///     .with_max_attempts(5)
+///     .with_base_delay(Duration::from_millis(250))
+///     .with_max_delay(Duration::from_secs(10));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RetryPolicy {
+    max_attempts: u32,
+    base_delay: Duration,
+    max_delay: Duration,
+    jitter: bool,
+    retry_non_idempotent: bool,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(30),
+            jitter: true,
+            retry_non_idempotent: false,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Creates the default retry policy, which makes up to 3 attempts, starting with a delay of
+    /// 500ms and never waiting longer than 30s
+    pub fn new() -> Self {
+        RetryPolicy::default()
+    }
+
+    /// Sets the maximum number of attempts, including the first one
+    pub fn with_max_attempts(self, max_attempts: u32) -> Self {
+        RetryPolicy {
+            max_attempts: max_attempts.max(1),
+            ..self
+        }
+    }
+
+    /// Sets the delay before the first retry, which doubles for each following retry
+    pub fn with_base_delay(self, base_delay: Duration) -> Self {
+        RetryPolicy { base_delay, ..self }
+    }
+
+    /// Sets the longest delay between two attempts, including delays requested by the panel
+    pub fn with_max_delay(self, max_delay: Duration) -> Self {
+        RetryPolicy { max_delay, ..self }
+    }
+
+    /// Sets whether to randomize the backoff delays, so that clients don't retry in lockstep.
+    /// Enabled by default
+    pub fn with_jitter(self, jitter: bool) -> Self {
+        RetryPolicy { jitter, ..self }
+    }
+
+    /// Also retries non-idempotent requests, such as `POST` and `PATCH`, after failures that may
+    /// have reached the panel. Please note that this can cause an action to be performed twice
+    pub fn retry_non_idempotent(self) -> Self {
+        RetryPolicy {
+            retry_non_idempotent: true,
+            ..self
+        }
+    }
+
+    /// Sends a request, retrying it according to this policy
+    pub(crate) async fn send(
+        &self,
+        transport: &dyn Transport,
+        request: Request,
+    ) -> crate::Result<Response> {
+        let method = &request.method().clone();
+        let mut attempt = 1;
+        loop {
+            // requests with streamed bodies can't be cloned, and therefore can't be retried
+            let Some(next) = request.try_clone().filter(|_| attempt < self.max_attempts) else {
+                return transport.send(request).await;
+            };
+            let delay = match transport.send(next).await {
+                Ok(response) => match self.response_delay(method, attempt, &response) {
+                    Some(delay) => delay,
+                    None => return Ok(response),
+                },
+                Err(err) if self.is_retryable(method, &err) => self.backoff(attempt),
+                Err(err) => return Err(err),
+            };
+            tokio::time::sleep(delay).await;
+            attempt += 1;
+        }
+    }
+
+    fn is_idempotent(&self, method: &Method) -> bool {
+        self.retry_non_idempotent
+            || matches!(
+                *method,
+                Method::GET | Method::HEAD | Method::PUT | Method::DELETE | Method::OPTIONS
+            )
+    }
+
+    fn is_retryable(&self, method: &Method, err: &crate::Error) -> bool {
+        let crate::Error::Reqwest(err) = err else {
+            return false;
+        };
+        err.is_connect() || ((err.is_timeout() || err.is_request()) && self.is_idempotent(method))
+    }
+
+    /// Gets how long to wait before retrying the request that got the given response, or [`None`]
+    /// if it should not be retried
+    fn response_delay(
+        &self,
+        method: &Method,
+        attempt: u32,
+        response: &Response,
+    ) -> Option<Duration> {
+        match response.status() {
+            StatusCode::TOO_MANY_REQUESTS => {}
+            StatusCode::BAD_GATEWAY | StatusCode::SERVICE_UNAVAILABLE
+                if self.is_idempotent(method) => {}
+            _ => return None,
+        }
+        let header = |name: &str| {
+            response
+                .headers()
+                .get(name)
+                .and_then(|header| header.to_str().ok())
+                .and_then(|header| header.trim().parse::<u64>().ok())
+        };
+        let requested = header("retry-after").map(Duration::from_secs).or_else(|| {
+            let reset = header("x-ratelimit-reset")?;
+            if header("x-ratelimit-remaining")? != 0 {
+                return None;
+            }
+            let now = SystemTime::now().duration_since(UNIX_EPOCH).ok()?;
+            Some(Duration::from_secs(reset).saturating_sub(now))
+        });
+        Some(match requested {
+            Some(delay) => delay.min(self.max_delay),
+            None => self.backoff(attempt),
+        })
+    }
+
+    /// Gets the exponential backoff delay after the given attempt
+    fn backoff(&self, attempt: u32) -> Duration {
+        let delay = self
+            .base_delay
+            .saturating_mul(2u32.saturating_pow(attempt - 1))
+            .min(self.max_delay);
+        if !self.jitter {
+            return delay;
+        }
+        // pick a random delay between half and all of the backoff delay
+        let random = RandomState::new().build_hasher().finish();
+        delay.mul_f64(0.5 + (random as f64 / u64::MAX as f64) * 0.5)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::RetryPolicy;
+    use crate::transport::{BoxFuture, Request, Response, Transport};
+    use reqwest::{Method, StatusCode};
+    use std::sync::Mutex;
+    use std::time::Duration;
+
+    /// Responds to each request with the next of the given statuses
+    #[derive(Debug)]
+    struct StatusTransport(Mutex<Vec<StatusCode>>);
+
+    impl Transport for StatusTransport {
+        fn send(&self, _request: Request) -> BoxFuture<'_, crate::Result<Response>> {
+            let status = self.0.lock().unwrap().remove(0);
+            Box::pin(async move { Ok(Response::new(status, "")) })
+        }
+    }
+
+    #[test]
+    fn test_backoff() {
+        let policy = RetryPolicy::new()
+            .with_base_delay(Duration::from_secs(1))
+            .with_max_delay(Duration::from_secs(5))
+            .with_jitter(false);
+        assert_eq!(policy.backoff(1), Duration::from_secs(1));
+        assert_eq!(policy.backoff(2), Duration::from_secs(2));
+        assert_eq!(policy.backoff(3), Duration::from_secs(4));
+        assert_eq!(policy.backoff(4), Duration::from_secs(5));
+
+        let policy = policy.with_jitter(true);
+        for attempt in 1..5 {
+            let delay = policy.backoff(attempt);
+            assert!(delay <= Duration::from_secs(5));
+            assert!(delay >= Duration::from_millis(500));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_retry_transport() {
+        let policy = RetryPolicy::new()
+            .with_base_delay(Duration::ZERO)
+            .with_max_attempts(3);
+        let policy = &policy;
+        let send = |method: Method, statuses: Vec<StatusCode>| {
+            let transport = StatusTransport(Mutex::new(statuses));
+            async move {
+                let status = policy
+                    .send(&transport, Request::new(method, "servers"))
+                    .await
+                    .unwrap()
+                    .status();
+                (status, transport.0.into_inner().unwrap().len())
+            }
+        };
+
+        let (status, remaining) = send(
+            Method::GET,
+            vec![
+                StatusCode::SERVICE_UNAVAILABLE,
+                StatusCode::OK,
+                StatusCode::OK,
+            ],
+        )
+        .await;
+        assert_eq!((status, remaining), (StatusCode::OK, 1));
+
+        let (status, remaining) = send(
+            Method::POST,
+            vec![StatusCode::SERVICE_UNAVAILABLE, StatusCode::OK],
+        )
+        .await;
+        assert_eq!((status, remaining), (StatusCode::SERVICE_UNAVAILABLE, 1));
+
+        let (status, remaining) = send(Method::POST, vec![StatusCode::TOO_MANY_REQUESTS; 3]).await;
+        assert_eq!((status, remaining), (StatusCode::TOO_MANY_REQUESTS, 0));
+    }
+}
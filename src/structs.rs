@@ -1,5 +1,7 @@
 use serde::de::DeserializeOwned;
 use serde::{Deserialize, Deserializer};
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
 use time::format_description::well_known::Iso8601;
 use time::OffsetDateTime;
 
@@ -36,6 +38,245 @@ pub struct PteroObject<T> {
     pub attributes: T,
 }
 
+/// A single error entry returned by the Pterodactyl API in the `errors` array of a failed request
+#[derive(Deserialize, Debug, Clone)]
+#[non_exhaustive]
+pub struct ApiError {
+    /// The error code, such as `DisplayException` or `ValidationException`
+    pub code: String,
+    /// The HTTP status reported for this error
+    #[serde(default)]
+    pub status: Option<String>,
+    /// A human readable description of the error
+    #[serde(default)]
+    pub detail: Option<String>,
+    /// What caused the error, if the panel reported it
+    #[serde(default)]
+    pub source: Option<ApiErrorSource>,
+    /// Extra information about the error, reported for validation errors
+    #[serde(default)]
+    pub meta: Option<ApiErrorMeta>,
+}
+
+impl ApiError {
+    /// The request field that caused this error, if any
+    pub fn field(&self) -> Option<&str> {
+        self.meta
+            .as_ref()
+            .and_then(|meta| meta.source_field.as_deref())
+            .or_else(|| {
+                self.source
+                    .as_ref()
+                    .and_then(|source| source.field.as_deref())
+            })
+    }
+}
+
+impl Display for ApiError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match &self.detail {
+            Some(detail) => write!(f, "{}: {}", self.code, detail),
+            None => f.write_str(&self.code),
+        }
+    }
+}
+
+/// The source of an [`ApiError`]
+#[derive(Deserialize, Debug, Clone)]
+#[non_exhaustive]
+pub struct ApiErrorSource {
+    /// The request field that caused the error
+    #[serde(default)]
+    pub field: Option<String>,
+}
+
+/// Extra information about an [`ApiError`]
+#[derive(Deserialize, Debug, Clone)]
+#[non_exhaustive]
+pub struct ApiErrorMeta {
+    /// The request field that failed validation
+    #[serde(default)]
+    pub source_field: Option<String>,
+    /// The validation rule that failed, such as `required` or `min`
+    #[serde(default)]
+    pub rule: Option<String>,
+}
+
+/// The field-level errors of a request which failed validation, keyed by the path of the field,
+/// such as `limits.memory` or `environment.SERVER_JARFILE`, along with the errors of the request
+/// which don't belong to a field
+#[derive(Debug, Clone, Default)]
+pub struct ValidationErrors {
+    fields: BTreeMap<String, Vec<FieldError>>,
+    general: Vec<FieldError>,
+}
+
+/// A validation rule that a field failed
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct FieldError {
+    /// The rule that failed, such as `required` or `min`
+    pub rule: Option<String>,
+    /// A human readable description of the failure
+    pub detail: String,
+}
+
+impl ValidationErrors {
+    /// Gets the errors of the given field, or an empty slice if the field is valid
+    pub fn field(&self, field: &str) -> &[FieldError] {
+        self.fields.get(field).map_or(&[], Vec::as_slice)
+    }
+
+    /// Iterates over the paths of the invalid fields
+    pub fn fields(&self) -> impl Iterator<Item = &str> {
+        self.fields.keys().map(String::as_str)
+    }
+
+    /// Iterates over the invalid fields and their errors
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &[FieldError])> {
+        self.fields
+            .iter()
+            .map(|(field, errors)| (field.as_str(), errors.as_slice()))
+    }
+
+    /// Gets the errors which don't belong to a field
+    pub fn general(&self) -> &[FieldError] {
+        &self.general
+    }
+
+    /// Whether the field with the given path is invalid
+    pub fn contains(&self, field: &str) -> bool {
+        self.fields.contains_key(field)
+    }
+
+    /// The number of invalid fields
+    pub fn len(&self) -> usize {
+        self.fields.len()
+    }
+
+    /// Whether there are no invalid fields
+    pub fn is_empty(&self) -> bool {
+        self.fields.is_empty()
+    }
+
+    /// Collects the errors by the field they belong to. Returns [`None`] if none of the errors
+    /// belong to a field
+    pub(crate) fn from_errors(errors: &[ApiError]) -> Option<Self> {
+        let mut fields: BTreeMap<String, Vec<FieldError>> = BTreeMap::new();
+        let mut general = Vec::new();
+        for error in errors {
+            let field_error = FieldError {
+                rule: error.meta.as_ref().and_then(|meta| meta.rule.clone()),
+                detail: error.detail.clone().unwrap_or_else(|| error.code.clone()),
+            };
+            match error.field() {
+                Some(field) => fields
+                    .entry(field.to_owned())
+                    .or_default()
+                    .push(field_error),
+                None => general.push(field_error),
+            }
+        }
+        if fields.is_empty() {
+            None
+        } else {
+            Some(ValidationErrors { fields, general })
+        }
+    }
+}
+
+impl Display for ValidationErrors {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for (i, (field, errors)) in self.iter().enumerate() {
+            if i != 0 {
+                f.write_str(", ")?;
+            }
+            write!(f, "{field}: ")?;
+            for (j, error) in errors.iter().enumerate() {
+                if j != 0 {
+                    f.write_str("; ")?;
+                }
+                f.write_str(&error.detail)?;
+            }
+        }
+        for error in &self.general {
+            write!(f, ", {}", error.detail)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Deserialize, Default)]
+pub(crate) struct ErrorResponse {
+    #[serde(default)]
+    pub(crate) errors: Vec<ApiError>,
+}
+
+impl ErrorResponse {
+    pub(crate) fn is_error(&self, error: &str) -> bool {
+        self.errors.iter().any(|e| e.code == error)
+    }
+}
+
+/// A single page of a paginated list in the API
+#[derive(Deserialize, Debug)]
+#[serde(bound(deserialize = "T: Deserialize<'de>"))]
+pub struct Page<T> {
+    /// The items on this page
+    #[serde(deserialize_with = "ptero_objects")]
+    pub data: Vec<T>,
+    /// The pagination metadata of the list, if the list is paginated
+    #[serde(default, rename = "meta", deserialize_with = "pagination")]
+    pub pagination: Option<Pagination>,
+}
+
+impl<T> Page<T> {
+    /// Gets the number of the page after this one, or [`None`] if this is the last page
+    pub fn next_page(&self) -> Option<u32> {
+        self.pagination
+            .as_ref()
+            .filter(|pagination| pagination.current_page < pagination.total_pages)
+            .map(|pagination| pagination.current_page + 1)
+    }
+}
+
+/// The pagination metadata of a list in the API
+#[derive(Deserialize, Debug, Copy, Clone)]
+#[non_exhaustive]
+pub struct Pagination {
+    /// The total number of items in the list
+    pub total: u64,
+    /// The number of items on this page
+    pub count: u64,
+    /// The maximum number of items on a page
+    pub per_page: u64,
+    /// The number of this page, starting from 1
+    pub current_page: u32,
+    /// The total number of pages
+    pub total_pages: u32,
+}
+
+fn pagination<'de, D>(deserializer: D) -> Result<Option<Pagination>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    struct Meta {
+        pagination: Option<Pagination>,
+    }
+    let meta: Option<Meta> = Deserialize::deserialize(deserializer)?;
+    Ok(meta.and_then(|meta| meta.pagination))
+}
+
+fn ptero_objects<'de, D, T>(deserializer: D) -> Result<Vec<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    let objects: Vec<PteroObject<T>> = Deserialize::deserialize(deserializer)?;
+    Ok(objects.into_iter().map(|obj| obj.attributes).collect())
+}
+
 #[derive(Deserialize)]
 #[serde(transparent)]
 pub(crate) struct PteroList<T>
@@ -76,3 +317,12 @@ where
         .map(|obj| obj.attributes)
         .collect())
 }
+
+pub(crate) fn ptero_object<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    let object: PteroObject<T> = Deserialize::deserialize(deserializer)?;
+    Ok(Some(object.attributes))
+}
@@ -0,0 +1,662 @@
+//! Recording and replaying of interactions with a panel, for tests which run without one.
+//!
+//! A [`Recorder`] sends requests to a real panel, and records each request and its response into
+//! a [`Cassette`], which can be saved to a file. A [`Replayer`] later answers the same requests
+//! from the cassette, without a panel. Passwords and tokens in the recorded bodies, such as API
+//! key secrets and websocket tokens, are replaced with `<redacted>`:
+//!
+//! ```no_run
+//! use pterodactyl_api::client::ClientBuilder;
+//! use pterodactyl_api::testing::{Recorder, Replayer};
+//!
+//! # async fn test() -> pterodactyl_api::Result<()> {
+//! let recorder = Recorder::new();
+//! let client = ClientBuilder::new("https://panel.example.com", "key")
+//!     .with_transport(recorder.clone())
+//!     .build();
+//! client.list_servers().await?;
+//! recorder.cassette().save("tests/cassettes/list_servers.json")?;
+//!
+//! let client = ClientBuilder::new("https://panel.example.com", "key")
+//!     .with_transport(Replayer::load("tests/cassettes/list_servers.json")?)
+//!     .build();
+//! client.list_servers().await?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! With the `websocket` feature, the messages of a server websocket opened by a client using a
+//! [`Recorder`] are recorded too, and [`Replayer::connect_websocket`] replays them.
+
+use crate::transport::{BoxFuture, Request, ReqwestTransport, Response, Transport};
+use reqwest::header::{HeaderName, HeaderValue};
+use reqwest::{Method, StatusCode};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+/// The recorded interactions with a panel
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Cassette {
+    /// The recorded requests and their responses, in the order they were sent
+    pub interactions: Vec<Interaction>,
+    /// The recorded websocket messages, in the order they were sent or received
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub frames: Vec<Frame>,
+}
+
+impl Cassette {
+    /// Loads a cassette from a JSON file
+    pub fn load(path: impl AsRef<Path>) -> crate::Result<Self> {
+        Ok(serde_json::from_slice(&std::fs::read(path)?)?)
+    }
+
+    /// Saves this cassette to a JSON file
+    pub fn save(&self, path: impl AsRef<Path>) -> crate::Result<()> {
+        Ok(std::fs::write(path, serde_json::to_vec_pretty(self)?)?)
+    }
+}
+
+/// A recorded request and its response
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct Interaction {
+    /// The method of the request
+    pub method: String,
+    /// The path of the request, including the query string
+    pub path: String,
+    /// The body of the request
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body: Option<Body>,
+    /// The status code of the response
+    pub status: u16,
+    /// The headers of the response
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub headers: BTreeMap<String, String>,
+    /// The body of the response
+    pub response: Body,
+}
+
+/// A recorded request or response body
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Body {
+    /// A JSON body
+    Json(serde_json::Value),
+    /// Any other body, which is recorded as (lossy) UTF-8 text
+    Text(String),
+}
+
+impl Body {
+    fn from_bytes(bytes: &[u8]) -> Self {
+        match serde_json::from_slice(bytes) {
+            Ok(json) => Body::Json(json),
+            Err(_) => Body::Text(String::from_utf8_lossy(bytes).into_owned()),
+        }
+    }
+
+    /// Replaces the secrets in a JSON body, such as passwords and tokens, so that they aren't
+    /// saved in cassettes
+    fn redact(self) -> Self {
+        match self {
+            Body::Json(mut json) => {
+                redact_secrets(&mut json);
+                Body::Json(json)
+            }
+            Body::Text(text) => Body::Text(text),
+        }
+    }
+
+    fn into_bytes(self) -> Vec<u8> {
+        match self {
+            Body::Json(json) => json.to_string().into_bytes(),
+            Body::Text(text) => text.into_bytes(),
+        }
+    }
+}
+
+/// The keys of the JSON values which hold secrets, in both request and response bodies
+const SECRET_KEYS: &[&str] = &[
+    "password",
+    "current_password",
+    "password_confirmation",
+    "token",
+    "secret_token",
+];
+
+/// The response headers which hold secrets, such as the panel's session cookies
+const SECRET_HEADERS: &[&str] = &["set-cookie", "authorization"];
+
+fn redact_secrets(json: &mut serde_json::Value) {
+    match json {
+        serde_json::Value::Object(object) => {
+            for (key, value) in object {
+                if value.is_string() && SECRET_KEYS.contains(&key.as_str()) {
+                    *value = serde_json::Value::String("<redacted>".to_owned());
+                } else {
+                    redact_secrets(value);
+                }
+            }
+        }
+        serde_json::Value::Array(values) => values.iter_mut().for_each(redact_secrets),
+        _ => {}
+    }
+}
+
+/// A recorded websocket message
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Frame {
+    /// A message sent to the panel
+    Sent(String),
+    /// A message received from the panel
+    Received(String),
+}
+
+/// Gets the path and query of a URL, which identifies a request regardless of the panel it was
+/// sent to
+fn request_path(url: &str) -> String {
+    match reqwest::Url::parse(url) {
+        Ok(url) => match url.query() {
+            Some(query) => format!("{}?{}", url.path(), query),
+            None => url.path().to_owned(),
+        },
+        Err(_) => url.to_owned(),
+    }
+}
+
+/// A transport which sends requests using another transport, and records them into a
+/// [`Cassette`]. Clones of a recorder record into the same cassette
+#[derive(Debug, Clone)]
+pub struct Recorder {
+    transport: Arc<dyn Transport>,
+    cassette: Arc<Mutex<Cassette>>,
+}
+
+impl Default for Recorder {
+    fn default() -> Self {
+        Self::with_transport(ReqwestTransport::default())
+    }
+}
+
+impl Recorder {
+    /// Creates a recorder which sends requests using [`reqwest`]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a recorder which sends requests using the given transport
+    pub fn with_transport(transport: impl Transport + 'static) -> Self {
+        Self {
+            transport: Arc::new(transport),
+            cassette: Arc::default(),
+        }
+    }
+
+    /// Gets a copy of the interactions recorded so far
+    pub fn cassette(&self) -> Cassette {
+        self.cassette.lock().unwrap().clone()
+    }
+}
+
+impl Transport for Recorder {
+    fn send(&self, request: Request) -> BoxFuture<'_, crate::Result<Response>> {
+        Box::pin(async move {
+            let method = request.method().to_string();
+            let path = request_path(request.url());
+            let body = request.body().map(Body::from_bytes).map(Body::redact);
+
+            let response = self.transport.send(request).await?;
+            let status = response.status();
+            let headers = response.headers().clone();
+            let bytes = response.bytes().await?;
+
+            self.cassette
+                .lock()
+                .unwrap()
+                .interactions
+                .push(Interaction {
+                    method,
+                    path,
+                    body,
+                    status: status.as_u16(),
+                    headers: headers
+                        .iter()
+                        .filter_map(|(name, value)| {
+                            let value = if SECRET_HEADERS.contains(&name.as_str()) {
+                                "<redacted>"
+                            } else {
+                                value.to_str().ok()?
+                            };
+                            Some((name.to_string(), value.to_owned()))
+                        })
+                        .collect(),
+                    response: Body::from_bytes(&bytes).redact(),
+                });
+
+            let mut response = Response::new(status, bytes);
+            *response.headers_mut() = headers;
+            Ok(response)
+        })
+    }
+
+    #[cfg(feature = "websocket")]
+    fn websocket_message(&self, direction: crate::transport::MessageDirection, message: &str) {
+        use crate::transport::MessageDirection;
+        let frame = match direction {
+            MessageDirection::Sent => Frame::Sent(redact_token(message)),
+            MessageDirection::Received => Frame::Received(message.to_owned()),
+        };
+        self.cassette.lock().unwrap().frames.push(frame);
+    }
+}
+
+/// Removes the token from websocket authentication messages, so that it isn't saved in cassettes
+#[cfg(feature = "websocket")]
+fn redact_token(message: &str) -> String {
+    match serde_json::from_str::<serde_json::Value>(message) {
+        Ok(serde_json::Value::Object(mut event)) if event["event"] == "auth" => {
+            event.insert("args".to_owned(), serde_json::json!(["<token>"]));
+            serde_json::Value::Object(event).to_string()
+        }
+        _ => message.to_owned(),
+    }
+}
+
+/// A transport which answers requests with the responses recorded in a [`Cassette`], without
+/// sending them. Each request is answered by the first interaction with the same method and path
+/// which hasn't answered a request yet
+#[derive(Debug, Clone)]
+pub struct Replayer {
+    state: Arc<Mutex<ReplayerState>>,
+}
+
+#[derive(Debug)]
+struct ReplayerState {
+    cassette: Cassette,
+    used: Vec<bool>,
+}
+
+impl Replayer {
+    /// Creates a replayer which answers requests from the given cassette
+    pub fn new(cassette: Cassette) -> Self {
+        Self {
+            state: Arc::new(Mutex::new(ReplayerState {
+                used: vec![false; cassette.interactions.len()],
+                cassette,
+            })),
+        }
+    }
+
+    /// Creates a replayer which answers requests from the cassette in the given JSON file
+    pub fn load(path: impl AsRef<Path>) -> crate::Result<Self> {
+        Ok(Self::new(Cassette::load(path)?))
+    }
+
+    /// Gets the number of recorded interactions which haven't answered a request yet
+    pub fn remaining(&self) -> usize {
+        let state = self.state.lock().unwrap();
+        state.used.iter().filter(|used| !**used).count()
+    }
+
+    fn replay(&self, method: &Method, path: &str) -> crate::Result<Response> {
+        let mut state = self.state.lock().unwrap();
+        let ReplayerState { cassette, used } = &mut *state;
+        let (interaction, used) = cassette
+            .interactions
+            .iter()
+            .zip(used.iter_mut())
+            .find(|(interaction, used)| {
+                !**used && interaction.method == method.as_str() && interaction.path == path
+            })
+            .ok_or_else(|| {
+                crate::Error::Transport(
+                    format!("No recorded interaction for {method} {path}").into(),
+                )
+            })?;
+        *used = true;
+
+        let status = StatusCode::from_u16(interaction.status)
+            .map_err(|err| crate::Error::Transport(err.into()))?;
+        let mut response = Response::new(status, interaction.response.clone().into_bytes());
+        for (name, value) in &interaction.headers {
+            if let (Ok(name), Ok(value)) = (
+                HeaderName::try_from(name.as_str()),
+                HeaderValue::try_from(value.as_str()),
+            ) {
+                response.headers_mut().append(name, value);
+            }
+        }
+        Ok(response)
+    }
+}
+
+impl Transport for Replayer {
+    fn send(&self, request: Request) -> BoxFuture<'_, crate::Result<Response>> {
+        let response = self.replay(request.method(), &request_path(request.url()));
+        Box::pin(async move { response })
+    }
+}
+
+#[cfg(feature = "websocket")]
+pub use replay_socket::ReplaySocket;
+
+#[cfg(feature = "websocket")]
+mod replay_socket {
+    use super::{Frame, Replayer};
+    use async_tungstenite::tungstenite::protocol::frame::coding::{Data, OpCode};
+    use async_tungstenite::tungstenite::protocol::frame::{Frame as WsFrame, FrameHeader};
+    use async_tungstenite::tungstenite::protocol::Role;
+    use async_tungstenite::WebSocketStream;
+    use futures_io::{AsyncRead, AsyncWrite};
+    use std::collections::VecDeque;
+    use std::io::{self, Cursor};
+    use std::pin::Pin;
+    use std::task::{Context, Poll};
+
+    impl Replayer {
+        /// Opens a websocket which replays the messages recorded in the cassette, to pass to
+        /// [`Server::run_websocket_loop`](crate::client::Server::run_websocket_loop) in place of
+        /// connecting to the given URL. Each recorded message received from the panel is
+        /// replayed once the messages recorded before it have been sent, and the websocket is
+        /// closed after the last message
+        pub async fn connect_websocket(
+            &self,
+            _url: String,
+        ) -> async_tungstenite::tungstenite::Result<WebSocketStream<ReplaySocket>> {
+            let frames = self.state.lock().unwrap().cassette.frames.clone();
+            Ok(WebSocketStream::from_raw_socket(
+                ReplaySocket {
+                    frames: frames.into(),
+                    sent: 0,
+                    read: Vec::new(),
+                    written: Vec::new(),
+                    closed: false,
+                },
+                Role::Client,
+                None,
+            )
+            .await)
+        }
+    }
+
+    /// The stream of a replayed websocket, which plays the part of the panel
+    #[derive(Debug)]
+    pub struct ReplaySocket {
+        frames: VecDeque<Frame>,
+        /// The number of messages sent by the client which haven't been matched with a recorded
+        /// message yet
+        sent: usize,
+        /// The encoded frames waiting to be read by the client
+        read: Vec<u8>,
+        /// The bytes written by the client which don't form a whole frame yet
+        written: Vec<u8>,
+        closed: bool,
+    }
+
+    impl ReplaySocket {
+        /// Encodes the next recorded messages received from the panel, up to the next recorded
+        /// message which hasn't been sent yet
+        fn fill(&mut self) -> io::Result<()> {
+            while self.read.is_empty() {
+                match self.frames.pop_front() {
+                    Some(Frame::Sent(message)) if self.sent == 0 => {
+                        return Err(io::Error::other(format!(
+                            "Expected the client to send the recorded message {message}"
+                        )));
+                    }
+                    Some(Frame::Sent(_)) => self.sent -= 1,
+                    Some(Frame::Received(message)) => {
+                        WsFrame::message(message, OpCode::Data(Data::Text), true)
+                            .format(&mut self.read)
+                            .map_err(io::Error::other)?;
+                    }
+                    None if !self.closed => {
+                        self.closed = true;
+                        WsFrame::close(None)
+                            .format(&mut self.read)
+                            .map_err(io::Error::other)?;
+                    }
+                    None => return Ok(()),
+                }
+            }
+            Ok(())
+        }
+
+        /// Decodes the whole frames written by the client, counting the sent text messages
+        fn drain(&mut self) -> io::Result<()> {
+            loop {
+                let mut cursor = Cursor::new(&self.written);
+                let Some((header, length)) =
+                    FrameHeader::parse(&mut cursor).map_err(io::Error::other)?
+                else {
+                    return Ok(());
+                };
+                let start = cursor.position() as usize;
+                let end = start + length as usize;
+                if self.written.len() < end {
+                    return Ok(());
+                }
+                if header.opcode == OpCode::Data(Data::Text) {
+                    self.sent += 1;
+                }
+                self.written.drain(..end);
+            }
+        }
+    }
+
+    impl AsyncRead for ReplaySocket {
+        fn poll_read(
+            self: Pin<&mut Self>,
+            _cx: &mut Context<'_>,
+            buf: &mut [u8],
+        ) -> Poll<io::Result<usize>> {
+            let this = self.get_mut();
+            this.fill()?;
+            let length = buf.len().min(this.read.len());
+            buf[..length].copy_from_slice(&this.read[..length]);
+            this.read.drain(..length);
+            Poll::Ready(Ok(length))
+        }
+    }
+
+    impl AsyncWrite for ReplaySocket {
+        fn poll_write(
+            self: Pin<&mut Self>,
+            _cx: &mut Context<'_>,
+            buf: &[u8],
+        ) -> Poll<io::Result<usize>> {
+            let this = self.get_mut();
+            this.written.extend_from_slice(buf);
+            this.drain()?;
+            Poll::Ready(Ok(buf.len()))
+        }
+
+        fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn poll_close(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+            Poll::Ready(Ok(()))
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Body, Cassette, Interaction, Recorder, Replayer};
+    use crate::client::ClientBuilder;
+    use crate::transport::{BoxFuture, Request, Response, Transport};
+    use reqwest::StatusCode;
+
+    /// Answers every request with an empty list of servers
+    #[derive(Debug)]
+    struct EmptyListTransport;
+
+    impl Transport for EmptyListTransport {
+        fn send(&self, _request: Request) -> BoxFuture<'_, crate::Result<Response>> {
+            Box::pin(async {
+                let mut response = Response::new(StatusCode::OK, r#"{"object":"list","data":[]}"#);
+                response
+                    .headers_mut()
+                    .insert("x-ratelimit-remaining", "59".parse().unwrap());
+                response.headers_mut().insert(
+                    "set-cookie",
+                    "pterodactyl_session=secret; path=/".parse().unwrap(),
+                );
+                Ok(response)
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_record_replay() {
+        let recorder = Recorder::with_transport(EmptyListTransport);
+        let client = ClientBuilder::new("https://panel.example.com", "key")
+            .with_transport(recorder.clone())
+            .build();
+        assert!(client.list_servers().await.unwrap().is_empty());
+
+        let cassette: Cassette =
+            serde_json::from_str(&serde_json::to_string(&recorder.cassette()).unwrap()).unwrap();
+        assert_eq!(
+            cassette.interactions,
+            [Interaction {
+                method: "GET".to_owned(),
+                path: "/api/client/".to_owned(),
+                body: None,
+                status: 200,
+                headers: [
+                    ("set-cookie".to_owned(), "<redacted>".to_owned()),
+                    ("x-ratelimit-remaining".to_owned(), "59".to_owned())
+                ]
+                .into(),
+                response: Body::Json(serde_json::json!({"object": "list", "data": []})),
+            }]
+        );
+
+        let replayer = Replayer::new(cassette);
+        let client = ClientBuilder::new("https://other.example.com", "key")
+            .with_transport(replayer.clone())
+            .build();
+        assert!(client.list_servers().await.unwrap().is_empty());
+        assert_eq!(replayer.remaining(), 0);
+        assert!(matches!(
+            client.list_servers().await,
+            Err(crate::Error::Transport(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_record_redacts_passwords() {
+        let recorder = Recorder::with_transport(EmptyListTransport);
+        let client = ClientBuilder::new("https://panel.example.com", "key")
+            .with_transport(recorder.clone())
+            .build();
+        client.update_password("hunter2", "hunter3").await.unwrap();
+        client
+            .update_email("user@example.com", "hunter3")
+            .await
+            .unwrap();
+
+        let cassette = recorder.cassette();
+        assert_eq!(
+            cassette.interactions[0].body,
+            Some(Body::Json(serde_json::json!({
+                "current_password": "<redacted>",
+                "password": "<redacted>",
+                "password_confirmation": "<redacted>",
+            })))
+        );
+        assert_eq!(
+            cassette.interactions[1].body,
+            Some(Body::Json(serde_json::json!({
+                "email": "user@example.com",
+                "password": "<redacted>",
+            })))
+        );
+    }
+
+    #[cfg(feature = "websocket")]
+    #[tokio::test]
+    async fn test_replay_websocket() {
+        use super::Frame;
+        use crate::client::websocket::{PteroWebSocketHandle, PteroWebSocketListener};
+        use crate::client::ServerState;
+
+        use std::sync::{Arc, Mutex};
+
+        #[derive(Default)]
+        struct Listener(Arc<Mutex<Vec<String>>>);
+
+        impl<H: PteroWebSocketHandle> PteroWebSocketListener<H> for Listener {
+            async fn on_ready(&mut self, handle: &mut H) -> crate::Result<()> {
+                handle.request_logs().await
+            }
+
+            async fn on_status(
+                &mut self,
+                _handle: &mut H,
+                status: ServerState,
+            ) -> crate::Result<()> {
+                self.0.lock().unwrap().push(format!("{status:?}"));
+                Ok(())
+            }
+
+            async fn on_console_output(
+                &mut self,
+                _handle: &mut H,
+                output: &str,
+            ) -> crate::Result<()> {
+                self.0.lock().unwrap().push(output.to_owned());
+                Ok(())
+            }
+        }
+
+        let replayer = Replayer::new(Cassette {
+            interactions: vec![Interaction {
+                method: "GET".to_owned(),
+                path: "/api/client/servers/1a7ce997/websocket".to_owned(),
+                body: None,
+                status: 200,
+                headers: Default::default(),
+                response: Body::Json(serde_json::json!({
+                    "data": {"token": "ws-token", "socket": "wss://node.example.com/api/servers/1a7ce997/ws"}
+                })),
+            }],
+            frames: vec![
+                Frame::Sent(r#"{"event":"auth","args":["<token>"]}"#.to_owned()),
+                Frame::Received(r#"{"event":"auth success"}"#.to_owned()),
+                Frame::Sent(r#"{"event":"send logs","args":[null]}"#.to_owned()),
+                Frame::Received(r#"{"event":"status","args":["running"]}"#.to_owned()),
+                Frame::Received(r#"{"event":"console output","args":["Done (1.2s)!"]}"#.to_owned()),
+            ],
+        });
+        let recorder = Recorder::with_transport(replayer.clone());
+        let client = ClientBuilder::new("https://panel.example.com", "key")
+            .with_transport(recorder.clone())
+            .build();
+        let events = Arc::default();
+        client
+            .get_server("1a7ce997")
+            .run_websocket_loop(
+                |url| replayer.connect_websocket(url),
+                Listener(Arc::clone(&events)),
+            )
+            .await
+            .unwrap();
+        assert_eq!(*events.lock().unwrap(), ["Running", "Done (1.2s)!"]);
+
+        let cassette = recorder.cassette();
+        assert_eq!(
+            cassette.interactions[0].response,
+            Body::Json(serde_json::json!({
+                "data": {"token": "<redacted>", "socket": "wss://node.example.com/api/servers/1a7ce997/ws"}
+            }))
+        );
+        assert!(!serde_json::to_string(&cassette)
+            .unwrap()
+            .contains("ws-token"));
+    }
+}
@@ -0,0 +1,240 @@
+//! The transport which sends HTTP requests to the panel. By default, requests are sent using
+//! [`reqwest`], but a custom [`Transport`] can be used instead, for example to return canned
+//! responses in tests:
+//!
+//! ```
+//! use pterodactyl_api::client::ClientBuilder;
+//! use pterodactyl_api::transport::{BoxFuture, Request, Response, Transport};
+//! use reqwest::StatusCode;
+//!
+//! #[derive(Debug)]
+//! struct FakeTransport;
+//!
+//! impl Transport for FakeTransport {
+//!     fn send(&self, request: Request) -> BoxFuture<'_, pterodactyl_api::Result<Response>> {
+//!         Box::pin(async move {
+//!             Ok(match request.url() {
+//!                 "https://panel.example.com/api/client/" => {
+//!                     Response::new(StatusCode::OK, r#"{"object":"list","data":[]}"#)
+//!                 }
+//!                 _ => Response::new(StatusCode::NOT_FOUND, ""),
+//!             })
+//!         })
+//!     }
+//! }
+//!
+//! # #[tokio::main(flavor = "current_thread")]
+//! # async fn main() -> pterodactyl_api::Result<()> {
+//! let client = ClientBuilder::new("https://panel.example.com", "key")
+//!     .with_transport(FakeTransport)
+//!     .build();
+//! assert!(client.list_servers().await?.is_empty());
+//! # Ok(())
+//! # }
+//! ```
+
+use bytes::Bytes;
+use reqwest::header::HeaderMap;
+use reqwest::{Body, Method, StatusCode};
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+
+/// A boxed future which can be sent between threads, returned by [`Transport::send`]
+pub type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
+
+/// Sends HTTP requests to the panel
+pub trait Transport: Send + Sync + fmt::Debug {
+    /// Sends the given request, and returns its response. Responses with unsuccessful status
+    /// codes should be returned as responses rather than errors, so that the error the panel
+    /// reported can be read from their body
+    fn send(&self, request: Request) -> BoxFuture<'_, crate::Result<Response>>;
+
+    /// Called with each text message sent or received over a server websocket opened by a client
+    /// using this transport. Does nothing by default
+    #[cfg(feature = "websocket")]
+    fn websocket_message(&self, _direction: MessageDirection, _message: &str) {}
+}
+
+/// Whether a websocket message was sent to or received from the panel
+#[cfg(feature = "websocket")]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum MessageDirection {
+    /// The message was sent to the panel
+    Sent,
+    /// The message was received from the panel
+    Received,
+}
+
+/// The default transport, which sends requests using a [`reqwest::Client`]
+#[derive(Debug, Clone, Default)]
+pub struct ReqwestTransport {
+    client: reqwest::Client,
+}
+
+impl ReqwestTransport {
+    /// Creates a transport which sends requests using the given client
+    pub fn new(client: reqwest::Client) -> Self {
+        Self { client }
+    }
+}
+
+impl Transport for ReqwestTransport {
+    fn send(&self, request: Request) -> BoxFuture<'_, crate::Result<Response>> {
+        Box::pin(async move {
+            let mut builder = self
+                .client
+                .request(request.method, request.url)
+                .headers(request.headers);
+            if let Some(body) = request.body {
+                builder = builder.body(body);
+            }
+            Ok(builder.send().await?.into())
+        })
+    }
+}
+
+/// An HTTP request to the panel
+#[derive(Debug)]
+pub struct Request {
+    method: Method,
+    url: String,
+    headers: HeaderMap,
+    body: Option<Body>,
+}
+
+impl Request {
+    /// Creates a request without any headers or body
+    pub fn new(method: Method, url: impl Into<String>) -> Self {
+        Self {
+            method,
+            url: url.into(),
+            headers: HeaderMap::new(),
+            body: None,
+        }
+    }
+
+    /// Sets the body of this request
+    pub fn with_body(self, body: impl Into<Body>) -> Self {
+        Self {
+            body: Some(body.into()),
+            ..self
+        }
+    }
+
+    /// Gets the method of this request
+    pub fn method(&self) -> &Method {
+        &self.method
+    }
+
+    /// Gets the full URL of this request, including the query string
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Gets the headers of this request
+    pub fn headers(&self) -> &HeaderMap {
+        &self.headers
+    }
+
+    /// Gets the headers of this request, to modify them
+    pub fn headers_mut(&mut self) -> &mut HeaderMap {
+        &mut self.headers
+    }
+
+    /// Gets the body of this request, or [`None`] if it has no body or the body is streamed
+    pub fn body(&self) -> Option<&[u8]> {
+        self.body.as_ref().and_then(Body::as_bytes)
+    }
+
+    /// Copies this request, or returns [`None`] if its body is streamed and can't be copied
+    pub(crate) fn try_clone(&self) -> Option<Self> {
+        let body = match &self.body {
+            Some(body) => Some(Body::from(Bytes::copy_from_slice(body.as_bytes()?))),
+            None => None,
+        };
+        Some(Self {
+            method: self.method.clone(),
+            url: self.url.clone(),
+            headers: self.headers.clone(),
+            body,
+        })
+    }
+}
+
+/// An HTTP response from the panel
+#[derive(Debug)]
+pub struct Response {
+    status: StatusCode,
+    headers: HeaderMap,
+    body: ResponseBody,
+}
+
+#[derive(Debug)]
+enum ResponseBody {
+    Bytes(Bytes),
+    Reqwest(reqwest::Response),
+}
+
+impl Response {
+    /// Creates a response with the given status code and body, without any headers
+    pub fn new(status: StatusCode, body: impl Into<Bytes>) -> Self {
+        Self {
+            status,
+            headers: HeaderMap::new(),
+            body: ResponseBody::Bytes(body.into()),
+        }
+    }
+
+    /// Gets the status code of this response
+    pub fn status(&self) -> StatusCode {
+        self.status
+    }
+
+    /// Gets the headers of this response
+    pub fn headers(&self) -> &HeaderMap {
+        &self.headers
+    }
+
+    /// Gets the headers of this response, to modify them
+    pub fn headers_mut(&mut self) -> &mut HeaderMap {
+        &mut self.headers
+    }
+
+    /// Reads the full body of this response
+    pub async fn bytes(self) -> crate::Result<Bytes> {
+        match self.body {
+            ResponseBody::Bytes(bytes) => Ok(bytes),
+            ResponseBody::Reqwest(response) => Ok(response.bytes().await?),
+        }
+    }
+
+    /// Reads the full body of this response as text
+    pub async fn text(self) -> crate::Result<String> {
+        match self.body {
+            ResponseBody::Bytes(bytes) => Ok(String::from_utf8_lossy(&bytes).into_owned()),
+            ResponseBody::Reqwest(response) => Ok(response.text().await?),
+        }
+    }
+
+    /// Streams the body of this response
+    #[cfg(feature = "stream")]
+    pub fn bytes_stream(self) -> impl futures_core::Stream<Item = reqwest::Result<Bytes>> {
+        use futures_util::future::Either;
+        use futures_util::stream;
+        match self.body {
+            ResponseBody::Bytes(bytes) => Either::Left(stream::once(async { Ok(bytes) })),
+            ResponseBody::Reqwest(response) => Either::Right(response.bytes_stream()),
+        }
+    }
+}
+
+impl From<reqwest::Response> for Response {
+    fn from(response: reqwest::Response) -> Self {
+        Self {
+            status: response.status(),
+            headers: response.headers().clone(),
+            body: ResponseBody::Reqwest(response),
+        }
+    }
+}